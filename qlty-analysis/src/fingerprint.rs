@@ -0,0 +1,196 @@
+use qlty_types::analysis::v1::Issue;
+
+/// Lines around the snippet which are included in the fingerprint, to tell apart
+/// identical snippets in the same file
+const FINGERPRINT_CONTEXT_LINES: usize = 2;
+
+/// Computes a stable identity for an issue from its rule, file and source text.
+///
+/// Line numbers are deliberately left out so that the fingerprint survives code
+/// being moved within a file, and whitespace is collapsed so that reindenting does
+/// not change it. Only the lines right around the snippet are used, so edits
+/// elsewhere in the file do not change it either. Issues without any source text
+/// fall back to their message.
+pub fn issue_fingerprint(issue: &Issue) -> String {
+    let mut context = md5::Context::new();
+
+    for part in [
+        issue.tool.as_str(),
+        issue.rule_key.as_str(),
+        &normalize_path(&issue.path().unwrap_or_default()),
+    ] {
+        context.consume(part.as_bytes());
+        context.consume(b"\0");
+    }
+
+    if issue.snippet.trim().is_empty() {
+        context.consume(normalize_whitespace(&issue.message).as_bytes());
+    } else {
+        context.consume(normalize_whitespace(&issue.snippet).as_bytes());
+        context.consume(b"\0");
+        context.consume(normalize_whitespace(&nearby_lines(issue)).as_bytes());
+    }
+
+    format!("{:x}", context.compute())
+}
+
+/// Narrows `snippet_with_context` down to the snippet and the few lines around it.
+/// The snippet may repeat within the context, so the occurrence closest to where
+/// the issue's start line places it is used.
+fn nearby_lines(issue: &Issue) -> String {
+    let context = issue.snippet_with_context.lines().collect::<Vec<_>>();
+    let snippet = issue.snippet.lines().collect::<Vec<_>>();
+
+    if snippet.is_empty() || snippet.len() > context.len() {
+        return "".to_string();
+    }
+
+    // The context extends equally before and after the snippet, unless it is cut
+    // short by the start of the file
+    let centered = (context.len() - snippet.len()) / 2;
+    let expected = match issue.range() {
+        Some(range) if range.start_line > 0 => centered.min(range.start_line as usize - 1),
+        _ => centered,
+    };
+
+    let start = (0..=context.len() - snippet.len())
+        .filter(|start| {
+            snippet
+                .iter()
+                .enumerate()
+                .all(|(offset, line)| context[start + offset].contains(line))
+        })
+        .min_by_key(|start| start.abs_diff(expected));
+
+    match start {
+        Some(start) => {
+            let first = start.saturating_sub(FINGERPRINT_CONTEXT_LINES);
+            let last = (start + snippet.len() + FINGERPRINT_CONTEXT_LINES).min(context.len());
+            context[first..last].join("\n")
+        }
+        None => "".to_string(),
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn issue(path: &str, line: u32, snippet: &str) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: "no-unused-vars".to_string(),
+            message: "'a' is assigned a value but never used.".to_string(),
+            snippet: snippet.to_string(),
+            snippet_with_context: format!("function main() {{\n{}\n}}", snippet),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    end_line: line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_lines_and_whitespace() {
+        let fingerprint = issue_fingerprint(&issue("src/main.js", 2, "  let a = 1;"));
+
+        assert_eq!(fingerprint.len(), 32);
+        assert_eq!(
+            fingerprint,
+            issue_fingerprint(&issue("./src/main.js", 20, "\tlet  a = 1;"))
+        );
+        assert_eq!(
+            fingerprint,
+            issue_fingerprint(&issue("src\\main.js", 2, "let a = 1;"))
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_issues() {
+        let fingerprint = issue_fingerprint(&issue("src/main.js", 2, "let a = 1;"));
+
+        assert_ne!(
+            fingerprint,
+            issue_fingerprint(&issue("src/other.js", 2, "let a = 1;"))
+        );
+        assert_ne!(
+            fingerprint,
+            issue_fingerprint(&issue("src/main.js", 2, "let b = 1;"))
+        );
+        assert_ne!(
+            fingerprint,
+            issue_fingerprint(&Issue {
+                rule_key: "prefer-const".to_string(),
+                ..issue("src/main.js", 2, "let a = 1;")
+            })
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_distant_lines() {
+        // Mirrors how snippets are extracted, with 10 lines of context either side
+        fn issue_in(lines: &[String], line: usize) -> Issue {
+            let first = line.saturating_sub(10).max(1);
+            let last = (line + 10).min(lines.len());
+
+            Issue {
+                snippet_with_context: lines[first - 1..last].join("\n"),
+                ..issue("src/main.js", line as u32, &lines[line - 1])
+            }
+        }
+
+        let mut lines = (1..=40)
+            .map(|line| format!("let a{} = {};", line, line))
+            .collect::<Vec<_>>();
+        lines[19] = "let unused = 1;".to_string();
+        lines[29] = "let unused = 1;".to_string();
+
+        let fingerprint = issue_fingerprint(&issue_in(&lines, 20));
+        let other_fingerprint = issue_fingerprint(&issue_in(&lines, 30));
+        assert_ne!(fingerprint, other_fingerprint);
+
+        lines.insert(16, "// a new comment".to_string());
+        assert_eq!(fingerprint, issue_fingerprint(&issue_in(&lines, 21)));
+        assert_eq!(other_fingerprint, issue_fingerprint(&issue_in(&lines, 31)));
+
+        lines[19] = "let changed = 1;".to_string();
+        assert_ne!(fingerprint, issue_fingerprint(&issue_in(&lines, 21)));
+    }
+
+    #[test]
+    fn test_fingerprint_without_snippet() {
+        let without_snippet = Issue {
+            tool: "exists".to_string(),
+            rule_key: "fail".to_string(),
+            message: "exists failed".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            issue_fingerprint(&without_snippet),
+            issue_fingerprint(&without_snippet.clone())
+        );
+        assert_ne!(
+            issue_fingerprint(&without_snippet),
+            issue_fingerprint(&Issue {
+                message: "exists failed again".to_string(),
+                ..without_snippet.clone()
+            })
+        );
+    }
+}
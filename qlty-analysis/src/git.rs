@@ -1,5 +1,7 @@
 mod diff;
 mod upstream;
+mod worktree;
 
 pub use diff::{DiffLineFilter, DiffMode, GitDiff};
 pub use upstream::compute_upstream;
+pub use worktree::{merge_base, Worktree};
@@ -1,6 +1,7 @@
+use super::merge_base;
 use crate::code::FileIndex;
-use anyhow::Result;
-use git2::{Diff, DiffOptions, Index, Repository};
+use anyhow::{Context, Result};
+use git2::{Commit, Diff, DiffOptions, Index, Repository, RevparseMode};
 use ignore::{DirEntry, Walk, WalkBuilder};
 use qlty_config::issue_transformer::IssueTransformer;
 use qlty_types::analysis::v1::Issue;
@@ -19,6 +20,7 @@ pub enum DiffMode {
     HeadToIndexFile(PathBuf),
     HeadToWorkdir,
     UpstreamToWorkdir(String),
+    Range(String),
 }
 
 #[derive(Debug, Clone)]
@@ -32,13 +34,15 @@ impl GitDiff {
         let repository = Repository::open(repository_root)?;
         let head_commit = repository.head()?.peel_to_commit()?;
 
-        let commit = match mode {
-            DiffMode::HeadToIndex | DiffMode::HeadToIndexFile(_) => head_commit,
-            DiffMode::HeadToWorkdir => head_commit,
+        let (commit, range_end) = match mode {
+            DiffMode::HeadToIndex | DiffMode::HeadToIndexFile(_) => (head_commit, None),
+            DiffMode::HeadToWorkdir => (head_commit, None),
             DiffMode::UpstreamToWorkdir(ref upstream_ref) => {
-                let upstream_head = repository.revparse_single(upstream_ref)?.peel_to_commit()?;
-                let merge_base = repository.merge_base(upstream_head.id(), head_commit.id())?;
-                repository.find_commit(merge_base)?
+                (merge_base(&repository, upstream_ref)?, None)
+            }
+            DiffMode::Range(ref range) => {
+                let (start, end) = Self::resolve_range(&repository, range)?;
+                (start, Some(end))
             }
         };
 
@@ -59,8 +63,8 @@ impl GitDiff {
                     Some(&mut diff_opts),
                 )?
             }
-            DiffMode::HeadToIndexFile(index_path) => {
-                let index = Index::open(&index_path)?;
+            DiffMode::HeadToIndexFile(ref index_path) => {
+                let index = Index::open(index_path)?;
 
                 repository.diff_tree_to_index(
                     Some(&commit.tree()?),
@@ -68,6 +72,11 @@ impl GitDiff {
                     Some(&mut diff_opts),
                 )?
             }
+            DiffMode::Range(_) => repository.diff_tree_to_tree(
+                Some(&commit.tree()?),
+                Some(&range_end.unwrap().tree()?),
+                Some(&mut diff_opts),
+            )?,
             _ => {
                 diff_opts.include_untracked(true);
 
@@ -92,6 +101,39 @@ impl GitDiff {
         })
     }
 
+    /// Resolves `A..B` to commits A and B, and `A...B` to the merge base of A and B
+    /// followed by B. The end of the range defaults to HEAD when omitted.
+    pub(crate) fn resolve_range<'repo>(
+        repository: &'repo Repository,
+        range: &str,
+    ) -> Result<(Commit<'repo>, Commit<'repo>)> {
+        let revspec = repository
+            .revparse(range)
+            .with_context(|| format!("Invalid commit range: {}", range))?;
+
+        let start = revspec
+            .from()
+            .with_context(|| format!("Invalid commit range: {}", range))?
+            .peel_to_commit()?;
+
+        let end = match revspec.to() {
+            Some(end) => end.peel_to_commit()?,
+            None => repository.head()?.peel_to_commit()?,
+        };
+
+        if revspec.mode().contains(RevparseMode::MERGE_BASE) {
+            let merge_base = repository.merge_base(start.id(), end.id())?;
+            Ok((repository.find_commit(merge_base)?, end))
+        } else {
+            Ok((start, end))
+        }
+    }
+
+    /// Lines added or modified in the diff, including entirely new files
+    pub fn added_lines(&self) -> &FileIndex {
+        &self.line_filter.index
+    }
+
     fn plus_lines_index(diff: &git2::Diff, repo_path: PathBuf) -> Result<FileIndex> {
         let index = Rc::new(RefCell::new(FileIndex::new()));
 
@@ -355,7 +397,7 @@ impl IssueTransformer for DiffLineFilter {
 mod test {
     use super::*;
     use itertools::Itertools;
-    use qlty_test_utilities::git::sample_repo;
+    use qlty_test_utilities::git::{sample_repo, sample_repo_feature_branch};
     use std::fs;
     use std::path::PathBuf;
 
@@ -430,4 +472,38 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_range_ignores_working_tree() -> Result<()> {
+        let (td, _repo) = sample_repo_feature_branch();
+        fs::write(td.path().join("untracked.rb"), "puts 'untracked'").unwrap();
+
+        for range in ["main..feature", "main...HEAD", "main"] {
+            let git_diff = GitDiff::compute(DiffMode::Range(range.to_string()), td.path())?;
+
+            assert_eq!(
+                git_diff
+                    .changed_files
+                    .iter()
+                    .cloned()
+                    .sorted()
+                    .collect::<Vec<PathBuf>>(),
+                [
+                    PathBuf::from("greetings.rb"),
+                    PathBuf::from("lib/hello.rb"),
+                    PathBuf::from("new_feature.rb"),
+                ]
+            );
+            assert!(git_diff
+                .added_lines()
+                .matches_line_range(&PathBuf::from("lib/hello.rb"), 2..=2));
+            assert!(!git_diff
+                .added_lines()
+                .matches_path(&PathBuf::from("untracked.rb")));
+        }
+
+        assert!(GitDiff::compute(DiffMode::Range("missing..HEAD".to_string()), td.path()).is_err());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,155 @@
+use super::GitDiff;
+use anyhow::{bail, Context, Result};
+use git2::{Commit, Repository};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+use tracing::{debug, warn};
+
+/// The commit where the current HEAD diverged from the upstream ref
+pub fn merge_base<'repo>(
+    repository: &'repo Repository,
+    upstream_ref: &str,
+) -> Result<Commit<'repo>> {
+    let head_commit = repository.head()?.peel_to_commit()?;
+    let upstream_head = repository.revparse_single(upstream_ref)?.peel_to_commit()?;
+    let merge_base = repository.merge_base(upstream_head.id(), head_commit.id())?;
+    Ok(repository.find_commit(merge_base)?)
+}
+
+/// A detached Git worktree in a temporary directory, removed when dropped
+#[derive(Debug)]
+pub struct Worktree {
+    repository_root: PathBuf,
+    path: PathBuf,
+    _tempdir: TempDir,
+}
+
+impl Worktree {
+    pub fn checkout(repository_root: &Path, commit: &str) -> Result<Self> {
+        let tempdir = tempfile::Builder::new()
+            .prefix("qlty-worktree-")
+            .tempdir()?;
+        let path = tempdir.path().join("worktree");
+
+        debug!("Adding worktree for {} at {}", commit, path.display());
+        Self::git(
+            repository_root,
+            &[
+                "worktree",
+                "add",
+                "--detach",
+                &path.to_string_lossy(),
+                commit,
+            ],
+        )?;
+
+        Ok(Self {
+            repository_root: repository_root.to_path_buf(),
+            path: path.canonicalize().unwrap_or(path),
+            _tempdir: tempdir,
+        })
+    }
+
+    /// Checks out the end of a commit range such as `v1.0..v1.1`, so that what
+    /// landed in the range can be analyzed without touching the working tree
+    pub fn checkout_range(repository_root: &Path, range: &str) -> Result<Self> {
+        let repository = Repository::open(repository_root)?;
+        let (_, end) = GitDiff::resolve_range(&repository, range)?;
+        Self::checkout(repository_root, &end.id().to_string())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn git(repository_root: &Path, args: &[&str]) -> Result<()> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repository_root)
+            .output()
+            .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+
+        if !output.status.success() {
+            bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(err) = Self::git(
+            &self.repository_root,
+            &[
+                "worktree",
+                "remove",
+                "--force",
+                &self.path.to_string_lossy(),
+            ],
+        ) {
+            warn!("Failed to remove worktree {}: {}", self.path.display(), err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::git::DiffMode;
+    use qlty_test_utilities::git::sample_repo_feature_branch;
+    use std::fs;
+
+    #[test]
+    fn test_worktree_at_merge_base() {
+        let (td, repo) = sample_repo_feature_branch();
+        let commit = merge_base(&repo, "main").unwrap();
+
+        let worktree = Worktree::checkout(td.path(), &commit.id().to_string()).unwrap();
+        let path = worktree.path().to_path_buf();
+
+        assert!(path.join("lib/hello.rb").exists());
+        assert!(!path.join("new_feature.rb").exists());
+        assert_ne!(
+            fs::read_to_string(path.join("lib/hello.rb")).unwrap(),
+            fs::read_to_string(td.path().join("lib/hello.rb")).unwrap()
+        );
+
+        drop(worktree);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_range_in_worktree() {
+        let (td, repo) = sample_repo_feature_branch();
+        let range = "feature~1..feature";
+
+        // Check out a different commit and leave changes in the working tree
+        repo.set_head("refs/heads/main").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            .unwrap();
+        fs::write(td.path().join("greetings.rb"), "puts 'uncommitted'").unwrap();
+
+        let worktree = Worktree::checkout_range(td.path(), range).unwrap();
+        let git_diff =
+            GitDiff::compute(DiffMode::Range(range.to_string()), worktree.path()).unwrap();
+
+        assert!(git_diff
+            .changed_files
+            .contains(&PathBuf::from("new_feature.rb")));
+        assert!(worktree.path().join("new_feature.rb").exists());
+        assert!(!td.path().join("new_feature.rb").exists());
+        assert_eq!(
+            fs::read_to_string(worktree.path().join("greetings.rb")).unwrap(),
+            "puts 'new feature'"
+        );
+        assert!(git_diff
+            .added_lines()
+            .matches_line_range(&PathBuf::from("lib/hello.rb"), 2..=2));
+    }
+}
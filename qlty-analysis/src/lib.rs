@@ -2,6 +2,7 @@ use serde::Serialize;
 
 pub mod cache;
 pub mod code;
+pub mod fingerprint;
 pub mod git;
 pub mod lang;
 mod report;
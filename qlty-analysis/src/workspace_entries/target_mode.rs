@@ -11,6 +11,7 @@ pub enum TargetMode {
     HeadDiff,
     Index,
     IndexFile(PathBuf),
+    Range(String),
 }
 
 impl TargetMode {
@@ -20,6 +21,7 @@ impl TargetMode {
             TargetMode::IndexFile(index_path) => DiffMode::HeadToIndexFile(index_path.clone()),
             TargetMode::HeadDiff => DiffMode::HeadToWorkdir,
             TargetMode::UpstreamDiff(upstream) => DiffMode::UpstreamToWorkdir(upstream.to_string()),
+            TargetMode::Range(range) => DiffMode::Range(range.to_string()),
             _ => panic!("diff_mode() called on {:?}", self),
         }
     }
@@ -31,6 +33,7 @@ impl TargetMode {
                 | TargetMode::IndexFile(_)
                 | TargetMode::HeadDiff
                 | TargetMode::UpstreamDiff(_)
+                | TargetMode::Range(_)
         )
     }
 }
@@ -44,7 +44,7 @@ impl WorkspaceEntryFinderBuilder {
 
     pub fn diff_line_filter(&mut self) -> Result<Box<dyn IssueTransformer>> {
         match self.mode {
-            TargetMode::HeadDiff | TargetMode::UpstreamDiff(_) => {
+            TargetMode::HeadDiff | TargetMode::UpstreamDiff(_) | TargetMode::Range(_) => {
                 Ok(Box::new(self.git_diff()?.line_filter))
             }
             _ => Ok(Box::new(NullIssueTransformer)),
@@ -59,7 +59,7 @@ impl WorkspaceEntryFinderBuilder {
                 // Use absolute paths, so when running in a subdirectory, the paths are still correct
                 self.paths.iter().map(|p| self.root.join(p)).collect(),
             ))),
-            TargetMode::UpstreamDiff(_) => Ok(Arc::new(DiffSource::new(
+            TargetMode::UpstreamDiff(_) | TargetMode::Range(_) => Ok(Arc::new(DiffSource::new(
                 self.git_diff()?.changed_files,
                 &self.root,
             ))),
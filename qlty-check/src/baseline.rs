@@ -0,0 +1,256 @@
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use qlty_analysis::fingerprint::issue_fingerprint;
+use qlty_config::config::issue_transformer::IssueTransformer;
+use qlty_types::analysis::v1::Issue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// A recorded set of known issues which are suppressed from check results
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    path: PathBuf,
+    entries: HashMap<String, BaselineEntry>,
+    matches: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    issues: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub path: String,
+    pub tool: String,
+    pub rule_key: String,
+    pub fingerprint: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BaselineSummary {
+    pub suppressed: usize,
+    pub stale: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline file: {}", path.display()))?;
+
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse baseline file: {}", path.display()))?;
+
+        Ok(Self::new(path, file.issues))
+    }
+
+    /// Builds a baseline in memory from issues which are already known
+    pub fn from_issues(issues: &[Issue]) -> Self {
+        Self::new(Path::new(""), Self::entries(issues))
+    }
+
+    fn new(path: &Path, entries: Vec<BaselineEntry>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            entries: entries
+                .into_iter()
+                .map(|entry| (entry.fingerprint.clone(), entry))
+                .collect(),
+            matches: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn write(path: &Path, issues: &[Issue]) -> Result<usize> {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            issues: Self::entries(issues),
+        };
+
+        let mut json = serde_json::to_string_pretty(&file)?;
+        json.push('\n');
+
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write baseline file: {}", path.display()))?;
+
+        Ok(file.issues.len())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn summary(&self) -> BaselineSummary {
+        let matches = self.matches.lock().unwrap();
+
+        let stale = self
+            .entries
+            .values()
+            .filter_map(|entry| {
+                let matched = matches.get(&entry.fingerprint).copied().unwrap_or_default();
+
+                if matched < entry.count {
+                    Some(BaselineEntry {
+                        count: entry.count - matched,
+                        ..entry.clone()
+                    })
+                } else {
+                    None
+                }
+            })
+            .sorted()
+            .collect();
+
+        BaselineSummary {
+            suppressed: matches.values().sum(),
+            stale,
+        }
+    }
+
+    fn entries(issues: &[Issue]) -> Vec<BaselineEntry> {
+        let mut entries: HashMap<String, BaselineEntry> = HashMap::new();
+
+        for issue in issues {
+            let fingerprint = fingerprint(issue);
+
+            entries
+                .entry(fingerprint.clone())
+                .or_insert_with(|| BaselineEntry {
+                    fingerprint,
+                    tool: issue.tool.clone(),
+                    rule_key: issue.rule_key.clone(),
+                    path: issue.path().unwrap_or_default(),
+                    count: 0,
+                })
+                .count += 1;
+        }
+
+        entries.into_values().sorted().collect()
+    }
+}
+
+impl IssueTransformer for Baseline {
+    fn initialize(&self) {
+        self.matches.lock().unwrap().clear();
+    }
+
+    fn transform(&self, issue: Issue) -> Option<Issue> {
+        let fingerprint = fingerprint(&issue);
+
+        if let Some(entry) = self.entries.get(&fingerprint) {
+            let mut matches = self.matches.lock().unwrap();
+            let matched = matches.entry(fingerprint).or_default();
+
+            if *matched < entry.count {
+                *matched += 1;
+                return None;
+            }
+        }
+
+        Some(issue)
+    }
+
+    fn clone_box(&self) -> Box<dyn IssueTransformer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Issues from a check run are already fingerprinted by the processor
+fn fingerprint(issue: &Issue) -> String {
+    if issue.fingerprint.is_empty() {
+        issue_fingerprint(issue)
+    } else {
+        issue.fingerprint.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+    use tempfile::tempdir;
+
+    fn issue(rule_key: &str, line: u32, snippet: &str) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of {}", rule_key),
+            snippet: snippet.to_string(),
+            location: Some(Location {
+                path: "src/index.js".to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    end_line: line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_baseline_suppresses_shifted_issues() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+
+        let recorded = vec![
+            issue("no-unused-vars", 3, "let a = 1;"),
+            issue("no-unused-vars", 9, "let a = 1;"),
+            issue("eqeqeq", 12, "if (a == b) {"),
+        ];
+        assert_eq!(Baseline::write(&path, &recorded).unwrap(), 2);
+
+        let baseline = Baseline::read(&path).unwrap();
+        baseline.initialize();
+
+        // Shifted lines and reindented code still match
+        assert!(baseline
+            .transform(issue("no-unused-vars", 5, "  let  a = 1;"))
+            .is_none());
+        assert!(baseline
+            .transform(issue("no-unused-vars", 11, "let a = 1;"))
+            .is_none());
+
+        // Only as many occurrences as were recorded are suppressed
+        assert!(baseline
+            .transform(issue("no-unused-vars", 20, "let a = 1;"))
+            .is_some());
+        assert!(baseline
+            .transform(issue("no-unused-vars", 5, "let b = 2;"))
+            .is_some());
+
+        let summary = baseline.summary();
+        assert_eq!(summary.suppressed, 2);
+        assert_eq!(summary.stale.len(), 1);
+        assert_eq!(summary.stale[0].rule_key, "eqeqeq");
+        assert_eq!(summary.stale[0].count, 1);
+
+        baseline.initialize();
+        assert_eq!(baseline.summary().suppressed, 0);
+    }
+
+    #[test]
+    fn test_baseline_from_issues() {
+        let baseline = Baseline::from_issues(&[issue("eqeqeq", 12, "if (a == b) {")]);
+        baseline.initialize();
+
+        assert!(baseline
+            .transform(issue("eqeqeq", 40, "if (a == b) {"))
+            .is_none());
+        assert!(baseline
+            .transform(issue("eqeqeq", 41, "if (a == c) {"))
+            .is_some());
+        assert_eq!(baseline.summary().suppressed, 1);
+    }
+
+    #[test]
+    fn test_baseline_read_missing_file() {
+        let dir = tempdir().unwrap();
+        assert!(Baseline::read(&dir.path().join("missing.json")).is_err());
+    }
+}
@@ -14,6 +14,9 @@ use std::sync::Arc;
 use std::{collections::HashMap, fmt::Debug, path::PathBuf};
 use tracing::trace;
 
+mod remote;
+pub use remote::{DirectoryCache, HttpCache, RemoteCache};
+
 #[derive(Debug, Clone)]
 pub struct IssueCache {
     pub cache: Box<dyn Cache>,
@@ -24,7 +27,11 @@ impl IssueCache {
         Self { cache }
     }
 
-    pub fn read(&self, cache_key: &IssuesCacheKey) -> Result<Option<IssuesCacheHit>> {
+    pub fn read(
+        &self,
+        cache_key: &IssuesCacheKey,
+        invocation_label: &str,
+    ) -> Result<Option<IssuesCacheHit>> {
         trace!(
             "IssueCache read for {}: {:?}",
             cache_key.hexdigest(),
@@ -36,6 +43,7 @@ impl IssueCache {
             Some(contents) => Ok(Some(IssuesCacheHit {
                 cache_key: cache_key.clone(),
                 issues: self.contents_to_issues(&contents),
+                invocation_label: invocation_label.to_string(),
             })),
             None => Ok(None),
         }
@@ -89,6 +97,8 @@ impl IssueCache {
 pub struct IssuesCacheHit {
     pub cache_key: IssuesCacheKey,
     pub issues: Vec<Issue>,
+    /// The plugin and driver which would have run
+    pub invocation_label: String,
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +116,7 @@ struct InvocationCacheKey {
     driver_name: String,
     affects_cache: HashMap<PathBuf, String>,
     configs: Arc<Vec<PluginConfigFile>>,
+    workspace_root: Option<PathBuf>,
 }
 
 impl InvocationCacheKey {
@@ -244,11 +255,18 @@ impl InvocationCacheKey {
         );
 
         digest.add("qlty_version", &self.qlty_version);
-        digest.add("tool", &self.tool.directory());
+        // Keys are shared through remote caches, so avoid paths which differ between machines
+        digest.add("tool.name", &self.tool.name());
+        digest.add("tool.version", &self.tool.version().unwrap_or_default());
+        digest.add("tool.fingerprint", &self.tool.fingerprint());
         digest.add("driver_name", &self.driver_name);
 
         for config in self.configs.clone().iter().sorted() {
-            digest.add(&config.path.to_string_lossy(), &config.contents);
+            let path = match &self.workspace_root {
+                Some(root) => config.path.strip_prefix(root).unwrap_or(&config.path),
+                None => &config.path,
+            };
+            digest.add(&path.to_string_lossy(), &config.contents);
         }
 
         for (path, contents) in self.affects_cache.iter().sorted() {
@@ -275,9 +293,10 @@ impl IssuesCacheKey {
             cache_busters.insert(path, contents);
         }
 
+        let workspace = Workspace::new().ok();
         let mut repository_tree_sha: Option<String> = None;
         let mut dirty_paths = Vec::new();
-        if let Ok(repository) = Workspace::new().and_then(|w| w.repo()) {
+        if let Some(Ok(repository)) = workspace.as_ref().map(|w| w.repo()) {
             repository_tree_sha = Self::repository_sha(&repository).ok();
             dirty_paths = Self::collect_dirty_paths(&repository);
         }
@@ -292,6 +311,7 @@ impl IssuesCacheKey {
                 driver_name,
                 affects_cache: cache_busters,
                 configs,
+                workspace_root: workspace.map(|w| w.root),
             }
             .build(),
         }
@@ -360,3 +380,77 @@ impl CacheKey for IssuesCacheKey {
         self.digest.hexdigest()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tool::ToolType;
+    use qlty_config::config::DriverDef;
+
+    #[derive(Debug, Clone)]
+    struct RootedTool {
+        tools_root: String,
+    }
+
+    impl Tool for RootedTool {
+        fn name(&self) -> String {
+            "rooted".to_string()
+        }
+
+        fn version(&self) -> Option<String> {
+            Some("1.0.0".to_string())
+        }
+
+        fn tool_type(&self) -> ToolType {
+            ToolType::Runtime
+        }
+
+        fn version_command(&self) -> Option<String> {
+            None
+        }
+
+        fn parent_directory(&self) -> String {
+            format!("{}/{}", self.tools_root, self.name())
+        }
+
+        fn clone_box(&self) -> Box<dyn Tool> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn invocation_key(tools_root: &str, workspace_root: &str) -> String {
+        let mut plugin = PluginDef::default();
+        plugin
+            .drivers
+            .insert("lint".to_string(), DriverDef::default());
+
+        let configs = vec![PluginConfigFile {
+            path: PathBuf::from(workspace_root).join(".rooted.yml"),
+            contents: "strict: true".to_string(),
+        }];
+
+        let mut digest = InvocationCacheKey {
+            qlty_version: QLTY_VERSION.to_string(),
+            tool: Box::new(RootedTool {
+                tools_root: tools_root.to_string(),
+            }),
+            plugin: Arc::new(plugin),
+            driver_name: "lint".to_string(),
+            affects_cache: HashMap::new(),
+            configs: Arc::new(configs),
+            workspace_root: Some(PathBuf::from(workspace_root)),
+        }
+        .build();
+
+        digest.finalize();
+        digest.hexdigest()
+    }
+
+    #[test]
+    fn test_invocation_key_is_machine_independent() {
+        assert_eq!(
+            invocation_key("/home/alice/.qlty/cache/tools", "/home/alice/project"),
+            invocation_key("/Users/bob/.qlty/cache/tools", "/builds/project")
+        );
+    }
+}
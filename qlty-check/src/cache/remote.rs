@@ -0,0 +1,541 @@
+use anyhow::{bail, Context, Result};
+use qlty_analysis::cache::{Cache, CacheKey};
+use qlty_config::config::CacheConfig;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use std::{fs, io};
+use tempfile::NamedTempFile;
+use tracing::{debug, trace, warn};
+use url::Url;
+
+const REMOTE_ENV_VAR: &str = "QLTY_CACHE_REMOTE";
+const READ_ONLY_ENV_VAR: &str = "QLTY_CACHE_READ_ONLY";
+const TOKEN_ENV_VAR: &str = "QLTY_CACHE_TOKEN";
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Layers a remote cache behind a local one. Reads fall through to the remote
+/// on a local miss and populate the local cache, and writes go to both.
+/// The first remote error is logged and the remote is skipped from then on,
+/// so an unreachable server degrades to local caching instead of failing.
+#[derive(Debug, Clone)]
+pub struct RemoteCache {
+    local: Box<dyn Cache>,
+    remote: Box<dyn Cache>,
+    read_only: bool,
+    available: Arc<AtomicBool>,
+}
+
+impl RemoteCache {
+    pub fn new(local: Box<dyn Cache>, remote: Box<dyn Cache>, read_only: bool) -> Self {
+        Self {
+            local,
+            remote,
+            read_only,
+            available: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Wraps the local cache with the remote cache configured in `qlty.toml`,
+    /// or by the `QLTY_CACHE_REMOTE` and `QLTY_CACHE_READ_ONLY` variables.
+    /// `QLTY_CACHE_TOKEN` is sent to HTTP servers as a bearer token.
+    pub fn from_config(
+        config: &CacheConfig,
+        root: &Path,
+        local: Box<dyn Cache>,
+        extension: &str,
+    ) -> Result<Box<dyn Cache>> {
+        let remote = std::env::var(REMOTE_ENV_VAR)
+            .ok()
+            .filter(|remote| !remote.is_empty())
+            .or_else(|| config.remote.clone());
+
+        let Some(remote) = remote else {
+            return Ok(local);
+        };
+
+        let read_only = match std::env::var(READ_ONLY_ENV_VAR) {
+            Ok(value) => value == "1" || value.eq_ignore_ascii_case("true"),
+            Err(_) => config.read_only,
+        };
+
+        let timeout = Duration::from_secs(config.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        let token = std::env::var(TOKEN_ENV_VAR).ok();
+
+        Ok(Box::new(Self::new(
+            local,
+            remote_backend(&remote, root, extension, timeout, token)?,
+            read_only,
+        )))
+    }
+
+    fn disable(&self, error: anyhow::Error) {
+        if self.available.swap(false, Ordering::SeqCst) {
+            warn!(
+                "Remote cache is unavailable, continuing with the local cache: {:?}",
+                error
+            );
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        self.available.load(Ordering::SeqCst)
+    }
+}
+
+impl Cache for RemoteCache {
+    fn read(&self, key: &dyn CacheKey) -> Result<Option<Vec<u8>>> {
+        if let Some(contents) = self.local.read(key)? {
+            return Ok(Some(contents));
+        }
+
+        if !self.is_available() {
+            return Ok(None);
+        }
+
+        match self.remote.read(key) {
+            Ok(Some(contents)) => {
+                debug!("Remote cache hit: {:?}", &key.hexdigest());
+
+                if let Err(error) = self.local.write(key, &contents) {
+                    warn!("Failed to store remote cache hit locally: {:?}", error);
+                }
+
+                Ok(Some(contents))
+            }
+            Ok(None) => Ok(None),
+            Err(error) => {
+                self.disable(error);
+                Ok(None)
+            }
+        }
+    }
+
+    fn write(&self, key: &dyn CacheKey, value: &[u8]) -> Result<()> {
+        self.local.write(key, value)?;
+
+        if !self.read_only && self.is_available() {
+            if let Err(error) = self.remote.write(key, value) {
+                self.disable(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn path(&self, key: &dyn CacheKey) -> PathBuf {
+        self.local.path(key)
+    }
+
+    /// Only the local cache is cleared, as the remote is shared with other machines
+    fn clear(&self) -> Result<()> {
+        self.local.clear()
+    }
+
+    fn clone_box(&self) -> Box<dyn Cache> {
+        Box::new(self.clone())
+    }
+}
+
+/// Stores entries on a server at `<url>/<digest>.<extension>` using plain GET
+/// and PUT requests, as supported by nginx with WebDAV enabled
+#[derive(Clone)]
+pub struct HttpCache {
+    url: Url,
+    extension: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl std::fmt::Debug for HttpCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("HttpCache")
+            .field("url", &self.url.as_str())
+            .field("extension", &self.extension)
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .finish_non_exhaustive()
+    }
+}
+
+impl HttpCache {
+    pub fn new(url: Url, extension: &str, timeout: Duration, token: Option<String>) -> Self {
+        let mut url = url;
+
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+
+        Self {
+            url,
+            extension: extension.to_string(),
+            token,
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+        }
+    }
+
+    fn url(&self, key: &dyn CacheKey) -> Result<Url> {
+        Ok(self
+            .url
+            .join(&format!("{}.{}", key.hexdigest(), self.extension))?)
+    }
+
+    fn request(&self, method: &str, url: &Url) -> ureq::Request {
+        let request = self.agent.request_url(method, url);
+
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+            None => request,
+        }
+    }
+}
+
+impl Cache for HttpCache {
+    fn read(&self, key: &dyn CacheKey) -> Result<Option<Vec<u8>>> {
+        let url = self.url(key)?;
+        trace!("HttpCache read: {}", url);
+
+        match self.request("GET", &url).call() {
+            Ok(response) => {
+                let mut contents = vec![];
+                response.into_reader().read_to_end(&mut contents)?;
+                Ok(Some(contents))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("Failed to read {}", url)),
+        }
+    }
+
+    fn write(&self, key: &dyn CacheKey, value: &[u8]) -> Result<()> {
+        let url = self.url(key)?;
+        trace!("HttpCache write: {} ({} bytes)", url, value.len());
+
+        self.request("PUT", &url)
+            .send_bytes(value)
+            .with_context(|| format!("Failed to write {}", url))?;
+
+        Ok(())
+    }
+
+    fn path(&self, key: &dyn CacheKey) -> PathBuf {
+        PathBuf::from(format!("{}.{}", key.hexdigest(), self.extension))
+    }
+
+    fn clear(&self) -> Result<()> {
+        bail!("Clearing an HTTP cache is not supported")
+    }
+
+    fn clone_box(&self) -> Box<dyn Cache> {
+        Box::new(self.clone())
+    }
+}
+
+/// Stores entries in a directory which may be shared between machines, such
+/// as an NFS mount. Entries are written to a temporary file and then renamed,
+/// so concurrent readers never see a partially written entry.
+#[derive(Debug, Clone)]
+pub struct DirectoryCache {
+    pub root: PathBuf,
+    pub extension: String,
+}
+
+impl DirectoryCache {
+    pub fn new(root: PathBuf, extension: &str) -> Self {
+        Self {
+            root,
+            extension: extension.to_string(),
+        }
+    }
+}
+
+impl Cache for DirectoryCache {
+    fn read(&self, key: &dyn CacheKey) -> Result<Option<Vec<u8>>> {
+        let path = self.path(key);
+        trace!("DirectoryCache read: {}", path.display());
+
+        match fs::read(&path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn write(&self, key: &dyn CacheKey, value: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        trace!(
+            "DirectoryCache write: {} ({} bytes)",
+            path.display(),
+            value.len()
+        );
+
+        fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create {}", self.root.display()))?;
+
+        let mut file = NamedTempFile::new_in(&self.root)?;
+        io::Write::write_all(&mut file, value)?;
+        file.persist(&path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(())
+    }
+
+    fn path(&self, key: &dyn CacheKey) -> PathBuf {
+        let mut path = self.root.join(key.hexdigest());
+        path.set_extension(&self.extension);
+        path
+    }
+
+    fn clear(&self) -> Result<()> {
+        bail!("Clearing a shared directory cache is not supported")
+    }
+
+    fn clone_box(&self) -> Box<dyn Cache> {
+        Box::new(self.clone())
+    }
+}
+
+/// Builds the backend for a remote, which is either an HTTP(S) URL, a `file://`
+/// URL or a directory path relative to the workspace root
+fn remote_backend(
+    remote: &str,
+    root: &Path,
+    extension: &str,
+    timeout: Duration,
+    token: Option<String>,
+) -> Result<Box<dyn Cache>> {
+    match Url::parse(remote) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {
+            Ok(Box::new(HttpCache::new(url, extension, timeout, token)))
+        }
+        Ok(url) if url.scheme() == "file" => match url.to_file_path() {
+            Ok(path) => Ok(Box::new(DirectoryCache::new(path, extension))),
+            Err(_) => bail!("Invalid remote cache directory: {}", remote),
+        },
+        // Windows paths such as `C:\cache` parse as URLs with a single letter scheme
+        Ok(url) if url.scheme().len() > 1 => {
+            bail!("Unsupported remote cache URL scheme: {}", url.scheme())
+        }
+        _ => Ok(Box::new(DirectoryCache::new(root.join(remote), extension))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_analysis::cache::{FilesystemCache, HashDigest};
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    type Entries = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// A minimal stand-in for a WebDAV server, storing PUT bodies in memory
+    fn start_server() -> (Url, Entries) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/qlty", listener.local_addr().unwrap())).unwrap();
+        let entries: Entries = Arc::new(Mutex::new(HashMap::new()));
+        let server_entries = entries.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let entries = server_entries.clone();
+                std::thread::spawn(move || serve(stream.unwrap(), entries));
+            }
+        });
+
+        (url, entries)
+    }
+
+    fn serve(stream: TcpStream, entries: Entries) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+
+        loop {
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                return;
+            }
+
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap().to_string();
+            let path = parts.next().unwrap().to_string();
+
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+
+                if header.trim().is_empty() {
+                    break;
+                }
+
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, body) = match method.as_str() {
+                "PUT" => {
+                    entries.lock().unwrap().insert(path, body);
+                    ("201 Created", vec![])
+                }
+                _ => match entries.lock().unwrap().get(&path) {
+                    Some(contents) => ("200 OK", contents.clone()),
+                    None => ("404 Not Found", vec![]),
+                },
+            };
+
+            write!(
+                writer,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n",
+                status,
+                body.len()
+            )
+            .unwrap();
+            writer.write_all(&body).unwrap();
+        }
+    }
+
+    fn key(value: &str) -> HashDigest {
+        let mut digest = HashDigest::new();
+        digest.add("key", value);
+        digest.finalize();
+        digest
+    }
+
+    fn local_cache() -> (tempfile::TempDir, Box<dyn Cache>) {
+        let dir = tempdir().unwrap();
+        let cache = FilesystemCache::new(dir.path().to_path_buf(), "protos");
+        (dir, Box::new(cache))
+    }
+
+    fn http_cache(url: Url) -> HttpCache {
+        HttpCache::new(url, "protos", Duration::from_secs(5), None)
+    }
+
+    #[test]
+    fn test_http_cache() {
+        let (url, entries) = start_server();
+        let cache = http_cache(url);
+
+        assert_eq!(cache.read(&key("a")).unwrap(), None);
+
+        cache.write(&key("a"), b"issues").unwrap();
+        assert_eq!(cache.read(&key("a")).unwrap(), Some(b"issues".to_vec()));
+
+        let path = format!("/qlty/{}.protos", key("a").hexdigest());
+        assert!(entries.lock().unwrap().contains_key(&path));
+    }
+
+    #[test]
+    fn test_http_cache_debug_redacts_token() {
+        let url = Url::parse("http://localhost/qlty").unwrap();
+        let cache = HttpCache::new(url, "protos", Duration::from_secs(5), Some("s3cret".into()));
+        let debug = format!("{:?}", cache);
+
+        assert!(!debug.contains("s3cret"));
+        assert!(debug.contains("[REDACTED]"));
+    }
+
+    #[test]
+    fn test_read_through_and_write_back() {
+        let (url, entries) = start_server();
+        let (_dir, local) = local_cache();
+        let cache = RemoteCache::new(local.clone(), Box::new(http_cache(url.clone())), false);
+
+        http_cache(url)
+            .write(&key("remote"), b"from remote")
+            .unwrap();
+
+        assert_eq!(
+            cache.read(&key("remote")).unwrap(),
+            Some(b"from remote".to_vec())
+        );
+        assert_eq!(
+            local.read(&key("remote")).unwrap(),
+            Some(b"from remote".to_vec())
+        );
+
+        cache.write(&key("local"), b"from local").unwrap();
+        assert_eq!(entries.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_read_only() {
+        let (url, entries) = start_server();
+        let (_dir, local) = local_cache();
+        let cache = RemoteCache::new(local.clone(), Box::new(http_cache(url)), true);
+
+        cache.write(&key("a"), b"issues").unwrap();
+
+        assert_eq!(cache.read(&key("a")).unwrap(), Some(b"issues".to_vec()));
+        assert!(entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_remote() {
+        // Bind and drop a listener to find a port with nothing listening on it
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let url = Url::parse(&format!("http://{}/", address)).unwrap();
+
+        let (_dir, local) = local_cache();
+        let cache = RemoteCache::new(local.clone(), Box::new(http_cache(url)), false);
+
+        assert_eq!(cache.read(&key("a")).unwrap(), None);
+        assert!(!cache.is_available());
+
+        cache.write(&key("a"), b"issues").unwrap();
+        assert_eq!(local.read(&key("a")).unwrap(), Some(b"issues".to_vec()));
+    }
+
+    #[test]
+    fn test_directory_cache() {
+        let dir = tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        let (_local_dir, local) = local_cache();
+
+        let remote = remote_backend(
+            &format!("{}", Url::from_directory_path(&shared).unwrap()),
+            dir.path(),
+            "protos",
+            Duration::from_secs(1),
+            None,
+        )
+        .unwrap();
+        let cache = RemoteCache::new(local, remote, false);
+
+        cache.write(&key("a"), b"issues").unwrap();
+        assert_eq!(
+            fs::read(shared.join(format!("{}.protos", key("a").hexdigest()))).unwrap(),
+            b"issues"
+        );
+    }
+
+    #[test]
+    fn test_remote_backend() {
+        let root = Path::new("/repo");
+        let backend = |remote: &str| {
+            remote_backend(remote, root, "protos", Duration::from_secs(1), None)
+                .map(|cache| format!("{:?}", cache))
+        };
+
+        assert!(backend("https://cache.example.com/qlty")
+            .unwrap()
+            .starts_with("HttpCache"));
+        assert!(backend("shared/cache")
+            .unwrap()
+            .contains(&format!("{:?}", root.join("shared/cache"))));
+        assert!(backend("s3://bucket").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 mod driver;
 mod invocation_result;
 mod invocation_script;
+pub mod profiler;
 pub mod staging_area;
 
 use self::staging_area::{load_config_file_from_qlty_dir, load_config_file_from_repository};
@@ -22,6 +23,7 @@ use ignore::{DirEntry, WalkBuilder, WalkState};
 pub use invocation_result::{InvocationResult, InvocationStatus};
 pub use invocation_script::{compute_invocation_script, plan_target_list};
 use itertools::Itertools;
+pub use profiler::{Profiler, SpanKind};
 use qlty_analysis::utils::fs::path_to_string;
 use qlty_config::config::DriverType;
 use qlty_config::issue_transformer::IssueTransformer;
@@ -34,7 +36,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{
     sync::{Arc, Mutex},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tracing::{debug, error, info, warn};
 
@@ -45,6 +47,7 @@ const MAX_ISSUES_PER_FILE: usize = 500;
 pub struct Executor {
     plan: Plan,
     progress: Progress,
+    profiler: Profiler,
     total_issues: Arc<AtomicUsize>,
 }
 
@@ -54,10 +57,15 @@ impl Executor {
         Self {
             plan: plan.clone(),
             progress,
+            profiler: Profiler::new(),
             total_issues: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    pub fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+
     pub fn install_and_invoke(&self) -> Result<Results> {
         let install_messages = self.install()?;
         self.run_prepare_scripts()?;
@@ -70,8 +78,12 @@ impl Executor {
 
     pub fn install(&self) -> Result<Vec<Message>> {
         let mut install_messages = vec![];
-        let installation_results =
-            Self::install_tools(self.plan.tools(), self.plan.jobs, self.progress.clone());
+        let installation_results = Self::install_tools(
+            self.plan.tools(),
+            self.plan.jobs,
+            self.progress.clone(),
+            &self.profiler,
+        );
 
         for installation_result in installation_results {
             let (name, result) = installation_result;
@@ -101,6 +113,7 @@ impl Executor {
         tools: Vec<(String, Box<dyn Tool>)>,
         jobs: usize,
         progress: Progress,
+        profiler: &Profiler,
     ) -> Vec<(String, Result<()>)> {
         let timer = Instant::now();
         let pool = rayon::ThreadPoolBuilder::new()
@@ -115,10 +128,18 @@ impl Executor {
             install_results = tools
                 .into_par_iter()
                 .map(|(name, tool)| {
-                    (
-                        name.clone(),
-                        Self::install_tool(name, tool, progress.clone()),
-                    )
+                    let start = profiler.elapsed();
+                    let result = Self::install_tool(name.clone(), tool, progress.clone());
+                    profiler.record(
+                        SpanKind::Install,
+                        &name,
+                        start,
+                        profiler.elapsed() - start,
+                        0,
+                        None,
+                    );
+
+                    (name, result)
                 })
                 .collect::<Vec<_>>();
         });
@@ -155,7 +176,16 @@ impl Executor {
 
         for (key, invocation) in prepare_scripts {
             let task = self.progress.task(&key, "Running prepare script...");
+            let start = self.profiler.elapsed();
             invocation.driver.run_prepare_script(invocation, &task)?;
+            self.profiler.record(
+                SpanKind::Prepare,
+                &key,
+                start,
+                self.profiler.elapsed() - start,
+                0,
+                None,
+            );
             task.clear();
         }
 
@@ -179,6 +209,17 @@ impl Executor {
             transformers.push(Box::new(Fixer::new(&self.plan, self.progress.clone())));
         }
 
+        for cache_hit in &self.plan.hits {
+            self.profiler.record(
+                SpanKind::CacheHit,
+                &cache_hit.invocation_label,
+                self.profiler.elapsed(),
+                Duration::ZERO,
+                1,
+                None,
+            );
+        }
+
         if !self.plan.invocations.is_empty() {
             let loaded_config_files = self.stage_workspace_entries()?;
             invocations = self.run_invocations(&transformers)?;
@@ -463,6 +504,7 @@ impl Executor {
                         plan.clone(),
                         self.plan.issue_cache.clone(),
                         self.progress.clone(),
+                        &self.profiler,
                         transformers,
                     );
 
@@ -645,9 +687,10 @@ fn run_invocation_with_error_capture(
     plan: InvocationPlan,
     cache: IssueCache,
     progress: Progress,
+    profiler: &Profiler,
     transformers: &[Box<dyn IssueTransformer>],
 ) -> PlanResult {
-    let result = run_invocation(plan.clone(), cache, progress, transformers);
+    let result = run_invocation(plan.clone(), cache, progress, profiler, transformers);
     PlanResult { plan, result }
 }
 
@@ -655,10 +698,13 @@ fn run_invocation(
     plan: InvocationPlan,
     cache: IssueCache,
     progress: Progress,
+    profiler: &Profiler,
     transformers: &[Box<dyn IssueTransformer>],
 ) -> Result<InvocationResult> {
     let task = progress.task(&plan.plugin_name, &plan.description());
+    let start = profiler.elapsed();
     let mut result = plan.driver.run(&plan, &task)?;
+    record_invocation(profiler, &plan, &result, start);
     let mut issue_limit_reached = HashSet::<PathBuf>::new();
 
     if let Some(file_results) = result.file_results.as_mut() {
@@ -714,6 +760,35 @@ fn run_invocation(
     Ok(result)
 }
 
+/// Splits the time spent in an invocation between running the tool and parsing its output
+fn record_invocation(
+    profiler: &Profiler,
+    plan: &InvocationPlan,
+    result: &InvocationResult,
+    start: Duration,
+) {
+    let label = plan.invocation_label();
+    let total = profiler.elapsed() - start;
+    let parse = Duration::from_secs_f64(result.parse_duration_secs).min(total);
+
+    profiler.record(
+        SpanKind::Invocation,
+        &label,
+        start,
+        total - parse,
+        plan.targets.len(),
+        result.pid,
+    );
+    profiler.record(
+        SpanKind::Parse,
+        &label,
+        start + total - parse,
+        parse,
+        plan.targets.len(),
+        None,
+    );
+}
+
 fn walk_collect_entries_parallel(builder: &WalkBuilder) -> Vec<DirEntry> {
     let dents = Arc::new(Mutex::new(vec![]));
 
@@ -125,6 +125,7 @@ impl Driver {
         let timer = Instant::now();
         let handle = cmd.start()?;
         let pids = handle.pids();
+        let pid = pids.first().copied();
         let timeout = plan.driver.timeout;
         let invocation_label = plan.invocation_label();
         let running = Arc::new(AtomicBool::new(true));
@@ -150,7 +151,9 @@ impl Driver {
             output.status.code().unwrap_or(-1)
         );
 
-        InvocationResult::from_command_output(plan, rerun, &output, duration)
+        let mut result = InvocationResult::from_command_output(plan, rerun, &output, duration)?;
+        result.pid = pid;
+        Ok(result)
     }
 
     pub fn terminate_processes(pids: Vec<u32>) {
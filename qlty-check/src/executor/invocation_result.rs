@@ -15,7 +15,7 @@ use qlty_types::analysis::v1::{
 };
 use serde::Serialize;
 use std::{collections::HashMap, path::PathBuf};
-use std::{process::Output, sync::Arc};
+use std::{process::Output, sync::Arc, time::Instant};
 use tracing::{debug, error, info, trace};
 
 #[derive(Debug, Clone)]
@@ -25,6 +25,8 @@ pub struct InvocationResult {
     pub invocation: Invocation,
     pub file_results: Option<Vec<FileResult>>,
     pub formatted: Option<Vec<PathBuf>>,
+    pub pid: Option<u32>,
+    pub parse_duration_secs: f64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -113,9 +115,14 @@ impl InvocationResult {
             messages: Default::default(),
             file_results: Default::default(),
             formatted: Default::default(),
+            pid: None,
+            parse_duration_secs: 0.0,
         };
 
+        let timer = Instant::now();
         invocation.process_results()?;
+        invocation.parse_duration_secs = timer.elapsed().as_secs_f64();
+
         invocation.write_out_file()?;
 
         let invocation_id_slug = invocation.invocation.id[0..6].to_owned();
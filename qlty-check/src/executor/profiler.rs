@@ -0,0 +1,271 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanKind {
+    Install,
+    Prepare,
+    Invocation,
+    Parse,
+    CacheHit,
+}
+
+/// A timed step of an execution, measured from when the profiler was created
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub kind: SpanKind,
+    pub label: String,
+    pub start: Duration,
+    pub duration: Duration,
+    pub thread: usize,
+    pub pid: Option<u32>,
+    pub targets: usize,
+}
+
+/// Collects spans from every thread of an execution
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    started: Instant,
+    spans: Arc<Mutex<Vec<Span>>>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            spans: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Records a span which started at `start` and lasted for `duration`
+    pub fn record(
+        &self,
+        kind: SpanKind,
+        label: &str,
+        start: Duration,
+        duration: Duration,
+        targets: usize,
+        pid: Option<u32>,
+    ) {
+        self.spans.lock().unwrap().push(Span {
+            kind,
+            label: label.to_string(),
+            start,
+            duration,
+            // Thread 0 is the main thread, and rayon workers are numbered from 1
+            thread: rayon::current_thread_index().map_or(0, |index| index + 1),
+            pid,
+            targets,
+        });
+    }
+
+    pub fn spans(&self) -> Vec<Span> {
+        let mut spans = self.spans.lock().unwrap().clone();
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriverTiming {
+    pub label: String,
+    pub invocations: usize,
+    pub targets: usize,
+    pub cache_hits: usize,
+    pub total: Duration,
+    pub slowest: Duration,
+}
+
+/// Sums the time spent in each driver, slowest first. Installs are shared
+/// between drivers, so they are reported separately in the trace.
+pub fn driver_timings(spans: &[Span]) -> Vec<DriverTiming> {
+    let mut timings: HashMap<&str, DriverTiming> = HashMap::new();
+
+    for span in spans {
+        if span.kind == SpanKind::Install {
+            continue;
+        }
+
+        let timing = timings.entry(&span.label).or_insert_with(|| DriverTiming {
+            label: span.label.clone(),
+            ..Default::default()
+        });
+
+        match span.kind {
+            SpanKind::Invocation => {
+                timing.invocations += 1;
+                timing.targets += span.targets;
+                timing.slowest = timing.slowest.max(span.duration);
+            }
+            SpanKind::CacheHit => timing.cache_hits += span.targets,
+            _ => {}
+        }
+
+        timing.total += span.duration;
+    }
+
+    let mut timings = timings.into_values().collect::<Vec<_>>();
+    timings.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.label.cmp(&b.label)));
+    timings
+}
+
+/// Writes spans in the Chrome trace event format, which can be opened in
+/// `chrome://tracing` or https://ui.perfetto.dev
+pub fn write_chrome_trace(path: &Path, spans: &[Span]) -> Result<()> {
+    let json = serde_json::to_string(&chrome_trace(spans))?;
+
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write profile: {}", path.display()))
+}
+
+fn chrome_trace(spans: &[Span]) -> Value {
+    let pid = std::process::id();
+    let threads = spans
+        .iter()
+        .map(|span| span.thread)
+        .collect::<BTreeSet<_>>();
+
+    let mut events = threads
+        .into_iter()
+        .map(|thread| {
+            json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": pid,
+                "tid": thread,
+                "args": {
+                    "name": if thread == 0 {
+                        "main".to_string()
+                    } else {
+                        format!("worker {}", thread)
+                    },
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for span in spans {
+        let mut event = json!({
+            "name": span.label,
+            "cat": span.kind,
+            "ts": span.start.as_micros() as u64,
+            "pid": pid,
+            "tid": span.thread,
+            "args": {
+                "targets": span.targets,
+                "cache_hit": span.kind == SpanKind::CacheHit,
+            },
+        });
+
+        if span.kind == SpanKind::CacheHit {
+            event["ph"] = json!("i");
+            event["s"] = json!("t");
+        } else {
+            event["ph"] = json!("X");
+            event["dur"] = json!(span.duration.as_micros() as u64);
+        }
+
+        if let Some(child_pid) = span.pid {
+            event["args"]["pid"] = json!(child_pid);
+        }
+
+        events.push(event);
+    }
+
+    json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn span(kind: SpanKind, label: &str, start_ms: u64, duration_ms: u64, targets: usize) -> Span {
+        Span {
+            kind,
+            label: label.to_string(),
+            start: Duration::from_millis(start_ms),
+            duration: Duration::from_millis(duration_ms),
+            thread: 1,
+            pid: None,
+            targets,
+        }
+    }
+
+    #[test]
+    fn test_driver_timings() {
+        let spans = vec![
+            span(SpanKind::Install, "eslint", 0, 5000, 0),
+            span(SpanKind::Invocation, "eslint/lint", 10, 300, 20),
+            span(SpanKind::Parse, "eslint/lint", 310, 20, 20),
+            span(SpanKind::Invocation, "eslint/lint", 10, 500, 30),
+            span(SpanKind::Invocation, "rubocop/lint", 10, 200, 5),
+            span(SpanKind::CacheHit, "rubocop/lint", 0, 0, 1),
+            span(SpanKind::CacheHit, "rubocop/lint", 0, 0, 1),
+        ];
+
+        assert_eq!(
+            driver_timings(&spans),
+            vec![
+                DriverTiming {
+                    label: "eslint/lint".to_string(),
+                    invocations: 2,
+                    targets: 50,
+                    cache_hits: 0,
+                    total: Duration::from_millis(820),
+                    slowest: Duration::from_millis(500),
+                },
+                DriverTiming {
+                    label: "rubocop/lint".to_string(),
+                    invocations: 1,
+                    targets: 5,
+                    cache_hits: 2,
+                    total: Duration::from_millis(200),
+                    slowest: Duration::from_millis(200),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chrome_trace() {
+        let mut invocation = span(SpanKind::Invocation, "eslint/lint", 10, 300, 20);
+        invocation.pid = Some(1234);
+
+        let trace = chrome_trace(&[invocation, span(SpanKind::CacheHit, "eslint/lint", 2, 0, 1)]);
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["ph"], "M");
+        assert_eq!(events[0]["args"]["name"], "worker 1");
+
+        assert_eq!(events[1]["ph"], "X");
+        assert_eq!(events[1]["cat"], "invocation");
+        assert_eq!(events[1]["ts"], 10_000);
+        assert_eq!(events[1]["dur"], 300_000);
+        assert_eq!(events[1]["args"]["pid"], 1234);
+        assert_eq!(events[1]["args"]["targets"], 20);
+
+        assert_eq!(events[2]["ph"], "i");
+        assert_eq!(events[2]["cat"], "cache_hit");
+        assert_eq!(events[2]["args"]["cache_hit"], true);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod baseline;
 pub mod cache;
 mod command;
 pub mod executor;
@@ -11,11 +12,13 @@ mod processor;
 pub mod report;
 pub mod results;
 mod settings;
+mod shard;
 pub mod source_reader;
 pub mod tool;
 pub mod ui;
 mod utils;
 
+pub use baseline::Baseline;
 pub use executor::{Executor, InvocationResult};
 pub use patch_builder::PATCH_CONTEXT_LENGTH;
 pub use planner::Planner;
@@ -23,5 +26,6 @@ pub use processor::Processor;
 pub use report::Report;
 pub use results::Results;
 pub use settings::{CheckFilter, Settings};
+pub use shard::{Shard, ShardReport};
 pub use tool::Tool;
 pub use ui::Progress;
@@ -2,7 +2,8 @@ use self::config::enabled_plugins; //, enabled_runtime_template};
 use self::level_filter::LevelFilter;
 use self::plugin::PluginPlanner;
 use self::plugin_mode_transformer::PluginModeTransformer;
-use crate::cache::{IssueCache, IssuesCacheHit};
+use crate::baseline::Baseline;
+use crate::cache::{IssueCache, IssuesCacheHit, RemoteCache};
 use crate::executor::staging_area::{Mode, StagingArea};
 use crate::issue_muter::IssueMuter;
 use crate::patch_builder::PatchBuilder;
@@ -67,19 +68,21 @@ pub struct Planner {
     plugin_configs: HashMap<String, Vec<PluginConfigFile>>,
     invocations: Vec<InvocationPlan>,
     transformers: Vec<Box<dyn IssueTransformer>>,
+    baseline: Option<Baseline>,
 }
 
 impl Planner {
     pub fn new(verb: ExecutionVerb, settings: &Settings) -> Result<Self> {
         let workspace = Workspace::for_root(&settings.root)?;
-        let cache = Self::build_cache(&workspace, settings)?;
+        let config = workspace.config()?;
+        let cache = Self::build_cache(&workspace, &config, settings)?;
         let issue_cache = IssueCache::new(cache.clone());
 
         Ok(Self {
             verb,
             settings: settings.clone(),
             workspace: workspace.clone(),
-            config: workspace.config()?,
+            config,
             staging_area: StagingArea::generate(Mode::Source, workspace.root.clone(), None),
             issue_cache,
             target_mode: None,
@@ -89,6 +92,7 @@ impl Planner {
             plugin_configs: HashMap::new(),
             invocations: vec![],
             transformers: vec![],
+            baseline: None,
         })
     }
 
@@ -98,6 +102,7 @@ impl Planner {
         self.compute_enabled_plugins()?;
         self.compute_staging_area()?;
         self.compute_invocations()?;
+        self.compute_baseline()?;
         self.compute_transformers();
         let plan = self.build_plan();
         info!(
@@ -136,6 +141,7 @@ impl Planner {
         let mut builder = PluginWorkspaceEntryFinderBuilder {
             mode: self.target_mode.as_ref().unwrap().clone(),
             root: self.settings.root.clone(),
+            cwd: self.settings.cwd.clone(),
             paths: self.settings.paths.clone(),
             file_types: self.config.file_types.clone(),
             ignores: self.config.ignore.clone(),
@@ -234,7 +240,9 @@ impl Planner {
 
             if !self.settings.emit_existing_issues {
                 match &self.target_mode.as_ref() {
-                    Some(TargetMode::UpstreamDiff(_)) | Some(TargetMode::HeadDiff) => {
+                    Some(TargetMode::UpstreamDiff(_))
+                    | Some(TargetMode::HeadDiff)
+                    | Some(TargetMode::Range(_)) => {
                         self.transformers.push(Box::new(DiffLineFilter));
                     }
                     _ => {}
@@ -272,6 +280,19 @@ impl Planner {
         for issue_override in &self.config.overrides {
             self.transformers.push(Box::new(issue_override.clone()));
         }
+
+        // the baseline applies to issues as they would otherwise be reported
+        if let Some(baseline) = &self.baseline {
+            self.transformers.push(Box::new(baseline.clone()));
+        }
+    }
+
+    fn compute_baseline(&mut self) -> Result<()> {
+        if let Some(path) = &self.settings.baseline {
+            self.baseline = Some(Baseline::read(path)?);
+        }
+
+        Ok(())
     }
 
     fn build_plan(&mut self) -> Result<Plan> {
@@ -294,6 +315,7 @@ impl Planner {
             transformers: self.transformers.clone(),
             staging_area: self.staging_area.clone(),
             fail_level: self.settings.fail_level,
+            baseline: self.baseline.clone(),
         })
     }
 
@@ -308,6 +330,8 @@ impl Planner {
             TargetMode::Sample(self.settings.sample.unwrap())
         } else if !self.settings.paths.is_empty() {
             TargetMode::Paths(self.settings.paths.len())
+        } else if let Some(range) = &self.settings.range {
+            TargetMode::Range(range.clone())
         } else if let Some(upstream) = compute_upstream(&self.workspace, &self.settings.upstream) {
             TargetMode::UpstreamDiff(upstream)
         } else {
@@ -315,15 +339,17 @@ impl Planner {
         }
     }
 
-    fn build_cache(workspace: &Workspace, settings: &Settings) -> Result<Box<dyn Cache>> {
+    fn build_cache(
+        workspace: &Workspace,
+        config: &QltyConfig,
+        settings: &Settings,
+    ) -> Result<Box<dyn Cache>> {
         if settings.cache {
             let library = workspace.library()?;
             library.create()?;
 
-            Ok(Box::new(FilesystemCache::new(
-                library.results_dir().join("issues"),
-                "protos",
-            )))
+            let local = FilesystemCache::new(library.results_dir().join("issues"), "protos");
+            RemoteCache::from_config(&config.cache, &workspace.root, Box::new(local), "protos")
         } else {
             Ok(Box::new(NullCache::new()))
         }
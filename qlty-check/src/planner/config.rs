@@ -260,7 +260,7 @@ mod test {
     fn build_planner(config: QltyConfig) -> Planner {
         let workspace = Workspace::default();
         let settings = Settings::default();
-        let cache = Planner::build_cache(&workspace, &settings).unwrap();
+        let cache = Planner::build_cache(&workspace, &config, &settings).unwrap();
 
         Planner {
             config,
@@ -276,6 +276,7 @@ mod test {
             plugin_configs: HashMap::new(),
             invocations: vec![],
             transformers: vec![],
+            baseline: None,
         }
     }
 
@@ -8,7 +8,7 @@ use super::{
 use crate::{
     cache::{IssueCache, IssuesCacheHit, IssuesCacheKey},
     executor::staging_area::StagingArea,
-    planner::{target_batcher::TargetBatcher, InvocationPlan},
+    planner::{invocation_plan::invocation_label, target_batcher::TargetBatcher, InvocationPlan},
     utils::generate_random_id,
     Tool,
 };
@@ -163,17 +163,25 @@ impl DriverPlanner {
             self.plugin.affects_cache.clone(),
         );
 
+        // Cached results for targets in other shards are reported by those shards
         let items = self
             .targets
             .par_iter()
+            .filter(|target| {
+                self.settings
+                    .shard
+                    .is_none_or(|shard| shard.contains(&target.path))
+            })
             .map(|target| {
                 let mut cache_key = cache_key.clone();
                 cache_key.finalize(target);
                 (target, cache_key)
             })
             .collect::<Vec<(&WorkspaceEntry, IssuesCacheKey)>>();
+        let label = invocation_label(&self.plugin_name, &self.driver_name, &self.plugin);
+
         for (target, cache_key) in items.into_iter() {
-            match self.issue_cache.read(&cache_key) {
+            match self.issue_cache.read(&cache_key, &label) {
                 Ok(Some(cache_hit)) => {
                     cache_hits.push(cache_hit);
                 }
@@ -211,6 +219,7 @@ impl DriverPlanner {
             current_prefix: self.plugin.prefix.clone(),
             all_prefixes: self.all_prefixes.clone(),
             workspace_root: self.workspace.root.clone(),
+            shard: self.settings.shard,
         };
 
         for driver_target_batch in target_batcher.compute(&targets)? {
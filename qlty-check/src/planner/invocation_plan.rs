@@ -21,6 +21,15 @@ use shell_escape::unix::escape;
 #[cfg(windows)]
 use shell_escape::windows::escape;
 
+/// Identifies a plugin's driver in logs and profiles, such as `eslint/lint`
+pub fn invocation_label(plugin_name: &str, driver_name: &str, plugin: &PluginDef) -> String {
+    if let Some(prefix) = &plugin.prefix {
+        format!("{}/{}/{}", plugin_name, driver_name, prefix)
+    } else {
+        format!("{}/{}", plugin_name, driver_name)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct InvocationPlan {
     pub invocation_id: String,
@@ -70,11 +79,7 @@ impl InvocationPlan {
     }
 
     pub fn invocation_label(&self) -> String {
-        if let Some(prefix) = &self.plugin.prefix {
-            format!("{}/{}/{}", self.plugin_name, self.driver_name, prefix)
-        } else {
-            format!("{}/{}", self.plugin_name, self.driver_name)
-        }
+        invocation_label(&self.plugin_name, &self.driver_name, &self.plugin)
     }
 
     pub fn uses_tmpfile(&self) -> bool {
@@ -1,4 +1,5 @@
 use super::InvocationPlan;
+use crate::baseline::Baseline;
 use crate::cache::IssueCache;
 use crate::cache::IssuesCacheHit;
 use crate::executor::staging_area::StagingArea;
@@ -27,6 +28,7 @@ pub struct Plan {
     pub transformers: Vec<Box<dyn IssueTransformer>>,
     pub invocations: Vec<InvocationPlan>,
     pub fail_level: Option<Level>,
+    pub baseline: Option<Baseline>,
 }
 
 impl Plan {
@@ -50,6 +52,7 @@ impl Plan {
             TargetMode::Paths(ref paths) => format!(" {} paths", paths),
             TargetMode::UpstreamDiff(ref upstream) => format!(" vs. {}", upstream),
             TargetMode::HeadDiff => " vs. HEAD".to_string(),
+            TargetMode::Range(ref range) => format!(" {}", range),
             _ => "".to_string(),
         }
     }
@@ -9,7 +9,7 @@ use qlty_analysis::{AllSource, ArgsSource, DiffSource};
 use qlty_config::config::ignore_group::IgnoreGroup;
 use qlty_config::config::issue_transformer::{IssueTransformer, NullIssueTransformer};
 use qlty_config::config::Ignore;
-use qlty_config::{FileType, Workspace};
+use qlty_config::FileType;
 use std::sync::Arc;
 use std::{collections::HashMap, path::PathBuf};
 
@@ -17,6 +17,7 @@ use std::{collections::HashMap, path::PathBuf};
 pub struct PluginWorkspaceEntryFinderBuilder {
     pub mode: TargetMode,
     pub root: PathBuf,
+    pub cwd: PathBuf,
     pub paths: Vec<PathBuf>,
     pub file_types: HashMap<String, FileType>,
     pub ignores: Vec<Ignore>,
@@ -44,9 +45,9 @@ impl PluginWorkspaceEntryFinderBuilder {
 
     pub fn diff_line_filter(&mut self) -> Result<Box<dyn IssueTransformer>> {
         match self.mode {
-            TargetMode::HeadDiff | TargetMode::UpstreamDiff(_) => Ok(Box::new(
-                self.git_diff.as_ref().unwrap().line_filter.clone(),
-            )),
+            TargetMode::HeadDiff | TargetMode::UpstreamDiff(_) | TargetMode::Range(_) => Ok(
+                Box::new(self.git_diff.as_ref().unwrap().line_filter.clone()),
+            ),
             _ => Ok(Box::new(NullIssueTransformer)),
         }
     }
@@ -80,7 +81,7 @@ impl PluginWorkspaceEntryFinderBuilder {
         matchers.push(Box::new(file_matcher));
 
         matchers.push(Box::new(PrefixMatcher::new(
-            path_to_string(Workspace::current_dir()),
+            path_to_string(&self.cwd),
             self.root.to_owned(),
         )));
 
@@ -104,17 +105,16 @@ impl PluginWorkspaceEntryFinderBuilder {
     }
 
     fn compute_source(&mut self) -> Result<()> {
-        let cwd = Workspace::current_dir();
-
         self.source = Some(match self.mode {
             TargetMode::All | TargetMode::Sample(_) => Arc::new(AllSource::new(self.root.clone())),
             TargetMode::Paths(_) => Arc::new(ArgsSource::new(
                 self.root.clone(),
                 // Use absolute paths, so when running in a subdirectory, the paths are still correct
-                self.paths.iter().map(|p| cwd.join(p)).collect(),
+                self.paths.iter().map(|p| self.cwd.join(p)).collect(),
             )),
             TargetMode::UpstreamDiff(_)
             | TargetMode::HeadDiff
+            | TargetMode::Range(_)
             | TargetMode::Index
             | TargetMode::IndexFile(_) => self.build_diff_source()?,
         });
@@ -142,6 +142,7 @@ impl PluginWorkspaceEntryFinderBuilder {
             self.mode,
             TargetMode::HeadDiff
                 | TargetMode::UpstreamDiff(_)
+                | TargetMode::Range(_)
                 | TargetMode::Index
                 | TargetMode::IndexFile(_)
         )
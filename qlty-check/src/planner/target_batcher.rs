@@ -15,6 +15,7 @@ use super::{
     config_files::PluginConfigFile, invocation_directory::InvocationDirectoryPlanner,
     target::Target,
 };
+use crate::Shard;
 
 const MAX_MAX_BATCH: usize = 512;
 
@@ -34,12 +35,13 @@ pub struct TargetBatcher {
     pub current_prefix: Option<String>,
     pub all_prefixes: Vec<String>,
     pub workspace_root: PathBuf,
+    pub shard: Option<Shard>,
 }
 
 impl TargetBatcher {
     pub fn compute(&self, targets: &[Target]) -> Result<Vec<DriverTargetBatch>> {
         let mut driver_target_batches = vec![];
-        let targets = self.filter_prefix(targets);
+        let targets = self.filter_prefix(&self.filter_shard(targets));
 
         match self.driver.batch_by {
             DriverBatchBy::None => {
@@ -103,6 +105,19 @@ impl TargetBatcher {
         }
     }
 
+    /// Splitting individual targets rather than whole batches keeps the
+    /// amount of work in each shard roughly even
+    fn filter_shard(&self, targets: &[Target]) -> Vec<Target> {
+        match &self.shard {
+            Some(shard) => targets
+                .iter()
+                .filter(|target| shard.contains(&target.path))
+                .cloned()
+                .collect(),
+            None => targets.to_vec(),
+        }
+    }
+
     fn batch_by_invocation_directory(
         &self,
         driver_target_batches: &mut Vec<DriverTargetBatch>,
@@ -302,6 +317,7 @@ mod test {
             current_prefix: None,
             all_prefixes: vec![],
             workspace_root: PathBuf::from("/User/test/project_root/"),
+            shard: None,
         }
     }
 
@@ -530,6 +546,7 @@ mod test {
             current_prefix: None,
             all_prefixes: vec![],
             workspace_root: temp_path.clone(),
+            shard: None,
         };
 
         let targets = vec![
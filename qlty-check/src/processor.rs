@@ -7,8 +7,9 @@ use crate::{
     Report,
 };
 use anyhow::Result;
+use qlty_analysis::fingerprint::issue_fingerprint;
 use qlty_analysis::IssueCount;
-use qlty_types::analysis::v1::{Category, ExecutionVerb, Issue};
+use qlty_types::analysis::v1::{Category, ExecutionVerb, Issue, Level};
 use tracing::info;
 
 pub struct Processor {
@@ -62,22 +63,7 @@ impl Processor {
     }
 
     fn compute_counts(&mut self) {
-        self.counts.total_issues = self.issues.len();
-
-        for issue in &self.issues {
-            if let Some(fail_level) = self.plan.fail_level {
-                if issue.level >= fail_level as i32 {
-                    self.counts.failure_issues += 1;
-                }
-            }
-
-            if issue.category == Category::Vulnerability as i32
-                || issue.category == Category::Secret as i32
-                || issue.category == Category::DependencyAlert as i32
-            {
-                self.counts.total_security_issues += 1;
-            }
-        }
+        self.counts = count_issues(&self.issues, self.plan.fail_level);
     }
 
     fn compute_issues(&mut self) {
@@ -115,7 +101,10 @@ impl Processor {
     }
 
     fn transform_issue(&self, issue: &Issue) -> Option<Issue> {
-        let mut transformed_issue: Option<Issue> = Some(issue.clone());
+        let mut issue = issue.clone();
+        issue.fingerprint = issue_fingerprint(&issue);
+
+        let mut transformed_issue: Option<Issue> = Some(issue);
 
         for transformer in self.plan.transformers.iter() {
             if transformed_issue.is_some() {
@@ -128,3 +117,27 @@ impl Processor {
         transformed_issue
     }
 }
+
+pub(crate) fn count_issues(issues: &[Issue], fail_level: Option<Level>) -> IssueCount {
+    let mut counts = IssueCount {
+        total_issues: issues.len(),
+        ..Default::default()
+    };
+
+    for issue in issues {
+        if let Some(fail_level) = fail_level {
+            if issue.level >= fail_level as i32 {
+                counts.failure_issues += 1;
+            }
+        }
+
+        if issue.category == Category::Vulnerability as i32
+            || issue.category == Category::Secret as i32
+            || issue.category == Category::DependencyAlert as i32
+        {
+            counts.total_security_issues += 1;
+        }
+    }
+
+    counts
+}
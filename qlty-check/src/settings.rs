@@ -1,10 +1,14 @@
+use crate::Shard;
 use qlty_config::config::CheckTrigger;
+use qlty_config::Workspace;
 use qlty_types::analysis::v1::{Issue, Level};
 use std::{fmt::Formatter, path::PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub root: PathBuf,
+    /// Targets are resolved relative to this directory, which is usually within `root`
+    pub cwd: PathBuf,
     pub all: bool,
     pub fix: bool,
     pub ai: bool,
@@ -17,6 +21,8 @@ pub struct Settings {
     pub sample: Option<usize>,
     pub filters: Vec<CheckFilter>,
     pub upstream: Option<String>,
+    pub range: Option<String>,
+    pub shard: Option<Shard>,
     pub index: bool,
     pub index_file: Option<PathBuf>,
     pub level: Level,
@@ -26,12 +32,14 @@ pub struct Settings {
     pub skip_errored_plugins: bool,
     pub emit_existing_issues: bool,
     pub auth_token: Option<String>,
+    pub baseline: Option<PathBuf>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             root: std::env::current_dir().expect("current dir"),
+            cwd: Workspace::current_dir(),
             all: false,
             fix: false,
             ai: false,
@@ -44,6 +52,8 @@ impl Default for Settings {
             sample: None,
             filters: vec![],
             upstream: None,
+            range: None,
+            shard: None,
             index: false,
             index_file: None,
             level: Level::Unspecified,
@@ -53,6 +63,7 @@ impl Default for Settings {
             skip_errored_plugins: false,
             emit_existing_issues: false,
             auth_token: None,
+            baseline: None,
         }
     }
 }
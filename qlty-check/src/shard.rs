@@ -0,0 +1,305 @@
+use crate::{processor::count_issues, Report};
+use anyhow::{bail, Context, Result};
+use qlty_analysis::workspace_entries::TargetMode;
+use qlty_types::analysis::v1::{ExecutionVerb, Issue, Level, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::warn;
+
+const SHARD_REPORT_VERSION: u32 = 1;
+
+/// One of `count` partitions of the targets of a check, numbered from 1.
+/// Targets are assigned by hashing their paths, so every machine computes
+/// the same partition without coordinating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shard {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl Shard {
+    pub fn contains(&self, path: &Path) -> bool {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let digest = Sha256::digest(path.as_bytes());
+        let hash = u64::from_be_bytes(digest[..8].try_into().unwrap());
+
+        (hash % self.count as u64) as usize == self.index - 1
+    }
+}
+
+impl FromStr for Shard {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let Some((index, count)) = value.split_once('/') else {
+            bail!("Expected a shard in the form N/M, such as 1/4");
+        };
+
+        let index = index
+            .trim()
+            .parse::<usize>()
+            .with_context(|| format!("Invalid shard index: {}", index))?;
+        let count = count
+            .trim()
+            .parse::<usize>()
+            .with_context(|| format!("Invalid shard count: {}", count))?;
+
+        if index == 0 || index > count {
+            bail!("Shard index must be between 1 and {}", count);
+        }
+
+        Ok(Self { index, count })
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+/// The results of checking a single shard, to be merged with the other shards
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShardReport {
+    version: u32,
+    pub shard: String,
+    pub issues: Vec<Issue>,
+    pub messages: Vec<Message>,
+    pub formatted: Vec<PathBuf>,
+}
+
+impl ShardReport {
+    pub fn write(path: &Path, shard: &Shard, report: &Report) -> Result<()> {
+        let file = Self {
+            version: SHARD_REPORT_VERSION,
+            shard: shard.to_string(),
+            issues: report.issues.clone(),
+            messages: report.messages.clone(),
+            formatted: report.formatted.clone(),
+        };
+
+        let mut json = serde_json::to_string_pretty(&file)?;
+        json.push('\n');
+
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write shard report: {}", path.display()))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read shard report: {}", path.display()))?;
+
+        let report: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse shard report: {}", path.display()))?;
+
+        if report.version != SHARD_REPORT_VERSION {
+            bail!(
+                "Unsupported shard report version {} in {}",
+                report.version,
+                path.display()
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Combines the reports of every shard of a check into a single report,
+    /// counting failures against the given level. Unless `allow_missing` is set,
+    /// a report is required for every shard.
+    pub fn merge(
+        reports: &[ShardReport],
+        fail_level: Option<Level>,
+        allow_missing: bool,
+    ) -> Result<Report> {
+        let mut shards = HashSet::new();
+        let mut count = None;
+
+        for report in reports {
+            let shard: Shard = report.shard.parse()?;
+
+            if count.is_some_and(|count| count != shard.count) {
+                bail!("Cannot merge reports from checks split into different numbers of shards");
+            }
+
+            if !shards.insert(shard) {
+                bail!("Found more than one report for shard {}", shard);
+            }
+
+            count = Some(shard.count);
+        }
+
+        if let Some(count) = count {
+            let missing = (1..=count)
+                .map(|index| Shard { index, count })
+                .filter(|shard| !shards.contains(shard))
+                .map(|shard| shard.to_string())
+                .collect::<Vec<_>>();
+
+            if !missing.is_empty() {
+                if !allow_missing {
+                    bail!("Missing reports for shards: {}", missing.join(", "));
+                }
+
+                warn!("Missing reports for shards: {}", missing.join(", "));
+            }
+        }
+
+        let mut issues = reports
+            .iter()
+            .flat_map(|report| report.issues.clone())
+            .collect::<Vec<_>>();
+        issues.sort();
+
+        Ok(Report {
+            verb: ExecutionVerb::Check,
+            target_mode: TargetMode::All,
+            messages: reports
+                .iter()
+                .flat_map(|report| report.messages.clone())
+                .collect(),
+            invocations: vec![],
+            counts: count_issues(&issues, fail_level),
+            issues,
+            formatted: reports
+                .iter()
+                .flat_map(|report| report.formatted.clone())
+                .collect(),
+            fixed: HashSet::new(),
+            fixable: HashSet::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_analysis::IssueCount;
+    use qlty_types::analysis::v1::Location;
+    use tempfile::tempdir;
+
+    fn issue(path: &str, level: Level) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: "no-var".to_string(),
+            level: level.into(),
+            location: Some(Location {
+                path: path.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn shard_report(shard: &str, issues: Vec<Issue>) -> ShardReport {
+        ShardReport {
+            version: SHARD_REPORT_VERSION,
+            shard: shard.to_string(),
+            issues,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!(
+            "2/3".parse::<Shard>().unwrap(),
+            Shard { index: 2, count: 3 }
+        );
+        assert!("0/3".parse::<Shard>().is_err());
+        assert!("4/3".parse::<Shard>().is_err());
+        assert!("1".parse::<Shard>().is_err());
+        assert!("a/b".parse::<Shard>().is_err());
+    }
+
+    #[test]
+    fn test_shards_partition_paths() {
+        let shards = (1..=4)
+            .map(|index| Shard { index, count: 4 })
+            .collect::<Vec<_>>();
+
+        let mut sizes = vec![0; 4];
+
+        for i in 0..1000 {
+            let path = PathBuf::from(format!("src/module_{}/file.rs", i));
+            let owners = shards
+                .iter()
+                .filter(|shard| shard.contains(&path))
+                .collect::<Vec<_>>();
+
+            assert_eq!(owners.len(), 1);
+            sizes[owners[0].index - 1] += 1;
+        }
+
+        for size in sizes {
+            assert!(size > 200, "Unbalanced shards: {}", size);
+        }
+    }
+
+    #[test]
+    fn test_write_and_merge() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("shard.json");
+
+        let report = ShardReport::merge(
+            &[shard_report("1/2", vec![issue("b.js", Level::High)])],
+            None,
+            true,
+        )
+        .unwrap();
+        ShardReport::write(&path, &Shard { index: 1, count: 2 }, &report).unwrap();
+
+        let reports = vec![
+            ShardReport::read(&path).unwrap(),
+            shard_report("2/2", vec![issue("a.js", Level::Low)]),
+        ];
+        let merged = ShardReport::merge(&reports, Some(Level::Medium), false).unwrap();
+
+        assert_eq!(
+            merged
+                .issues
+                .iter()
+                .map(|issue| issue.path().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["a.js", "b.js"]
+        );
+        assert!(matches!(
+            merged.counts,
+            IssueCount {
+                total_issues: 2,
+                failure_issues: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_shards() {
+        assert!(ShardReport::merge(
+            &[shard_report("1/2", vec![]), shard_report("1/2", vec![])],
+            None,
+            true
+        )
+        .is_err());
+        assert!(ShardReport::merge(
+            &[shard_report("1/2", vec![]), shard_report("2/3", vec![])],
+            None,
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_merge_requires_every_shard() {
+        let reports = [shard_report("1/2", vec![issue("a.js", Level::High)])];
+
+        let error = ShardReport::merge(&reports, Some(Level::Medium), false).unwrap_err();
+        assert_eq!(error.to_string(), "Missing reports for shards: 2/2");
+
+        let merged = ShardReport::merge(&reports, Some(Level::Medium), true).unwrap();
+        assert_eq!(merged.issues.len(), 1);
+    }
+}
@@ -40,7 +40,7 @@ use std::path::Path;
 use std::process::Output;
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 use std::{collections::HashMap, fmt::Debug, path::PathBuf};
 use tracing::warn;
 use tracing::{debug, error, info};
@@ -218,6 +218,7 @@ pub trait Tool: Debug + Sync + Send {
                 self.name(),
                 self.version()
             );
+            self.mark_used();
 
             Ok(())
         } else {
@@ -281,6 +282,19 @@ pub trait Tool: Debug + Sync + Send {
         Ok(())
     }
 
+    /// Bumps the modification time of the done file, which `qlty cache prune`
+    /// uses to find installations which are no longer used
+    fn mark_used(&self) {
+        let result = std::fs::File::options()
+            .write(true)
+            .open(self.donefile_path())
+            .and_then(|file| file.set_modified(SystemTime::now()));
+
+        if let Err(err) = result {
+            debug!("Failed to mark {} as used: {}", self.name(), err);
+        }
+    }
+
     fn donefile_path(&self) -> PathBuf {
         PathBuf::from(format!("{}.done", self.directory()))
     }
@@ -71,6 +71,9 @@ pub enum Commands {
     /// Install linters and their dependencies
     Install(Install),
 
+    /// Run a language server for editor integrations
+    Lsp(Lsp),
+
     /// Compute code quality metrics
     Metrics(Metrics),
 
@@ -130,6 +133,7 @@ impl Arguments {
             Commands::Githooks(command) => command.execute(self),
             Commands::Install(command) => command.execute(self),
             Commands::Init(command) => command.execute(self),
+            Commands::Lsp(command) => command.execute(self),
             Commands::Metrics(command) => command.execute(self),
             Commands::Panic(command) => command.execute(self),
             Commands::Parse(command) => command.execute(self),
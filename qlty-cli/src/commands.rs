@@ -13,6 +13,7 @@ mod fmt;
 pub mod githooks;
 mod init;
 mod install;
+mod lsp;
 mod metrics;
 mod panic;
 mod parse;
@@ -26,7 +27,7 @@ mod version;
 
 pub use {
     build::Build, check::Check, completions::Completions, dashboard::Dashboard, deinit::Deinit,
-    discord::Discord, docs::Docs, fmt::Fmt, init::Init, install::Install, metrics::Metrics,
-    panic::Panic, parse::Parse, patch::Patch, smells::Smells, telemetry::Telemetry,
-    upgrade::Upgrade, validate::Validate, version::Version,
+    discord::Discord, docs::Docs, fmt::Fmt, init::Init, install::Install, lsp::Lsp,
+    metrics::Metrics, panic::Panic, parse::Parse, patch::Patch, smells::Smells,
+    telemetry::Telemetry, upgrade::Upgrade, validate::Validate, version::Version,
 };
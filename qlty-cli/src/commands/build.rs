@@ -1,4 +1,5 @@
 use crate::export::AnalysisExport;
+use crate::format::{GitLabFormatter, HtmlFormatter, OutputFormat};
 use crate::{Arguments, CommandError, CommandSuccess};
 use anyhow::Result;
 use clap::Args;
@@ -46,6 +47,14 @@ pub struct Build {
     #[arg(long)]
     pub print: bool,
 
+    /// [DEPRECATED, use --format=gitlab] Print a GitLab Code Quality report instead of exporting the build
+    #[arg(long, hide = true, conflicts_with_all = ["print", "format"])]
+    pub gitlab: bool,
+
+    /// Print a report in this format instead of exporting the build
+    #[arg(long, value_parser = OutputFormat::parser(&["gitlab", "html"]), conflicts_with = "print")]
+    pub format: Option<OutputFormat>,
+
     #[arg(long)]
     pub skip_errored_plugins: bool,
 
@@ -55,6 +64,10 @@ pub struct Build {
 
 impl Build {
     pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        if self.gitlab {
+            eprintln!("WARNING: --gitlab is deprecated, use --format=gitlab instead\n");
+        }
+
         let workspace = Workspace::require_initialized()?;
         workspace.fetch_sources()?;
 
@@ -166,6 +179,16 @@ impl Build {
 
             let json = serde_json::to_string_pretty(&report)?;
             println!("{}", json);
+        } else if self.output_format() == Some(OutputFormat::Gitlab) {
+            info!("Printing GitLab Code Quality report...");
+            GitLabFormatter::boxed(report.issues.clone()).write_to(&mut std::io::stdout())?;
+        } else if self.output_format() == Some(OutputFormat::Html) {
+            info!("Printing HTML report...");
+            HtmlFormatter::new(&workspace.root, "qlty build")
+                .with_issues(report.issues.clone())
+                .with_stats(report.file_stats())
+                .boxed()
+                .write_to(&mut std::io::stdout())?;
         } else {
             let output_path = match self.output_path {
                 Some(ref path) => path.clone(),
@@ -182,6 +205,14 @@ impl Build {
         }
     }
 
+    fn output_format(&self) -> Option<OutputFormat> {
+        if self.gitlab {
+            Some(OutputFormat::Gitlab)
+        } else {
+            self.format
+        }
+    }
+
     fn run_structure(&self, config: &QltyConfig, files: &[Arc<File>]) -> Result<Report> {
         let planner = qlty_smells::structure::Planner::new(config, files.to_vec())?;
         let plan = planner.compute()?;
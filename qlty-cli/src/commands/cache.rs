@@ -9,7 +9,7 @@ mod status;
 
 pub use clean::Clean;
 pub use dir::Dir;
-pub use prune::Prune;
+pub use prune::{auto_prune, Prune};
 pub use status::Status;
 
 #[derive(Debug, Args)]
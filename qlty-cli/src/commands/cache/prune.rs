@@ -1,18 +1,48 @@
 use crate::{Arguments, CommandError, CommandSuccess};
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use bytesize::ByteSize;
 use clap::Args;
 use cli_table::{
     format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
     print_stdout, Cell as _, Table as _,
 };
-use qlty_config::Workspace;
+use console::style;
+use qlty_config::config::CacheConfig;
+use qlty_config::{CacheUsage, EvictionPolicy, Library, Workspace};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+const AUTO_PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
 
 #[derive(Args, Debug)]
-pub struct Prune {}
+pub struct Prune {
+    /// Evict the least recently used entries from the global cache until it is
+    /// smaller than SIZE, such as 20GB. Results are evicted before tools.
+    #[arg(long, value_name = "SIZE")]
+    pub max_size: Option<ByteSize>,
+
+    /// Evict tools and results from the global cache which have not been used
+    /// for AGE, such as 30d or 12h
+    #[arg(long, value_name = "AGE", value_parser = parse_age)]
+    pub max_age: Option<Duration>,
+}
 
 impl Prune {
     pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        if self.max_size.is_none() && self.max_age.is_none() {
+            return self.prune_project();
+        }
+
+        let usages = Library::evict(&EvictionPolicy {
+            max_age: self.max_age,
+            max_size: self.max_size.map(|size| size.as_u64()),
+        })?;
+
+        print_usages(&usages)?;
+        CommandSuccess::ok()
+    }
+
+    fn prune_project(&self) -> Result<CommandSuccess, CommandError> {
         let workspace = Workspace::new()?;
         let library = workspace.library()?;
 
@@ -77,3 +107,167 @@ impl Prune {
         CommandSuccess::ok()
     }
 }
+
+fn print_usages(usages: &[CacheUsage]) -> Result<()> {
+    let rows = usages
+        .iter()
+        .map(|usage| {
+            vec![
+                usage.category.to_string().cell().justify(Justify::Left),
+                format!("{}", usage.evicted_entries)
+                    .cell()
+                    .justify(Justify::Right),
+                ByteSize(usage.evicted_bytes)
+                    .to_string_as(true)
+                    .cell()
+                    .justify(Justify::Right),
+                format!("{}", usage.remaining_entries)
+                    .cell()
+                    .justify(Justify::Right),
+                ByteSize(usage.remaining_bytes)
+                    .to_string_as(true)
+                    .cell()
+                    .justify(Justify::Right),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = rows
+        .table()
+        .title(vec![
+            "Category".cell(),
+            "Entries Removed".cell().justify(Justify::Right),
+            "Bytes Removed".cell().justify(Justify::Right),
+            "Entries Remaining".cell().justify(Justify::Right),
+            "Bytes Remaining".cell().justify(Justify::Right),
+        ])
+        .border(Border::builder().build())
+        .separator(
+            Separator::builder()
+                .title(Some(HorizontalLine::default()))
+                .column(Some(VerticalLine::default()))
+                .build(),
+        );
+    print_stdout(table)?;
+
+    let reclaimed = usages.iter().map(|usage| usage.evicted_bytes).sum();
+    eprintln!();
+    eprintln!(
+        "{}",
+        style(format!(
+            "Reclaimed {}",
+            ByteSize(reclaimed).to_string_as(true)
+        ))
+        .bold()
+    );
+
+    Ok(())
+}
+
+/// Prunes the global cache using the limits set in the `[cache]` section of
+/// `qlty.toml`. Measuring the cache walks every tool installation, so this
+/// runs at most once a day.
+pub fn auto_prune(config: &CacheConfig) -> Result<()> {
+    if config.max_size.is_none() && config.max_age.is_none() {
+        return Ok(());
+    }
+
+    let policy = EvictionPolicy {
+        max_age: config
+            .max_age
+            .as_deref()
+            .map(parse_age)
+            .transpose()
+            .map_err(|err| anyhow!("Invalid cache max_age: {}", err))?,
+        max_size: config
+            .max_size
+            .as_deref()
+            .map(|size| size.parse::<ByteSize>().map(|size| size.as_u64()))
+            .transpose()
+            .map_err(|err| anyhow!("Invalid cache max_size: {}", err))?,
+    };
+
+    let marker = Library::global_cache_root()?.join("last-prune");
+
+    if let Ok(modified) = marker.metadata().and_then(|metadata| metadata.modified()) {
+        if modified.elapsed().unwrap_or_default() < AUTO_PRUNE_INTERVAL {
+            return Ok(());
+        }
+    }
+
+    std::fs::create_dir_all(Library::global_cache_root()?)?;
+    std::fs::write(&marker, "")?;
+
+    let timer = Instant::now();
+    let usages = Library::evict(&policy)?;
+    let reclaimed: u64 = usages.iter().map(|usage| usage.evicted_bytes).sum();
+
+    if reclaimed > 0 {
+        info!(
+            "Pruned {} from the cache in {:.2}s",
+            ByteSize(reclaimed).to_string_as(true),
+            timer.elapsed().as_secs_f32()
+        );
+    }
+
+    Ok(())
+}
+
+/// Parses a duration such as `90m`, `12h`, `30d` or `2w`
+fn parse_age(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_start);
+
+    let Ok(amount) = amount.parse::<u64>() else {
+        bail!(
+            "Invalid age: {} (expected a number and a unit, such as 30d)",
+            value
+        );
+    };
+
+    let seconds = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => bail!("Invalid age unit in {} (expected s, m, h, d or w)", value),
+    };
+
+    let Some(seconds) = amount.checked_mul(seconds) else {
+        bail!("Invalid age: {} is too large", value);
+    };
+
+    let age = Duration::from_secs(seconds);
+
+    if age < EvictionPolicy::MIN_AGE {
+        bail!(
+            "Invalid age: {} is shorter than {}h, and entries used more recently may belong to a running check",
+            value,
+            EvictionPolicy::MIN_AGE.as_secs() / 3600
+        );
+    }
+
+    Ok(age)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("90m").unwrap(), Duration::from_secs(90 * 60));
+        assert_eq!(parse_age("30d").unwrap(), Duration::from_secs(30 * 86400));
+        assert_eq!(parse_age("2w").unwrap(), Duration::from_secs(14 * 86400));
+        assert_eq!(parse_age("1h").unwrap(), EvictionPolicy::MIN_AGE);
+        assert!(parse_age("30m").is_err());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+        assert!(parse_age(&format!("{}w", u64::MAX / 60)).is_err());
+    }
+}
@@ -1,22 +1,41 @@
-use crate::format::SarifFormatter;
+mod merge;
+
+use crate::commands::cache::auto_prune;
+use crate::files_from::target_paths;
+use crate::format::{GitHubFormatter, OutputFormat};
+use crate::ui::print_profile;
 use crate::ui::ApplyMode;
 use crate::ui::ErrorsFormatter;
 use crate::ui::Steps;
 use crate::ui::TextFormatter;
+use crate::watcher::watch;
 use crate::{Arguments, CommandError, CommandSuccess, Trigger};
 use anyhow::bail;
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, Subcommand};
 use console::{style, Emoji};
+use qlty_analysis::git::{merge_base, GitDiff, Worktree};
+use qlty_analysis::workspace_entries::TargetMode;
+use qlty_check::executor::profiler::write_chrome_trace;
 use qlty_check::planner::Plan;
-use qlty_check::{planner::Planner, CheckFilter, Executor, Processor, Report, Settings};
+use qlty_check::{
+    planner::Planner, Baseline, CheckFilter, Executor, Processor, Report, Settings, Shard,
+    ShardReport,
+};
 use qlty_config::Workspace;
-use qlty_formats::{Formatter, JsonFormatter};
+use qlty_coverage::ci::{GitHub, CI};
+use qlty_coverage::patch::PatchCoverage;
+use qlty_coverage::publish::{
+    Planner as CoveragePlanner, Processor as CoverageProcessor, Reader as CoverageReader,
+    Settings as CoverageSettings,
+};
 use qlty_types::analysis::v1::ExecutionVerb;
+use qlty_types::analysis::v1::Issue;
 use qlty_types::analysis::v1::Level;
+use qlty_types::tests::v1::FileCoverage;
 use std::io::BufRead as _;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread;
 use tracing::debug;
 use tracing::warn;
@@ -26,7 +45,11 @@ static THINKING: Emoji<'_, '_> = Emoji("🤔  ", "");
 static FORMATTING: Emoji<'_, '_> = Emoji("📝  ", "");
 
 #[derive(Args, Clone, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Check {
+    #[command(subcommand)]
+    pub command: Option<CheckCommand>,
+
     /// Check all files, not just changed
     #[arg(short, long, conflicts_with = "upstream")]
     pub all: bool,
@@ -94,10 +117,43 @@ pub struct Check {
     #[arg(long)]
     pub upstream: Option<String>,
 
+    /// Check files changed between two commits, such as `main..feature`, as they are
+    /// at the later commit
+    #[arg(long, value_name = "RANGE", conflicts_with_all = ["all", "upstream", "sample", "watch", "fix"])]
+    pub range: Option<String>,
+
+    /// Read the files to check from a file, or from stdin with `-`
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["all", "upstream", "range", "sample"])]
+    pub files_from: Option<PathBuf>,
+
+    /// Only report issues which do not already exist at the upstream merge base
+    #[arg(long, conflicts_with_all = ["all", "sample", "range", "files_from", "write_baseline"])]
+    pub new_only: bool,
+
+    /// Watch for file changes and re-check the changed files
+    #[arg(long, conflicts_with_all = ["sample", "new_only", "write_baseline"])]
+    pub watch: bool,
+
+    /// Check only the targets in shard N of M, to split a check across machines
+    #[arg(long, value_name = "N/M", conflicts_with_all = ["sample", "new_only", "watch", "write_baseline"])]
+    pub shard: Option<Shard>,
+
+    /// Where to write the partial report for `qlty check merge` [default: qlty-shard-N-of-M.json]
+    #[arg(long, value_name = "FILE", requires = "shard")]
+    pub shard_report: Option<PathBuf>,
+
+    /// Report uncovered added lines from a coverage report, or show coverage in HTML output
+    #[arg(long, value_name = "REPORT")]
+    pub coverage: Vec<String>,
+
     /// Disable caching issues
     #[arg(long)]
     pub no_cache: bool,
 
+    /// Write a Chrome trace of install, prepare, invocation and parse timings to a file
+    #[arg(long, value_name = "FILE")]
+    pub profile: Option<PathBuf>,
+
     /// Print errors to stderr
     #[arg(long)]
     pub print_errors: bool,
@@ -106,14 +162,30 @@ pub struct Check {
     #[arg(long, value_enum, default_value = "fmt")]
     fail_level: Level,
 
-    /// JSON output
-    #[arg(long, hide = true, conflicts_with = "sarif")]
+    /// Output format [default: text, plus annotations when running in GitHub Actions]
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// [DEPRECATED, use --format=json] JSON output
+    #[arg(long, hide = true, conflicts_with_all = ["format", "sarif", "gitlab"])]
     json: bool,
 
-    /// SARIF output
-    #[arg(long, conflicts_with = "json")]
+    /// [DEPRECATED, use --format=sarif] SARIF output
+    #[arg(long, hide = true, conflicts_with_all = ["format", "json", "gitlab"])]
     sarif: bool,
 
+    /// [DEPRECATED, use --format=gitlab] GitLab Code Quality output
+    #[arg(long, hide = true, conflicts_with_all = ["format", "json", "sarif"])]
+    gitlab: bool,
+
+    /// Suppress issues recorded in a baseline file [default: baseline from qlty.toml]
+    #[arg(long, value_name = "FILE", conflicts_with = "write_baseline")]
+    baseline: Option<PathBuf>,
+
+    /// Record the fingerprints of all current issues to a baseline file
+    #[arg(long, value_name = "FILE")]
+    write_baseline: Option<PathBuf>,
+
     /// Allow individual plugins to be skipped if they fail or crash
     #[arg(hide = true, long, conflicts_with = "fail_level")]
     skip_errored_plugins: bool,
@@ -125,17 +197,77 @@ pub struct Check {
     pub paths: Vec<PathBuf>,
 }
 
+#[derive(Subcommand, Clone, Debug)]
+pub enum CheckCommand {
+    /// Combine the partial reports from a sharded check into a single report
+    Merge(merge::Merge),
+}
+
 impl Check {
     pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        if let Some(CheckCommand::Merge(merge)) = &self.command {
+            return merge.execute();
+        }
+
         self.validate_options()?;
+        self.print_deprecation_warnings();
 
         let workspace = Workspace::require_initialized()?;
         workspace.fetch_sources()?;
 
-        let settings = self.build_settings(&workspace)?;
+        let mut settings = self.build_settings(&workspace)?;
 
+        // A commit range is analyzed in a checkout of its end, leaving the working tree alone
+        let _range_worktree = match &self.range {
+            Some(range) => {
+                let worktree = Worktree::checkout_range(&workspace.root, range)?;
+                prepare_worktree(&workspace, &worktree)?;
+                settings.cwd = worktree_cwd(&settings.cwd, &workspace.root, &worktree);
+                settings.root = worktree.path().to_path_buf();
+                Some(worktree)
+            }
+            None => None,
+        };
+
+        if self.files_from.is_some() && settings.paths.is_empty() {
+            eprintln!("{}", style("No files to check").dim());
+            return CommandSuccess::ok();
+        }
+
+        if self.watch {
+            let cache_config = workspace.config()?.cache;
+
+            return watch(&workspace.root, &workspace.config()?.ignore, |paths| {
+                // Pruning runs at most once a day, so a long watch session still prunes
+                if let Err(err) = auto_prune(&cache_config) {
+                    warn!("Failed to prune the cache: {:?}", err);
+                }
+
+                if paths.is_empty() {
+                    self.run(&settings)
+                } else {
+                    let mut settings = settings.clone();
+                    settings.all = false;
+                    settings.upstream = None;
+                    settings.paths = paths.to_vec();
+                    self.run(&settings)
+                }
+            });
+        }
+
+        let result = self.run(&settings);
+
+        if let Err(err) = auto_prune(&workspace.config()?.cache) {
+            warn!("Failed to prune the cache: {:?}", err);
+        }
+
+        result
+    }
+
+    fn run(&self, settings: &Settings) -> Result<CommandSuccess, CommandError> {
         let mut counter = 0;
         let mut dirty = true;
+        let mut upstream_issues = None;
 
         while dirty {
             if counter > 0 {
@@ -152,7 +284,7 @@ impl Check {
                 steps.start(THINKING, "Planning... ");
             }
 
-            let plan = Planner::new(ExecutionVerb::Check, &settings)?.compute()?;
+            let mut plan = Planner::new(ExecutionVerb::Check, settings)?.compute()?;
 
             if self.verbose >= 1 {
                 steps.start(LOOKING_GLASS, format!("Analyzing{}...", plan.description()));
@@ -164,7 +296,20 @@ impl Check {
             }
 
             let executor = Executor::new(&plan);
-            let results = executor.install_and_invoke()?;
+            let mut results = executor.install_and_invoke()?;
+            let file_coverages = self.file_coverages(&plan)?;
+            results
+                .issues
+                .extend(self.coverage_issues(&plan, &file_coverages)?);
+
+            if self.new_only {
+                if upstream_issues.is_none() {
+                    upstream_issues = Some(self.upstream_issues(settings, &plan)?);
+                }
+
+                plan.transformers
+                    .push(Box::new(upstream_issues.clone().unwrap()));
+            }
 
             let mut processor = Processor::new(&plan, results);
             let report = processor.compute()?;
@@ -174,11 +319,16 @@ impl Check {
                     steps.start(FORMATTING, "Formatting...");
                 }
 
-                self.format_after_fix(&settings, &report)?;
+                self.format_after_fix(settings, &report)?;
             }
 
-            dirty = self.write_stdout(&report, &plan, &settings)?;
+            dirty = self.write_stdout(&report, &plan, settings, &file_coverages)?;
             self.write_stderr(&report)?;
+            self.write_baseline(&report, &plan)?;
+            self.write_shard_report(&report)?;
+            self.write_upstream_summary(upstream_issues.as_ref());
+            self.write_step_summary(&report)?;
+            self.write_profile(&executor)?;
 
             if !dirty {
                 if !self.no_error && !self.skip_errored_plugins && report.has_errors() {
@@ -268,6 +418,57 @@ impl Check {
             return Err(CommandError::InvalidOptions { message });
         }
 
+        if self.range.is_some() && !self.paths.is_empty() {
+            let message = format!(
+                "the argument '{}' cannot be used with specified {}",
+                style("--range").yellow(),
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
+        if self.new_only && !self.paths.is_empty() {
+            let message = format!(
+                "the argument '{}' cannot be used with specified {}",
+                style("--new-only").yellow(),
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
+        // Uncovered lines are only reported for lines added in a diff, but HTML output shows
+        // coverage for whichever files are checked
+        let html = self.output_format() == Some(OutputFormat::Html);
+
+        if !self.coverage.is_empty() && !self.paths.is_empty() && !html {
+            let message = format!(
+                "the argument '{}' cannot be used with specified {}",
+                style("--coverage").yellow(),
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
+        for (used, argument) in [
+            (self.all && !html, "--all"),
+            (self.sample.is_some() && !html, "--sample"),
+            (self.files_from.is_some() && !html, "--files-from"),
+            (self.watch, "--watch"),
+        ] {
+            if !self.coverage.is_empty() && used {
+                let message = format!(
+                    "the argument '{}' cannot be used with '{}'",
+                    style("--coverage").yellow(),
+                    style(argument).yellow()
+                );
+
+                return Err(CommandError::InvalidOptions { message });
+            }
+        }
+
         for path in &self.paths {
             if !path.exists() {
                 let message = format!("path '{}' does not exist", path.display());
@@ -292,16 +493,19 @@ impl Check {
         settings.formatters = !self.no_formatters;
         settings.filters = CheckFilter::from_optional_list(self.filter.clone());
         settings.upstream = self.compute_upstream(&workspace)?;
+        settings.range = self.range.clone();
+        settings.shard = self.shard;
         settings.level = self.level;
-        settings.fail_level = if self.no_fail {
+        settings.fail_level = if self.no_fail || self.write_baseline.is_some() {
             None
         } else {
             Some(self.fail_level)
         };
         settings.cache = !self.no_cache;
-        settings.paths = self.paths.clone();
+        settings.paths = target_paths(&self.paths, self.files_from.as_deref())?;
         settings.trigger = self.trigger.into();
         settings.skip_errored_plugins = self.skip_errored_plugins;
+        settings.baseline = self.compute_baseline(workspace, &settings.root)?;
 
         // Get auth token if AI is enabled
         if settings.ai {
@@ -317,6 +521,19 @@ impl Check {
         Ok(settings)
     }
 
+    fn compute_baseline(&self, workspace: &Workspace, root: &Path) -> Result<Option<PathBuf>> {
+        // Recording a new baseline must see every issue
+        if self.write_baseline.is_some() {
+            return Ok(None);
+        }
+
+        if self.baseline.is_some() {
+            return Ok(self.baseline.clone());
+        }
+
+        Ok(workspace.config()?.baseline.map(|path| root.join(path)))
+    }
+
     fn compute_upstream(&self, workspace: &Workspace) -> Result<Option<String>> {
         if self.upstream_from_pre_push {
             let mut buffer = String::new();
@@ -357,28 +574,294 @@ impl Check {
         }
     }
 
-    fn write_stdout(&self, report: &Report, plan: &Plan, settings: &Settings) -> Result<bool> {
-        if self.json {
-            let formatter = JsonFormatter::new(report.issues.clone());
+    /// Analyzes the changed files as they were at the upstream merge base (or HEAD
+    /// when there is no upstream) so that issues which already existed can be hidden
+    fn upstream_issues(&self, settings: &Settings, plan: &Plan) -> Result<Baseline> {
+        let repository = plan.workspace.repo()?;
+
+        let commit = match &plan.target_mode {
+            TargetMode::UpstreamDiff(upstream) => merge_base(&repository, upstream)?,
+            TargetMode::HeadDiff => repository.head()?.peel_to_commit()?,
+            _ => bail!("--new-only requires comparing against an upstream"),
+        };
+
+        let git_diff = GitDiff::compute(plan.target_mode.diff_mode(), &plan.workspace.root)?;
+        let worktree = Worktree::checkout(&plan.workspace.root, &commit.id().to_string())?;
+
+        let paths = git_diff
+            .changed_files
+            .iter()
+            .map(|path| worktree.path().join(path))
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+
+        if paths.is_empty() {
+            return Ok(Baseline::from_issues(&[]));
+        }
+
+        prepare_worktree(&plan.workspace, &worktree)?;
+
+        let mut upstream_settings = settings.clone();
+        upstream_settings.root = worktree.path().to_path_buf();
+        upstream_settings.paths = paths;
+        upstream_settings.upstream = None;
+        upstream_settings.fix = false;
+        upstream_settings.ai = false;
+        upstream_settings.progress = false;
+        upstream_settings.baseline = None;
+
+        debug!(
+            "Analyzing {} files at {} for --new-only",
+            upstream_settings.paths.len(),
+            commit.id()
+        );
+
+        upstream_settings.cwd = worktree_cwd(&settings.cwd, &plan.workspace.root, &worktree);
+
+        let report = Self::check_upstream(&upstream_settings)?;
+        Ok(Baseline::from_issues(&report.issues))
+    }
+
+    fn check_upstream(settings: &Settings) -> Result<Report> {
+        let plan = Planner::new(ExecutionVerb::Check, settings)?.compute()?;
+        let results = Executor::new(&plan).install_and_invoke()?;
+        Processor::new(&plan, results).compute()
+    }
+
+    fn file_coverages(&self, plan: &Plan) -> Result<Vec<FileCoverage>> {
+        if self.coverage.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let settings = CoverageSettings {
+            dry_run: true,
+            quiet: true,
+            paths: self.coverage.clone(),
+            ..Default::default()
+        };
+
+        let coverage_plan = CoveragePlanner::new(&plan.config, &settings).compute()?;
+        let results = CoverageReader::new(&coverage_plan).read()?;
+        let report = CoverageProcessor::new(&coverage_plan, results).compute()?;
+
+        Ok(report.file_coverages)
+    }
+
+    fn coverage_issues(&self, plan: &Plan, file_coverages: &[FileCoverage]) -> Result<Vec<Issue>> {
+        if self.coverage.is_empty() {
+            return Ok(vec![]);
+        }
+
+        if !plan.target_mode.is_diff() {
+            if self.output_format() == Some(OutputFormat::Html) {
+                return Ok(vec![]);
+            }
+
+            bail!("--coverage can only be used when checking a diff");
+        }
+
+        let git_diff = GitDiff::compute(plan.target_mode.diff_mode(), &plan.workspace.root)?;
+        let patch = PatchCoverage::compute(file_coverages, git_diff.added_lines());
+
+        Ok(patch.issues())
+    }
+
+    fn write_stdout(
+        &self,
+        report: &Report,
+        plan: &Plan,
+        settings: &Settings,
+        file_coverages: &[FileCoverage],
+    ) -> Result<bool> {
+        let format = self.output_format().unwrap_or_default();
+
+        if let Some(formatter) =
+            format.report_formatter(report, &plan.workspace.root, file_coverages)
+        {
             formatter.write_to(&mut std::io::stdout())?;
-            Ok(false)
+            return Ok(false);
+        }
+
+        let apply_mode = if self.fix {
+            ApplyMode::All
+        } else if self.no_fix {
+            ApplyMode::None
+        } else {
+            ApplyMode::Ask
+        };
+
+        let mut formatter =
+            TextFormatter::new(report, &plan.workspace, settings, self.summary, apply_mode);
+
+        let dirty = formatter.write_to(&mut std::io::stdout())?;
+
+        if self.github_annotations() {
+            GitHubFormatter::boxed(report.issues.clone()).write_to(&mut std::io::stdout())?;
+        }
+
+        Ok(dirty)
+    }
+
+    fn write_baseline(&self, report: &Report, plan: &Plan) -> Result<()> {
+        if let Some(path) = &self.write_baseline {
+            let count = Baseline::write(path, &report.issues)?;
+
+            eprintln!(
+                "Wrote {} baseline {} to {}",
+                count,
+                if count == 1 { "entry" } else { "entries" },
+                path.display()
+            );
+        } else if let Some(baseline) = &plan.baseline {
+            let summary = baseline.summary();
+            let path = baseline
+                .path()
+                .strip_prefix(&plan.workspace.root)
+                .unwrap_or(baseline.path());
+
+            if summary.suppressed > 0 {
+                eprintln!(
+                    "{}",
+                    style(format!(
+                        "Suppressed {} {} from baseline {}",
+                        summary.suppressed,
+                        if summary.suppressed == 1 {
+                            "issue"
+                        } else {
+                            "issues"
+                        },
+                        path.display()
+                    ))
+                    .dim()
+                );
+            }
+
+            // Entries for files or plugins which were not checked cannot be judged stale
+            if plan.target_mode == TargetMode::All
+                && plan.settings.filters.is_empty()
+                && !summary.stale.is_empty()
+            {
+                if summary.stale.len() == 1 {
+                    eprintln!("1 baseline entry no longer matches any issues:");
+                } else {
+                    eprintln!(
+                        "{} baseline entries no longer match any issues:",
+                        summary.stale.len()
+                    );
+                }
+
+                for entry in &summary.stale {
+                    eprintln!("  {}:{} {}", entry.tool, entry.rule_key, entry.path);
+                }
+
+                eprintln!(
+                    "Run {} to remove them.",
+                    style(format!(
+                        "qlty check --all --write-baseline {}",
+                        path.display()
+                    ))
+                    .bold()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_shard_report(&self, report: &Report) -> Result<()> {
+        if let Some(shard) = &self.shard {
+            let path = self.shard_report.clone().unwrap_or_else(|| {
+                PathBuf::from(format!(
+                    "qlty-shard-{}-of-{}.json",
+                    shard.index, shard.count
+                ))
+            });
+
+            ShardReport::write(&path, shard, report)?;
+            eprintln!(
+                "{}",
+                style(format!(
+                    "Wrote report for shard {} to {}",
+                    shard,
+                    path.display()
+                ))
+                .dim()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn write_profile(&self, executor: &Executor) -> Result<()> {
+        if let Some(path) = &self.profile {
+            let spans = executor.profiler().spans();
+            write_chrome_trace(path, &spans)?;
+
+            eprintln!();
+            print_profile(&mut std::io::stderr(), &spans)?;
+            eprintln!(
+                "{}",
+                style(format!("Wrote profile to {}", path.display())).dim()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn write_upstream_summary(&self, upstream_issues: Option<&Baseline>) {
+        if let Some(upstream_issues) = upstream_issues {
+            let suppressed = upstream_issues.summary().suppressed;
+
+            if suppressed > 0 {
+                eprintln!(
+                    "{}",
+                    style(format!(
+                        "Hid {} {} which already existed upstream",
+                        suppressed,
+                        if suppressed == 1 { "issue" } else { "issues" }
+                    ))
+                    .dim()
+                );
+            }
+        }
+    }
+
+    fn output_format(&self) -> Option<OutputFormat> {
+        if self.json {
+            Some(OutputFormat::Json)
         } else if self.sarif {
-            let formatter = SarifFormatter::boxed(report.clone());
-            formatter.write_to(&mut std::io::stdout())?;
-            Ok(false)
+            Some(OutputFormat::Sarif)
+        } else if self.gitlab {
+            Some(OutputFormat::Gitlab)
         } else {
-            let apply_mode = if self.fix {
-                ApplyMode::All
-            } else if self.no_fix {
-                ApplyMode::None
-            } else {
-                ApplyMode::Ask
-            };
+            self.format
+        }
+    }
 
-            let mut formatter =
-                TextFormatter::new(report, &plan.workspace, settings, self.summary, apply_mode);
+    fn print_deprecation_warnings(&self) {
+        for (flag, deprecated) in [
+            ("json", self.json),
+            ("sarif", self.sarif),
+            ("gitlab", self.gitlab),
+        ] {
+            if deprecated {
+                eprintln!("WARNING: --{flag} is deprecated, use --format={flag} instead\n");
+            }
+        }
+    }
+
+    fn write_step_summary(&self, report: &Report) -> Result<()> {
+        if self.github_annotations() {
+            GitHubFormatter::new(report.issues.clone()).write_step_summary()?;
+        }
+
+        Ok(())
+    }
 
-            formatter.write_to(&mut std::io::stdout())
+    fn github_annotations(&self) -> bool {
+        match self.output_format() {
+            Some(format) => format == OutputFormat::Github,
+            None => GitHub::default().detect(),
         }
     }
 
@@ -391,3 +874,22 @@ impl Check {
         Ok(())
     }
 }
+
+/// Analyzes the worktree with the same plugins as the workspace, regardless of
+/// the config committed there
+fn prepare_worktree(workspace: &Workspace, worktree: &Worktree) -> Result<()> {
+    let worktree_workspace = Workspace::for_root(worktree.path())?;
+    let config_path = worktree_workspace.config_path()?;
+    std::fs::create_dir_all(config_path.parent().unwrap())?;
+    std::fs::copy(workspace.config_path()?, &config_path)?;
+    worktree_workspace.fetch_sources()
+}
+
+/// Targets are matched relative to the current directory, so mirror it in the worktree
+fn worktree_cwd(cwd: &Path, root: &Path, worktree: &Worktree) -> PathBuf {
+    cwd.strip_prefix(root)
+        .map(|relative| worktree.path().join(relative))
+        .ok()
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| worktree.path().to_path_buf())
+}
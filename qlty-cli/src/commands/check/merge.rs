@@ -0,0 +1,88 @@
+use crate::format::OutputFormat;
+use crate::ui::{ApplyMode, TextFormatter};
+use crate::{CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::Args;
+use qlty_check::{Settings, ShardReport};
+use qlty_config::Workspace;
+use qlty_types::analysis::v1::Level;
+use std::path::PathBuf;
+
+#[derive(Args, Clone, Debug)]
+pub struct Merge {
+    /// Exit successfully regardless of what issues are found
+    #[arg(long, conflicts_with = "fail_level")]
+    pub no_fail: bool,
+
+    /// Minimium level of issues to fail on
+    #[arg(long, value_enum, default_value = "fmt")]
+    fail_level: Level,
+
+    /// Output format [default: text]
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Merge even if the reports of some shards are missing, such as when a shard failed
+    #[arg(long)]
+    pub allow_missing_shards: bool,
+
+    /// Print a summary of issues
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Partial reports written by `qlty check --shard`
+    #[arg(required = true)]
+    pub reports: Vec<PathBuf>,
+}
+
+impl Merge {
+    pub fn execute(&self) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::new()?;
+
+        let reports = self
+            .reports
+            .iter()
+            .map(|path| ShardReport::read(path))
+            .collect::<Result<Vec<_>>>()?;
+
+        let fail_level = if self.no_fail {
+            None
+        } else {
+            Some(self.fail_level)
+        };
+
+        let report = ShardReport::merge(&reports, fail_level, self.allow_missing_shards)?;
+
+        match self
+            .format
+            .unwrap_or_default()
+            .report_formatter(&report, &workspace.root, &[])
+        {
+            Some(formatter) => formatter.write_to(&mut std::io::stdout())?,
+            None => {
+                let settings = Settings {
+                    root: workspace.root.clone(),
+                    fail_level,
+                    ..Default::default()
+                };
+
+                TextFormatter::new(
+                    &report,
+                    &workspace,
+                    &settings,
+                    self.summary,
+                    ApplyMode::None,
+                )
+                .write_to(&mut std::io::stdout())?;
+            }
+        }
+
+        Ok(CommandSuccess {
+            unformatted_count: Some(report.unformatted_count()),
+            issues_count: Some(report.counts.total_issues),
+            security_issues_count: Some(report.counts.total_security_issues),
+            fail: report.is_failure(),
+            ..Default::default()
+        })
+    }
+}
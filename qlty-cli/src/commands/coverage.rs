@@ -1,8 +1,10 @@
 mod complete;
+mod diff;
 mod publish;
 mod transform;
 mod utils;
 pub use complete::Complete;
+pub use diff::Diff;
 pub use publish::Publish;
 pub use transform::Transform;
 
@@ -28,6 +30,9 @@ pub enum Commands {
 
     /// Mark coverage as complete on Qlty Cloud
     Complete(Complete),
+
+    /// Show coverage of lines changed since the upstream ref
+    Diff(Diff),
 }
 
 impl Arguments {
@@ -36,6 +41,7 @@ impl Arguments {
             Commands::Transform(command) => command.execute(args),
             Commands::Publish(command) => command.execute(args),
             Commands::Complete(command) => command.execute(args),
+            Commands::Diff(command) => command.execute(args),
         }
     }
 }
@@ -0,0 +1,152 @@
+use super::utils::{load_config, print_initial_messages};
+use crate::{CommandError, CommandSuccess};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use console::style;
+use qlty_analysis::git::{compute_upstream, DiffMode, GitDiff};
+use qlty_config::Workspace;
+use qlty_coverage::formats::Formats;
+use qlty_coverage::patch::PatchCoverage;
+use qlty_coverage::print::{print_patch_coverage_as_json, print_patch_coverage_as_text};
+use qlty_coverage::publish::{Planner, Processor, Reader, Settings};
+
+#[derive(Debug, Args)]
+pub struct Diff {
+    #[arg(long, value_enum)]
+    /// The format of the coverage report.
+    /// If not specified, the format will be inferred from the file extension or contents.
+    pub format: Option<Formats>,
+
+    #[arg(long)]
+    /// Upstream base ref to compare against. Defaults to the remote default branch.
+    pub upstream: Option<String>,
+
+    #[arg(long)]
+    /// The prefix to add to file paths in coverage payloads, to make them match the project's directory structure.
+    pub add_prefix: Option<String>,
+
+    #[arg(long)]
+    /// The prefix to remove from absolute paths in coverage payloads to make them relative to the project root.
+    /// This is usually the directory in which the tests were run. Defaults to the root of the git repository.
+    pub strip_prefix: Option<String>,
+
+    #[arg(long, value_parser = clap::value_parser!(f64))]
+    /// Exit with a non-zero status if patch coverage is below this percentage (0-100).
+    pub fail_under: Option<f64>,
+
+    #[arg(long)]
+    /// JSON output
+    pub json: bool,
+
+    #[clap(long, short)]
+    /// Suppresses most of the standard output messages.
+    pub quiet: bool,
+
+    // Paths to coverage reports
+    pub paths: Vec<String>,
+}
+
+impl Diff {
+    pub fn execute(&self, _args: &crate::Arguments) -> Result<CommandSuccess, CommandError> {
+        self.validate_options()?;
+        print_initial_messages(self.quiet || self.json);
+
+        let workspace = Workspace::new()?;
+        let upstream = compute_upstream(&workspace, &self.upstream).ok_or_else(|| {
+            anyhow!("Unable to determine an upstream ref. Please specify one with --upstream.")
+        })?;
+
+        let settings = Settings {
+            add_prefix: self.add_prefix.clone(),
+            dry_run: true,
+            quiet: self.quiet,
+            report_format: self.format,
+            strip_prefix: self.strip_prefix.clone(),
+            paths: self.paths.clone(),
+            ..Default::default()
+        };
+
+        let plan = Planner::new(&load_config(), &settings).compute()?;
+        let results = Reader::new(&plan).read()?;
+        let report = Processor::new(&plan, results).compute()?;
+
+        let git_diff = GitDiff::compute(
+            DiffMode::UpstreamToWorkdir(upstream.clone()),
+            &workspace.root,
+        )?;
+        let patch = PatchCoverage::compute(&report.file_coverages, git_diff.added_lines());
+
+        if self.json {
+            print_patch_coverage_as_json(&patch)?;
+        } else {
+            self.print_summary(&upstream, &patch)?;
+        }
+
+        if let Some(fail_under) = self.fail_under {
+            if patch.total_lines() > 0 && patch.totals.coverage_percentage < fail_under {
+                if !self.quiet && !self.json {
+                    eprintln!(
+                        "{}",
+                        style(format!(
+                            "Patch coverage {:.2}% is below the minimum of {:.2}%",
+                            patch.totals.coverage_percentage, fail_under
+                        ))
+                        .red()
+                        .bold()
+                    );
+                }
+
+                return Ok(CommandSuccess {
+                    fail: true,
+                    ..Default::default()
+                });
+            }
+        }
+
+        CommandSuccess::ok()
+    }
+
+    fn validate_options(&self) -> Result<(), CommandError> {
+        if let Some(fail_under) = self.fail_under {
+            if !(0.0..=100.0).contains(&fail_under) {
+                return Err(CommandError::InvalidOptions {
+                    message: "--fail-under must be between 0 and 100".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn print_summary(&self, upstream: &str, patch: &PatchCoverage) -> Result<()> {
+        if patch.total_lines() == 0 {
+            if !self.quiet {
+                eprintln!("No coverable lines changed since {}", upstream);
+            }
+
+            return Ok(());
+        }
+
+        if !self.quiet {
+            eprintln!("Patch coverage against {}", upstream);
+            eprintln!();
+        }
+
+        print_patch_coverage_as_text(patch)?;
+
+        if !self.quiet {
+            eprintln!();
+            eprintln!(
+                "    {}",
+                style(format!(
+                    "Patch Coverage:      {:.2}%",
+                    patch.totals.coverage_percentage
+                ))
+                .bold()
+            );
+            eprintln!();
+        }
+
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use num_format::{Locale, ToFormattedString as _};
 use qlty_coverage::formats::Formats;
 use qlty_coverage::print::{print_report_as_json, print_report_as_text};
 use qlty_coverage::publish::{Plan, Planner, Processor, Reader, Report, Settings, Upload};
+use qlty_coverage::threshold::{ThresholdChecker, ThresholdResults};
 use qlty_coverage::token::load_auth_token;
 use qlty_coverage::validate::{ValidationStatus, Validator};
 use std::io::Write as _;
@@ -120,6 +121,11 @@ pub struct Publish {
     /// Default is 90.
     pub validate_file_threshold: Option<f64>,
 
+    #[arg(long, value_parser = clap::value_parser!(f64))]
+    /// Exit with a non-zero status if total line coverage is below this percentage (0-100).
+    /// Overrides `minimum` under `[coverage]` in qlty.toml.
+    pub fail_under: Option<f64>,
+
     #[arg(long)]
     /// Mark this upload as incomplete. This is useful when issuing multiple qlty coverage publish commands for the same coverage tag.
     /// The server will merge the uploads into a single report when qlty coverage complete is called.
@@ -147,7 +153,9 @@ impl Publish {
             load_auth_token(&self.token, self.project.as_deref())?
         };
 
-        let plan = Planner::new(&load_config(), &settings).compute()?;
+        let config = load_config();
+        let threshold_checker = ThresholdChecker::new(&config.coverage, self.fail_under)?;
+        let plan = Planner::new(&config, &settings).compute()?;
 
         self.validate_plan(&plan)?;
 
@@ -183,11 +191,17 @@ impl Publish {
             }
         }
 
+        let threshold_results = threshold_checker.check(&report.file_coverages);
+
+        if !threshold_checker.is_empty() {
+            self.print_threshold_results(&threshold_results);
+        }
+
         let export = report.export_to(self.output_dir.clone())?;
         self.print_export_status(&export.to);
 
         if self.dry_run {
-            return CommandSuccess::ok();
+            return Self::threshold_outcome(&threshold_results);
         }
 
         self.print_section_header(" AUTHENTICATION ");
@@ -203,7 +217,75 @@ impl Publish {
         let bytes = export.total_size_bytes()?;
         self.print_upload_complete(bytes, timer.elapsed().as_secs_f32(), &upload.url);
 
-        CommandSuccess::ok()
+        Self::threshold_outcome(&threshold_results)
+    }
+
+    fn threshold_outcome(results: &ThresholdResults) -> Result<CommandSuccess, CommandError> {
+        if results.passed() {
+            CommandSuccess::ok()
+        } else {
+            Ok(CommandSuccess {
+                fail: true,
+                ..Default::default()
+            })
+        }
+    }
+
+    // Failures are shown even when quiet, since they determine the exit status
+    fn print_threshold_results(&self, results: &ThresholdResults) {
+        if results.passed() {
+            if !self.quiet {
+                self.print_section_header(" THRESHOLDS ");
+                eprintln!("    {}", style("All coverage thresholds met").green());
+                eprintln!();
+            }
+
+            return;
+        }
+
+        eprintln!("{}", style(" THRESHOLDS ").bold().reverse());
+        eprintln!();
+
+        let mut tw = TabWriter::new(vec![]);
+
+        tw.write_all(
+            format!(
+                "    {}\t{}\t{}\t{}\n",
+                style("Path").bold().underlined(),
+                style("Lines").bold().underlined(),
+                style("Coverage").bold().underlined(),
+                style("Minimum").bold().underlined(),
+            )
+            .as_bytes(),
+        )
+        .ok();
+
+        for failure in results.failures() {
+            tw.write_all(
+                format!(
+                    "    {}\t{}/{}\t{:.2}%\t{:.2}%\n",
+                    failure.path.as_deref().unwrap_or("(total)"),
+                    failure.covered_lines.to_formatted_string(&Locale::en),
+                    failure.coverable_lines.to_formatted_string(&Locale::en),
+                    failure.coverage_percentage,
+                    failure.minimum,
+                )
+                .as_bytes(),
+            )
+            .ok();
+        }
+
+        tw.flush().ok();
+        let table = String::from_utf8(tw.into_inner().unwrap_or_default()).unwrap_or_default();
+        eprintln!("{}", table);
+
+        eprintln!(
+            "    {}",
+            style("Coverage is below the configured minimum")
+                .red()
+                .bold()
+        );
+        eprintln!();
     }
 
     fn print_upload(&self, upload: &Upload) {
@@ -411,7 +493,15 @@ impl Publish {
             eprintln!("\n    {}\n", style("Missing code files:").bold().yellow());
 
             for path in missing_files.iter().take(paths_to_show) {
-                eprintln!("      {}", style(path.to_string()).yellow());
+                if let Some(candidates) = report.ambiguous_files.get(*path) {
+                    eprintln!(
+                        "      {} {}",
+                        style(path.to_string()).yellow(),
+                        style(format!("(ambiguous, matches {} files)", candidates.len())).dim()
+                    );
+                } else {
+                    eprintln!("      {}", style(path.to_string()).yellow());
+                }
             }
 
             if !show_all && paths_to_show < missing_files.len() {
@@ -486,6 +576,52 @@ impl Publish {
             .bold()
         );
         eprintln!();
+
+        if report.totals.total_branches() > 0 {
+            self.print_coverage_counts(
+                "Branch",
+                "Branches",
+                report.totals.covered_branches,
+                report.totals.uncovered_branches,
+                report.totals.branch_coverage_percentage,
+            );
+        }
+
+        if report.totals.total_functions() > 0 {
+            self.print_coverage_counts(
+                "Function",
+                "Functions",
+                report.totals.covered_functions,
+                report.totals.uncovered_functions,
+                report.totals.function_coverage_percentage,
+            );
+        }
+    }
+
+    fn print_coverage_counts(
+        &self,
+        kind: &str,
+        plural: &str,
+        covered: u64,
+        uncovered: u64,
+        percentage: f64,
+    ) {
+        let covered = covered.to_formatted_string(&Locale::en);
+        let uncovered = uncovered.to_formatted_string(&Locale::en);
+        let max_length = covered.len().max(uncovered.len());
+
+        let covered_label = format!("Covered {plural}:");
+        let uncovered_label = format!("Uncovered {plural}:");
+        let coverage_label = format!("{kind} Coverage:");
+
+        eprintln!("    {covered_label:<21}{covered:>max_length$}");
+        eprintln!("    {uncovered_label:<21}{uncovered:>max_length$}");
+        eprintln!();
+        eprintln!(
+            "    {}",
+            style(format!("{coverage_label:<21}{percentage:.2}%")).bold()
+        );
+        eprintln!();
     }
 
     fn print_export_status(&self, export_path: &Option<PathBuf>) {
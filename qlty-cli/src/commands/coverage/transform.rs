@@ -6,8 +6,8 @@ use qlty_coverage::{
     formats::Formats,
     print::{print_file_coverages_as_json, print_file_coverages_as_text},
     transform::{Planner, Processor, Settings},
+    writer::{writer_for, OutputFormats},
 };
-use qlty_formats::{Formatter, JsonEachRowFormatter};
 use qlty_types::tests::v1::FileCoverage;
 use std::path::PathBuf;
 
@@ -33,9 +33,14 @@ pub struct Transform {
     pub strip_prefix: Option<String>,
 
     #[arg(long)]
-    /// The output file name for the transformed coverage report. If not specified, the report will be saved to 'coverage.jsonl'.
+    /// The output file name for the transformed coverage report. If not specified, the report will be saved to
+    /// 'coverage.jsonl', 'lcov.info' or 'cobertura.xml' depending on the output format.
     pub output: Option<String>,
 
+    #[arg(long, value_enum, default_value_t)]
+    /// The format of the transformed coverage report.
+    pub output_format: OutputFormats,
+
     #[arg(long)]
     /// Print coverage
     pub print: bool,
@@ -48,8 +53,10 @@ pub struct Transform {
     /// Suppresses most of the standard output messages.
     pub quiet: bool,
 
-    /// The path to the coverage report, which may include its format (e.g., 'lcov:./coverage/lcov.info').
-    pub path: String,
+    /// The paths to the coverage reports, which may include their format (e.g., 'lcov:./coverage/lcov.info').
+    /// When multiple reports are given, coverage for the same file is merged by summing hits.
+    #[arg(required = true)]
+    pub paths: Vec<String>,
 }
 
 impl Transform {
@@ -57,14 +64,18 @@ impl Transform {
         print_initial_messages(self.quiet);
 
         if !self.quiet {
-            eprintln!("Transforming coverage report {}", self.path);
+            if self.paths.len() > 1 {
+                eprintln!("Merging coverage reports {}", self.paths.join(", "));
+            } else {
+                eprintln!("Transforming coverage report {}", self.paths.join(", "));
+            }
         }
 
         let settings = Settings {
             report_format: self.report_format,
             add_prefix: self.add_prefix.clone(),
             strip_prefix: self.strip_prefix.clone(),
-            path: self.path.clone(),
+            paths: self.paths.clone(),
         };
 
         let planner = Planner::new(&settings);
@@ -81,7 +92,8 @@ impl Transform {
 
             eprintln_unless!(
                 self.quiet,
-                "Exported qlty coverage report to {}",
+                "Exported {} coverage report to {}",
+                self.output_format,
                 self.output()
             );
         }
@@ -92,12 +104,12 @@ impl Transform {
     fn output(&self) -> String {
         self.output
             .clone()
-            .unwrap_or_else(|| "coverage.jsonl".to_string())
+            .unwrap_or_else(|| self.output_format.default_filename().to_string())
     }
 
     fn export(&self, file_coverages: &Vec<FileCoverage>) -> Result<()> {
         let output_path = PathBuf::from(self.output());
-        JsonEachRowFormatter::new(file_coverages.clone()).write_to_file(&output_path)?;
+        writer_for(self.output_format, file_coverages.clone()).write_to_file(&output_path)?;
         Ok(())
     }
 
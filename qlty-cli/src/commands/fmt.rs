@@ -1,7 +1,10 @@
+use crate::files_from::target_paths;
 use crate::ui::{ApplyMode, TextFormatter};
+use crate::watcher::watch;
 use crate::{Arguments, CommandError, CommandSuccess, Trigger};
 use anyhow::Result;
 use clap::Args;
+use console::style;
 use duct::cmd;
 use qlty_check::{planner::Planner, CheckFilter, Executor, Processor, Settings};
 use qlty_config::Workspace;
@@ -44,6 +47,14 @@ pub struct Fmt {
     #[arg(long)]
     pub upstream: Option<String>,
 
+    /// Format files changed between two commits, such as `main..feature`
+    #[arg(long, value_name = "RANGE", conflicts_with_all = ["all", "upstream", "sample", "watch"])]
+    pub range: Option<String>,
+
+    /// Read the files to format from a file, or from stdin with `-`
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["all", "upstream", "range", "sample"])]
+    pub files_from: Option<PathBuf>,
+
     /// Format files in the Git index
     #[arg(long, conflicts_with = "index_file")]
     pub index: bool,
@@ -52,6 +63,10 @@ pub struct Fmt {
     #[arg(long, conflicts_with = "index")]
     pub index_file: Option<PathBuf>,
 
+    /// Watch for file changes and re-format the changed files
+    #[arg(long, conflicts_with_all = ["sample", "index", "index_file"])]
+    pub watch: bool,
+
     /// Files to analyze
     pub paths: Vec<PathBuf>,
 }
@@ -64,7 +79,31 @@ impl Fmt {
         workspace.fetch_sources()?;
 
         let settings = self.build_settings()?;
-        let plan = Planner::new(ExecutionVerb::Fmt, &settings)?.compute()?;
+
+        if self.files_from.is_some() && settings.paths.is_empty() {
+            eprintln!("{}", style("No files to format").dim());
+            return CommandSuccess::ok();
+        }
+
+        if self.watch {
+            return watch(&workspace.root, &workspace.config()?.ignore, |paths| {
+                if paths.is_empty() {
+                    self.run(&settings)
+                } else {
+                    let mut settings = settings.clone();
+                    settings.all = false;
+                    settings.upstream = None;
+                    settings.paths = paths.to_vec();
+                    self.run(&settings)
+                }
+            });
+        }
+
+        self.run(&settings)
+    }
+
+    fn run(&self, settings: &Settings) -> Result<CommandSuccess, CommandError> {
+        let plan = Planner::new(ExecutionVerb::Fmt, settings)?.compute()?;
         let executor = Executor::new(&plan);
         let results = executor.install_and_invoke()?;
 
@@ -76,7 +115,7 @@ impl Fmt {
         }
 
         let mut formatter =
-            TextFormatter::new(&report, &plan.workspace, &settings, false, ApplyMode::None);
+            TextFormatter::new(&report, &plan.workspace, settings, false, ApplyMode::None);
         formatter.write_to(&mut std::io::stdout())?;
 
         if !self.no_error && report.has_errors() {
@@ -95,6 +134,16 @@ impl Fmt {
     }
 
     fn validate_options(&self) -> Result<(), CommandError> {
+        if self.range.is_some() && !self.paths.is_empty() {
+            let message = format!(
+                "the argument '{}' cannot be used with specified {}",
+                style("--range").yellow(),
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
         for path in &self.paths {
             if !path.exists() {
                 let message = format!("path '{}' does not exist", path.display());
@@ -131,9 +180,10 @@ impl Fmt {
         settings.progress = !self.no_progress;
         settings.filters = CheckFilter::from_optional_list(self.filter.clone());
         settings.upstream = self.upstream.clone();
+        settings.range = self.range.clone();
         settings.index = self.index;
         settings.index_file = self.index_file.clone();
-        settings.paths = self.paths.clone();
+        settings.paths = target_paths(&self.paths, self.files_from.as_deref())?;
         settings.trigger = self.trigger.into();
 
         Ok(settings)
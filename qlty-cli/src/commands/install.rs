@@ -1,6 +1,7 @@
 use crate::{Arguments, CommandError, CommandSuccess};
 use anyhow::{Context, Result};
 use clap::Args;
+use qlty_check::executor::Profiler;
 use qlty_check::planner::{plugin_supported_on_platform, Plan};
 use qlty_check::tool::tool_builder::ToolBuilder;
 use qlty_check::{Executor, Planner, Progress, Tool};
@@ -66,7 +67,7 @@ impl Install {
         let progress = Progress::new(!self.no_progress, tools.len() as u64);
         let jobs = Planner::jobs_count(self.jobs);
 
-        let results = Executor::install_tools(tools, jobs, progress);
+        let results = Executor::install_tools(tools, jobs, progress, &Profiler::new());
         for (name, result) in results {
             result.with_context(|| format!("Failed to install {}", name))?;
         }
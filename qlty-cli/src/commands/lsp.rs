@@ -0,0 +1,36 @@
+mod protocol;
+mod server;
+mod transport;
+
+use crate::{Arguments, CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::Args;
+use qlty_config::Workspace;
+use server::Server;
+
+#[derive(Args, Debug)]
+pub struct Lsp {
+    /// Communicate over stdio (the only supported transport)
+    #[arg(long)]
+    pub stdio: bool,
+}
+
+impl Lsp {
+    pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::require_initialized()?;
+        workspace.fetch_sources()?;
+
+        // Targets are matched relative to the current directory, which editors may
+        // set anywhere within the project
+        std::env::set_current_dir(&workspace.root)?;
+
+        let stdin = std::io::stdin();
+        let mut server = Server::new(workspace, std::io::stdout());
+
+        if server.run(&mut stdin.lock())? {
+            CommandSuccess::ok()
+        } else {
+            Err(CommandError::new("Language server exited before shutdown"))
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use diffy::Patch;
+use qlty_types::analysis::v1::{Issue, Level, Range as IssueRange, Suggestion};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::debug;
+
+const ERROR: u8 = 1;
+const WARNING: u8 = 2;
+const INFORMATION: u8 = 3;
+const HINT: u8 = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Range {
+    pub fn overlaps(&self, other: &Range) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub range: Range,
+    pub severity: u8,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_description: Option<CodeDescription>,
+    pub source: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeDescription {
+    pub href: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeAction {
+    pub title: String,
+    pub kind: &'static str,
+    pub diagnostics: Vec<Diagnostic>,
+    pub edit: WorkspaceEdit,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceEdit {
+    pub changes: HashMap<String, Vec<TextEdit>>,
+}
+
+pub fn diagnostic(issue: &Issue) -> Diagnostic {
+    let mut range = issue.range().map(convert_range).unwrap_or_default();
+
+    // Highlight the whole line when a tool only reports where the issue starts
+    if range.end <= range.start {
+        range.end = Position {
+            line: range.start.line + 1,
+            character: 0,
+        };
+    }
+
+    Diagnostic {
+        range,
+        severity: match issue.level() {
+            Level::High => ERROR,
+            Level::Medium => WARNING,
+            Level::Low | Level::Fmt | Level::Unspecified => INFORMATION,
+            Level::Note => HINT,
+        },
+        code: issue.rule_key.clone(),
+        code_description: if issue.documentation_url.is_empty() {
+            None
+        } else {
+            Some(CodeDescription {
+                href: issue.documentation_url.clone(),
+            })
+        },
+        source: format!("qlty:{}", issue.tool),
+        message: issue.message.clone(),
+    }
+}
+
+/// Converts a suggestion into edits against the current contents of a document.
+/// Linters provide replacements, while AI fixes only carry a unified diff.
+pub fn text_edits(suggestion: &Suggestion, path: &str, contents: &str) -> Vec<TextEdit> {
+    if !suggestion.replacements.is_empty() {
+        suggestion
+            .replacements
+            .iter()
+            .filter(|replacement| {
+                replacement
+                    .location
+                    .as_ref()
+                    .is_none_or(|location| location.path == path)
+            })
+            .filter_map(|replacement| {
+                Some(TextEdit {
+                    range: convert_range(replacement.location.as_ref()?.range?),
+                    new_text: replacement.data.clone(),
+                })
+            })
+            .collect()
+    } else if !suggestion.patch.is_empty() {
+        let patched = Patch::from_str(&suggestion.patch)
+            .map_err(|error| error.to_string())
+            .and_then(|patch| diffy::apply(contents, &patch).map_err(|error| error.to_string()));
+
+        match patched {
+            Ok(patched) => vec![full_document_edit(contents, patched)],
+            Err(error) => {
+                debug!("Unable to apply suggestion patch: {}", error);
+                vec![]
+            }
+        }
+    } else {
+        vec![]
+    }
+}
+
+pub fn full_document_edit(contents: &str, new_text: String) -> TextEdit {
+    let last_line = contents.split('\n').next_back().unwrap_or_default();
+
+    TextEdit {
+        range: Range {
+            start: Position::default(),
+            end: Position {
+                line: contents.matches('\n').count() as u32,
+                character: last_line.encode_utf16().count() as u32,
+            },
+        },
+        new_text,
+    }
+}
+
+/// Issue lines and columns are 1-based, while LSP positions are 0-based
+fn convert_range(range: IssueRange) -> Range {
+    if range.start_line == 0 {
+        return Range::default();
+    }
+
+    Range {
+        start: Position {
+            line: range.start_line - 1,
+            character: range.start_column.saturating_sub(1),
+        },
+        end: Position {
+            line: range.end_line.max(range.start_line) - 1,
+            character: range.end_column.saturating_sub(1),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Replacement};
+
+    fn location(start_line: u32, start_column: u32, end_line: u32, end_column: u32) -> Location {
+        Location {
+            path: "src/index.ts".to_string(),
+            range: Some(IssueRange {
+                start_line,
+                start_column,
+                end_line,
+                end_column,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let issue = Issue {
+            tool: "eslint".to_string(),
+            rule_key: "no-var".to_string(),
+            message: "Unexpected var".to_string(),
+            level: Level::Medium.into(),
+            documentation_url: "https://eslint.org/docs/rules/no-var".to_string(),
+            location: Some(location(3, 1, 3, 12)),
+            ..Default::default()
+        };
+
+        let diagnostic = diagnostic(&issue);
+        assert_eq!(diagnostic.range.start, position(2, 0));
+        assert_eq!(diagnostic.range.end, position(2, 11));
+        assert_eq!(diagnostic.severity, WARNING);
+        assert_eq!(diagnostic.source, "qlty:eslint");
+        assert_eq!(
+            serde_json::to_value(&diagnostic).unwrap()["codeDescription"]["href"],
+            "https://eslint.org/docs/rules/no-var"
+        );
+
+        let whole_line = super::diagnostic(&Issue {
+            location: Some(location(5, 0, 0, 0)),
+            ..issue.clone()
+        });
+        assert_eq!(whole_line.range.start, position(4, 0));
+        assert_eq!(whole_line.range.end, position(5, 0));
+
+        let whole_file = super::diagnostic(&Issue {
+            location: None,
+            ..issue
+        });
+        assert_eq!(whole_file.range.start, position(0, 0));
+        assert_eq!(whole_file.range.end, position(1, 0));
+    }
+
+    #[test]
+    fn test_text_edits_from_replacements() {
+        let suggestion = Suggestion {
+            replacements: vec![Replacement {
+                data: "let".to_string(),
+                location: Some(location(3, 1, 3, 4)),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            text_edits(&suggestion, "src/index.ts", ""),
+            vec![TextEdit {
+                range: Range {
+                    start: position(2, 0),
+                    end: position(2, 3),
+                },
+                new_text: "let".to_string(),
+            }]
+        );
+        assert!(text_edits(&suggestion, "src/other.ts", "").is_empty());
+    }
+
+    #[test]
+    fn test_text_edits_from_patch() {
+        let original = "const a = 1;\nvar b = 2;\nconst c = 3;";
+        let fixed = "const a = 1;\nlet b = 2;\nconst c = 3;";
+        let suggestion = Suggestion {
+            patch: diffy::create_patch(original, fixed).to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            text_edits(&suggestion, "src/index.ts", original),
+            vec![TextEdit {
+                range: Range {
+                    start: position(0, 0),
+                    end: position(2, 12),
+                },
+                new_text: fixed.to_string(),
+            }]
+        );
+        assert!(text_edits(&suggestion, "src/index.ts", "changed\n").is_empty());
+    }
+}
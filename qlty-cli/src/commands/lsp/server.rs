@@ -0,0 +1,462 @@
+use super::protocol::{self, CodeAction, Range, WorkspaceEdit};
+use super::transport::{read_message, write_message};
+use anyhow::{bail, Context, Result};
+use qlty_check::{planner::Planner, Executor, Processor, Report, Settings};
+use qlty_config::version::QLTY_VERSION;
+use qlty_config::Workspace;
+use qlty_types::analysis::v1::{ExecutionVerb, Issue};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+use url::Url;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Serves diagnostics, fixes and formatting for documents within a workspace.
+/// Requests are handled one at a time, so analysis blocks until it completes.
+pub struct Server<W: Write> {
+    workspace: Workspace,
+    writer: W,
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+#[derive(Debug)]
+struct Document {
+    path: PathBuf,
+    text: String,
+    issues: Vec<Issue>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentParams {
+    text_document: TextDocumentItem,
+    #[serde(default)]
+    content_changes: Vec<ContentChange>,
+    range: Option<Range>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextDocumentItem {
+    uri: String,
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentChange {
+    text: String,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(workspace: Workspace, writer: W) -> Self {
+        Self {
+            workspace,
+            writer,
+            documents: HashMap::new(),
+            shutdown: false,
+        }
+    }
+
+    /// Handles messages until the client exits, returning whether it shut down cleanly
+    pub fn run(&mut self, reader: &mut impl BufRead) -> Result<bool> {
+        while let Some(message) = read_message(reader)? {
+            let method = message["method"].as_str().unwrap_or_default().to_string();
+
+            if method == "exit" {
+                break;
+            }
+
+            match message.get("id") {
+                Some(id) => self.handle_request(id.clone(), &method, message["params"].clone())?,
+                None => {
+                    if let Err(error) = self.handle_notification(&method, message["params"].clone())
+                    {
+                        warn!("Failed to handle {}: {:?}", method, error);
+                        self.log_message(&format!("{}: {}", method, error))?;
+                    }
+                }
+            }
+        }
+
+        Ok(self.shutdown)
+    }
+
+    fn handle_request(&mut self, id: Value, method: &str, params: Value) -> Result<()> {
+        debug!("Request {}: {}", id, method);
+
+        let result = match method {
+            "initialize" => Ok(Self::capabilities()),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/codeAction" => match serde_json::from_value(params) {
+                Ok(params) => self.code_actions(params),
+                Err(error) => return self.send_invalid_params(id, method, error),
+            },
+            "textDocument/formatting" => match serde_json::from_value(params) {
+                Ok(params) => self.formatting(params),
+                Err(error) => return self.send_invalid_params(id, method, error),
+            },
+            _ => {
+                return self.send_error(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Unknown method: {}", method),
+                );
+            }
+        };
+
+        match result {
+            Ok(result) => self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err(error) => {
+                warn!("Failed to handle {}: {:?}", method, error);
+                self.send_error(id, INTERNAL_ERROR, &error.to_string())
+            }
+        }
+    }
+
+    fn send_invalid_params(
+        &mut self,
+        id: Value,
+        method: &str,
+        error: serde_json::Error,
+    ) -> Result<()> {
+        warn!("Invalid params for {}: {}", method, error);
+        self.send_error(
+            id,
+            INVALID_PARAMS,
+            &format!("Invalid params for {}: {}", method, error),
+        )
+    }
+
+    fn send_error(&mut self, id: Value, code: i64, message: &str) -> Result<()> {
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }))
+    }
+
+    fn handle_notification(&mut self, method: &str, params: Value) -> Result<()> {
+        debug!("Notification: {}", method);
+
+        match method {
+            "textDocument/didOpen" => {
+                let params: TextDocumentParams = serde_json::from_value(params)?;
+                let uri = params.text_document.uri;
+
+                self.documents.insert(
+                    uri.clone(),
+                    Document {
+                        path: uri_to_path(&uri)?,
+                        text: params.text_document.text.unwrap_or_default(),
+                        issues: vec![],
+                    },
+                );
+
+                self.check(&uri)
+            }
+            "textDocument/didChange" => {
+                let params: TextDocumentParams = serde_json::from_value(params)?;
+
+                if let (Some(document), Some(change)) = (
+                    self.documents.get_mut(&params.text_document.uri),
+                    params.content_changes.into_iter().next_back(),
+                ) {
+                    document.text = change.text;
+                }
+
+                Ok(())
+            }
+            "textDocument/didSave" => {
+                let params: TextDocumentParams = serde_json::from_value(params)?;
+                self.check(&params.text_document.uri)
+            }
+            "textDocument/didClose" => {
+                let params: TextDocumentParams = serde_json::from_value(params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.publish_diagnostics(&params.text_document.uri, &[])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn capabilities() -> Value {
+        json!({
+            "capabilities": {
+                "textDocumentSync": {
+                    "openClose": true,
+                    "change": 1,
+                    "save": { "includeText": false },
+                },
+                "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                "documentFormattingProvider": true,
+            },
+            "serverInfo": { "name": "qlty", "version": QLTY_VERSION },
+        })
+    }
+
+    fn check(&mut self, uri: &str) -> Result<()> {
+        let Some(path) = self
+            .documents
+            .get(uri)
+            .map(|document| document.path.clone())
+        else {
+            return Ok(());
+        };
+
+        if !path.starts_with(&self.workspace.root) || !path.is_file() {
+            debug!("Skipping {} outside of workspace", path.display());
+            return Ok(());
+        }
+
+        let (report, _) = self.analyze(&path, true)?;
+        self.update_issues(uri, report.issues)
+    }
+
+    fn code_actions(&mut self, params: TextDocumentParams) -> Result<Value> {
+        let uri = params.text_document.uri;
+        let range = params.range.unwrap_or_default();
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(json!([]));
+        };
+
+        let actions = document
+            .issues
+            .iter()
+            .filter_map(|issue| {
+                let diagnostic = protocol::diagnostic(issue);
+
+                if !diagnostic.range.overlaps(&range) {
+                    return None;
+                }
+
+                let suggestion = issue.suggestions.first()?;
+                let edits = protocol::text_edits(suggestion, &issue.path()?, &document.text);
+
+                if edits.is_empty() {
+                    return None;
+                }
+
+                Some(CodeAction {
+                    title: if suggestion.description.is_empty() {
+                        format!("Fix {}:{}", issue.tool, issue.rule_key)
+                    } else {
+                        suggestion.description.clone()
+                    },
+                    kind: "quickfix",
+                    diagnostics: vec![diagnostic],
+                    edit: WorkspaceEdit {
+                        changes: HashMap::from([(uri.clone(), edits)]),
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(serde_json::to_value(actions)?)
+    }
+
+    /// Formatters run against a staged copy of the file, so the formatted contents
+    /// are read back from the staging area rather than written to the workspace
+    fn formatting(&mut self, params: TextDocumentParams) -> Result<Value> {
+        let uri = params.text_document.uri;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(Value::Null);
+        };
+
+        let path = document.path.clone();
+        let text = document.text.clone();
+
+        if std::fs::read_to_string(&path).ok().as_ref() != Some(&text) {
+            info!("Not formatting {} with unsaved changes", path.display());
+            return Ok(Value::Null);
+        }
+
+        let (report, staged_path) = self.analyze(&path, false)?;
+        self.update_issues(&uri, report.issues)?;
+
+        let formatted = std::fs::read_to_string(&staged_path).unwrap_or_else(|_| text.clone());
+
+        if formatted == text {
+            Ok(json!([]))
+        } else {
+            Ok(serde_json::to_value(vec![protocol::full_document_edit(
+                &text, formatted,
+            )])?)
+        }
+    }
+
+    /// Runs a check on a single file, returning the report and the path of the
+    /// file in the staging area after any formatters have run
+    fn analyze(&self, path: &Path, cache: bool) -> Result<(Report, PathBuf)> {
+        let settings = Settings {
+            root: self.workspace.root.clone(),
+            paths: vec![path.to_path_buf()],
+            progress: false,
+            cache,
+            fail_level: None,
+            ..Default::default()
+        };
+
+        let plan = Planner::new(ExecutionVerb::Check, &settings)?.compute()?;
+        let results = Executor::new(&plan).install_and_invoke()?;
+        let report = Processor::new(&plan, results).compute()?;
+
+        let staged_path = plan
+            .staging_area
+            .destination_directory
+            .join(path.strip_prefix(&self.workspace.root)?);
+
+        Ok((report, staged_path))
+    }
+
+    fn update_issues(&mut self, uri: &str, issues: Vec<Issue>) -> Result<()> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+
+        let relative_path = document.path.strip_prefix(&self.workspace.root)?;
+
+        document.issues = issues
+            .into_iter()
+            .filter(|issue| {
+                issue
+                    .path()
+                    .is_some_and(|path| Path::new(&path) == relative_path)
+            })
+            .collect();
+
+        let issues = document.issues.clone();
+        self.publish_diagnostics(uri, &issues)
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, issues: &[Issue]) -> Result<()> {
+        let diagnostics = issues.iter().map(protocol::diagnostic).collect::<Vec<_>>();
+
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }))
+    }
+
+    fn log_message(&mut self, message: &str) -> Result<()> {
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "method": "window/logMessage",
+            "params": { "type": 1, "message": message },
+        }))
+    }
+
+    fn send(&mut self, message: Value) -> Result<()> {
+        write_message(&mut self.writer, &message)
+    }
+}
+
+fn uri_to_path(uri: &str) -> Result<PathBuf> {
+    let url = Url::parse(uri).with_context(|| format!("Invalid document URI: {}", uri))?;
+
+    match url.to_file_path() {
+        Ok(path) => Ok(path.canonicalize().unwrap_or(path)),
+        Err(_) => bail!("Unsupported document URI: {}", uri),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+    use tempfile::tempdir;
+
+    fn run(messages: &[Value]) -> (bool, Vec<Value>) {
+        let dir = tempdir().unwrap();
+        let workspace = Workspace::for_root(dir.path()).unwrap();
+
+        let mut input = vec![];
+        for message in messages {
+            write_message(&mut input, message).unwrap();
+        }
+
+        let mut output = vec![];
+        let shutdown = Server::new(workspace, &mut output)
+            .run(&mut Cursor::new(input))
+            .unwrap();
+
+        let mut reader = Cursor::new(output);
+        let mut responses = vec![];
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            responses.push(message);
+        }
+
+        (shutdown, responses)
+    }
+
+    #[test]
+    fn test_initialize_and_shutdown() {
+        let (shutdown, responses) = run(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+
+        assert!(shutdown);
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(
+            responses[0]["result"]["capabilities"]["documentFormattingProvider"],
+            true
+        );
+        assert_eq!(responses[0]["result"]["serverInfo"]["name"], "qlty");
+        assert_eq!(responses[1]["id"], 2);
+        assert!(responses[1]["result"].is_null());
+    }
+
+    #[test]
+    fn test_exit_without_shutdown() {
+        let (shutdown, responses) = run(&[json!({ "jsonrpc": "2.0", "method": "exit" })]);
+
+        assert!(!shutdown);
+        assert!(responses.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        let (_, responses) = run(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "textDocument/hover", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+        ]);
+
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(responses[1]["id"], 2);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let (shutdown, responses) = run(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "textDocument/codeAction", "params": { "range": 1 } }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/formatting" }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+
+        // The server keeps going after rejecting the requests
+        assert!(shutdown);
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(responses[2]["id"], 3);
+    }
+}
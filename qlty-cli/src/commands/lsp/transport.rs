@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// Reads a JSON-RPC message framed by a `Content-Length` header,
+/// returning `None` once the client closes the stream
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut content_length = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            if content_length.is_some() {
+                break;
+            } else {
+                continue;
+            }
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .with_context(|| format!("Invalid Content-Length header: {}", line))?,
+                );
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap()];
+    reader.read_exact(&mut body)?;
+
+    Ok(Some(
+        serde_json::from_slice(&body).context("Failed to parse JSON-RPC message")?,
+    ))
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_round_trip() {
+        let mut buffer = vec![];
+        write_message(
+            &mut buffer,
+            &json!({ "jsonrpc": "2.0", "method": "initialized" }),
+        )
+        .unwrap();
+        write_message(
+            &mut buffer,
+            &json!({ "jsonrpc": "2.0", "id": 1, "result": "ü" }),
+        )
+        .unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(
+            read_message(&mut reader).unwrap().unwrap()["method"],
+            "initialized"
+        );
+        assert_eq!(read_message(&mut reader).unwrap().unwrap()["result"], "ü");
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_with_content_type() {
+        let body = r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#;
+        let input = format!(
+            "content-length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}",
+            body.len(),
+            body
+        );
+
+        let message = read_message(&mut Cursor::new(input)).unwrap().unwrap();
+        assert_eq!(message["id"], 2);
+    }
+}
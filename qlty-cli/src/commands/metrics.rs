@@ -1,3 +1,5 @@
+use crate::files_from::target_paths;
+use crate::format::{HtmlFormatter, OutputFormat};
 use crate::ui::Steps;
 use crate::{Arguments, CommandError, CommandSuccess};
 use anyhow::Result;
@@ -8,7 +10,7 @@ use cli_table::{
 };
 use console::style;
 use console::Emoji;
-use qlty_analysis::git::compute_upstream;
+use qlty_analysis::git::{compute_upstream, Worktree};
 use qlty_analysis::workspace_entries::{TargetMode, WorkspaceEntryFinderBuilder};
 use qlty_analysis::Report;
 use qlty_config::Workspace;
@@ -57,14 +59,26 @@ pub struct Metrics {
     #[arg(long)]
     pub upstream: Option<String>,
 
+    /// Analyze files changed between two commits, such as `main..feature`
+    #[arg(long, value_name = "RANGE", conflicts_with_all = ["all", "upstream"])]
+    pub range: Option<String>,
+
+    /// Read the files to analyze from a file, or from stdin with `-`
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["all", "upstream", "range"])]
+    pub files_from: Option<PathBuf>,
+
     /// Only show results
     #[arg(long)]
     pub quiet: bool,
 
     /// JSON output
-    #[arg(long, hide = true)]
+    #[arg(long, hide = true, conflicts_with = "format")]
     json: bool,
 
+    /// Output format
+    #[arg(long, value_parser = OutputFormat::parser(&["text", "json", "html"]))]
+    format: Option<OutputFormat>,
+
     /// Files to analyze
     pub paths: Vec<PathBuf>,
 }
@@ -92,10 +106,33 @@ impl Metrics {
         steps.start(THINKING, "Planning... ");
 
         let config = workspace.config()?;
-        let target_mode = self.compute_target_mode(&workspace);
+        let paths = target_paths(&self.paths, self.files_from.as_deref())?;
+
+        if self.files_from.is_some() && paths.is_empty() {
+            eprintln!("{}", style("No files to analyze").dim());
+            return CommandSuccess::ok();
+        }
+
+        let target_mode = self.compute_target_mode(&workspace, &paths);
+
+        // A commit range is analyzed from a checkout of its end, leaving the working tree alone
+        let current_dir = std::env::current_dir()?;
+        let range_worktree = match &target_mode {
+            TargetMode::Range(range) => {
+                let worktree = Worktree::checkout_range(&workspace.root, range)?;
+                std::env::set_current_dir(worktree.path())?;
+                Some(worktree)
+            }
+            _ => None,
+        };
+        let source_root = match &range_worktree {
+            Some(worktree) => worktree.path().to_path_buf(),
+            None => workspace.root.clone(),
+        };
+
         let mut workspace_entry_finder_builder = WorkspaceEntryFinderBuilder {
             mode: target_mode.clone(),
-            paths: self.paths.clone(),
+            paths: paths.clone(),
             config: config.clone(),
             ..Default::default()
         };
@@ -125,19 +162,22 @@ impl Metrics {
 
         let mut processor = Processor::new(results);
         let report = processor.compute()?;
+        std::env::set_current_dir(current_dir)?;
 
         steps.start(SPARKLES, "Reporting... ");
-        self.print(plan.mode, &report)?;
+        self.print(&source_root, plan.mode, &report)?;
         self.print_target_suggestion_if_necessary(&plan, &target_mode);
 
         CommandSuccess::ok()
     }
 
-    fn compute_target_mode(&self, workspace: &Workspace) -> TargetMode {
+    fn compute_target_mode(&self, workspace: &Workspace, paths: &[PathBuf]) -> TargetMode {
         if self.all {
             TargetMode::All
-        } else if !self.paths.is_empty() {
-            TargetMode::Paths(self.paths.len())
+        } else if !paths.is_empty() {
+            TargetMode::Paths(paths.len())
+        } else if let Some(range) = &self.range {
+            TargetMode::Range(range.clone())
         } else if let Some(upstream) = compute_upstream(workspace, &self.upstream) {
             TargetMode::UpstreamDiff(upstream)
         } else {
@@ -170,6 +210,16 @@ impl Metrics {
             return Err(CommandError::InvalidOptions { message });
         }
 
+        if self.range.is_some() && !self.paths.is_empty() {
+            let message = format!(
+                "the argument '{}' cannot be used with specified {}",
+                style("--range").yellow(),
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
         Ok(())
     }
 
@@ -183,6 +233,7 @@ impl Metrics {
         let target_name = match target_mode {
             TargetMode::UpstreamDiff(upstream) => upstream.to_owned(),
             TargetMode::HeadDiff => String::from("HEAD"),
+            TargetMode::Range(range) => range.to_owned(),
             _ => return,
         };
 
@@ -194,11 +245,32 @@ impl Metrics {
         );
     }
 
-    fn print(&self, mode: MetricsMode, report: &Report) -> Result<()> {
-        if self.json {
-            self.print_json(report)
+    fn print(&self, root: &Path, mode: MetricsMode, report: &Report) -> Result<()> {
+        let format = if self.json {
+            OutputFormat::Json
         } else {
-            self.print_text(mode, report)
+            self.format.unwrap_or_default()
+        };
+
+        match format {
+            OutputFormat::Json => self.print_json(report),
+            OutputFormat::Html => {
+                let stats = match mode {
+                    MetricsMode::Files if self.dirs => report.directory_stats(),
+                    MetricsMode::Files => report.file_stats(),
+                    MetricsMode::Functions => report
+                        .function_stats_by_path()
+                        .into_values()
+                        .flatten()
+                        .collect(),
+                };
+
+                HtmlFormatter::new(root, "qlty metrics")
+                    .with_stats(stats)
+                    .boxed()
+                    .write_to(&mut std::io::stdout())
+            }
+            _ => self.print_text(mode, report),
         }
     }
 
@@ -1,3 +1,5 @@
+use crate::files_from::target_paths;
+use crate::format::{GitLabFormatter, HtmlFormatter, OutputFormat};
 use crate::ui::Highlighter;
 use crate::ui::Steps;
 use crate::{Arguments, CommandError, CommandSuccess};
@@ -6,13 +8,16 @@ use clap::Args;
 use console::{style, Emoji};
 use itertools::Itertools;
 use qlty_analysis::code::File;
-use qlty_analysis::git::compute_upstream;
+use qlty_analysis::git::{compute_upstream, Worktree};
 use qlty_analysis::workspace_entries::TargetMode;
 use qlty_analysis::workspace_entries::WorkspaceEntryFinderBuilder;
 use qlty_analysis::Report;
 use qlty_config::{QltyConfig, Workspace};
 use qlty_types::analysis::v1::Issue;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 static EYES: Emoji<'_, '_> = Emoji("👀  ", "");
 static LOOKING_GLASS: Emoji<'_, '_> = Emoji("🔍  ", "");
@@ -41,14 +46,30 @@ pub struct Smells {
     #[arg(long)]
     pub upstream: Option<String>,
 
+    /// Analyze files changed between two commits, such as `main..feature`
+    #[arg(long, value_name = "RANGE", conflicts_with_all = ["all", "upstream"])]
+    pub range: Option<String>,
+
+    /// Read the files to analyze from a file, or from stdin with `-`
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["all", "upstream", "range"])]
+    pub files_from: Option<PathBuf>,
+
     /// Only show results
     #[arg(long)]
     pub quiet: bool,
 
     /// JSON output
-    #[arg(long, hide = true)]
+    #[arg(long, hide = true, conflicts_with_all = ["gitlab", "format"])]
     json: bool,
 
+    /// [DEPRECATED, use --format=gitlab] GitLab Code Quality output
+    #[arg(long, hide = true, conflicts_with_all = ["json", "format"])]
+    gitlab: bool,
+
+    /// Output format
+    #[arg(long, value_parser = OutputFormat::parser(&["text", "json", "gitlab", "html"]))]
+    format: Option<OutputFormat>,
+
     /// Files to analyze
     pub paths: Vec<PathBuf>,
 }
@@ -57,25 +78,49 @@ impl Smells {
     pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
         self.run_assertions()?;
 
+        if self.gitlab {
+            eprintln!("WARNING: --gitlab is deprecated, use --format=gitlab instead\n");
+        }
+
         let workspace = Workspace::new()?;
         workspace.fetch_sources()?;
 
         let config = workspace.config()?;
+        let paths = target_paths(&self.paths, self.files_from.as_deref())?;
+
+        if self.files_from.is_some() && paths.is_empty() {
+            eprintln!("{}", style("No files to analyze").dim());
+            return CommandSuccess::ok();
+        }
 
         let mut steps_count = 2;
 
         if !self.no_duplication {
             steps_count += 1;
         }
-        let target_mode = self.compute_target_mode(&workspace);
+        let target_mode = self.compute_target_mode(&workspace, &paths);
 
         let mut steps = Steps::new(self.quiet, steps_count);
         steps.start(LOOKING_GLASS, self.analyzing_message(&target_mode));
 
-        let target_mode = self.compute_target_mode(&workspace);
+        // A commit range is analyzed from a checkout of its end, leaving the working tree alone
+        let current_dir = std::env::current_dir()?;
+        let range_worktree = match &target_mode {
+            TargetMode::Range(range) => {
+                let worktree = Worktree::checkout_range(&workspace.root, range)?;
+                std::env::set_current_dir(worktree.path())?;
+                Some(worktree)
+            }
+            _ => None,
+        };
+        let source_root = match &range_worktree {
+            Some(worktree) => worktree.path().to_path_buf(),
+            None => workspace.root.clone(),
+        };
+
         let mut workspace_entry_finder_builder = WorkspaceEntryFinderBuilder {
             mode: target_mode.clone(),
-            paths: self.paths.clone(),
+            paths: paths.clone(),
             config: config.clone(),
             exclude_tests: !self.include_tests,
             ..Default::default()
@@ -94,23 +139,26 @@ impl Smells {
                 format!("Looking for duplication across {} files... ", files.len()),
             );
 
-            report.merge(&self.run_duplication(&target_mode, &config, &files)?);
+            report.merge(&self.run_duplication(&target_mode, &paths, &config, &files)?);
         }
 
-        report.relativeize_paths(&workspace.root);
+        report.relativeize_paths(&source_root);
+        std::env::set_current_dir(current_dir)?;
 
         steps.start(SPARKLES, "Reporting... ");
         println!();
-        self.write_stdout(&workspace, &report.issues)?;
+        self.write_stdout(&workspace, &source_root, &report.issues)?;
 
         CommandSuccess::ok()
     }
 
-    fn compute_target_mode(&self, workspace: &Workspace) -> TargetMode {
+    fn compute_target_mode(&self, workspace: &Workspace, paths: &[PathBuf]) -> TargetMode {
         if self.all {
             TargetMode::All
-        } else if !self.paths.is_empty() {
-            TargetMode::Paths(self.paths.len())
+        } else if !paths.is_empty() {
+            TargetMode::Paths(paths.len())
+        } else if let Some(range) = &self.range {
+            TargetMode::Range(range.clone())
         } else if let Some(upstream) = compute_upstream(workspace, &self.upstream) {
             TargetMode::UpstreamDiff(upstream)
         } else {
@@ -143,6 +191,16 @@ impl Smells {
             return Err(CommandError::InvalidOptions { message });
         }
 
+        if self.range.is_some() && !self.paths.is_empty() {
+            let message = format!(
+                "the argument '{}' cannot be used with specified {}",
+                style("--range").yellow(),
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
         Ok(())
     }
 
@@ -159,6 +217,7 @@ impl Smells {
     fn run_duplication(
         &self,
         target_mode: &TargetMode,
+        paths: &[PathBuf],
         config: &QltyConfig,
         files: &[Arc<File>],
     ) -> Result<Report> {
@@ -167,15 +226,15 @@ impl Smells {
             return Ok(Report::default());
         }
 
-        let paths = if self.paths.is_empty() {
+        let paths = if paths.is_empty() {
             match target_mode {
-                TargetMode::HeadDiff | TargetMode::UpstreamDiff(_) => {
+                TargetMode::HeadDiff | TargetMode::UpstreamDiff(_) | TargetMode::Range(_) => {
                     files.iter().map(|file| file.path.clone()).collect()
                 }
                 _ => vec![],
             }
         } else {
-            self.paths.clone()
+            paths.to_vec()
         };
 
         let settings = qlty_smells::duplication::Settings {
@@ -204,11 +263,25 @@ impl Smells {
         Ok(executor.report())
     }
 
-    fn write_stdout(&self, workspace: &Workspace, issues: &[Issue]) -> Result<()> {
-        if self.json {
-            self.write_stdout_json(issues)
+    fn write_stdout(&self, workspace: &Workspace, root: &Path, issues: &[Issue]) -> Result<()> {
+        let format = if self.json {
+            OutputFormat::Json
+        } else if self.gitlab {
+            OutputFormat::Gitlab
         } else {
-            self.write_stdout_text(workspace, issues)
+            self.format.unwrap_or_default()
+        };
+
+        match format {
+            OutputFormat::Json => self.write_stdout_json(issues),
+            OutputFormat::Gitlab => {
+                GitLabFormatter::boxed(issues.to_vec()).write_to(&mut std::io::stdout())
+            }
+            OutputFormat::Html => HtmlFormatter::new(root, "qlty smells")
+                .with_issues(issues.to_vec())
+                .boxed()
+                .write_to(&mut std::io::stdout()),
+            _ => self.write_stdout_text(workspace, root, issues),
         }
     }
 
@@ -250,6 +323,7 @@ impl Smells {
             }
             TargetMode::UpstreamDiff(ref upstream) => format!(" vs. {}", upstream),
             TargetMode::HeadDiff => " vs. HEAD".to_string(),
+            TargetMode::Range(ref range) => format!(" {}", range),
             TargetMode::Index => " index".to_string(),
             TargetMode::IndexFile(ref file) => format!(" index file {}", file.display()),
         };
@@ -257,7 +331,13 @@ impl Smells {
         format!("Analyzing{}...", suffix)
     }
 
-    fn write_stdout_text(&self, workspace: &Workspace, issues: &[Issue]) -> Result<()> {
+    /// Paths are shown relative to the current directory, and sources are read from `root`
+    fn write_stdout_text(
+        &self,
+        workspace: &Workspace,
+        root: &Path,
+        issues: &[Issue],
+    ) -> Result<()> {
         let cwd = std::env::current_dir().expect("Unable to identify current directory");
         let issues_by_path = issues
             .iter()
@@ -292,7 +372,7 @@ impl Smells {
                     println!();
 
                     if highlighter.as_ref().is_none() {
-                        highlighter = Some(Highlighter::new(&path_buf)?);
+                        highlighter = Some(Highlighter::new(&root.join(path.as_ref().unwrap()))?);
                     }
 
                     highlighter
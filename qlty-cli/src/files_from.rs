@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Combines paths given as arguments with any read from `--files-from`
+pub fn target_paths(paths: &[PathBuf], files_from: Option<&Path>) -> Result<Vec<PathBuf>> {
+    let mut paths = paths.to_vec();
+
+    if let Some(files_from) = files_from {
+        paths.extend(read_files_from(files_from)?);
+    }
+
+    Ok(paths)
+}
+
+/// Reads a list of paths from a file, or from stdin when the path is `-`.
+/// Entries are NUL-separated when the input contains a NUL byte (as produced by
+/// `git diff -z` or `find -print0`) and newline-separated otherwise.
+/// Entries which no longer exist, such as deleted files, are skipped.
+fn read_files_from(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut contents = String::new();
+
+    if path == Path::new("-") {
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .context("Failed to read paths from stdin")?;
+    } else {
+        contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read paths from {}", path.display()))?;
+    }
+
+    Ok(parse_paths(&contents))
+}
+
+fn parse_paths(contents: &str) -> Vec<PathBuf> {
+    let separator = if contents.contains('\0') { '\0' } else { '\n' };
+
+    contents
+        .split(separator)
+        .map(|entry| entry.trim_end_matches('\r'))
+        .filter(|entry| !entry.trim().is_empty())
+        .map(PathBuf::from)
+        .filter(|path| {
+            let exists = path.exists();
+
+            if !exists {
+                debug!("Skipping nonexistent path: {}", path.display());
+            }
+
+            exists
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_paths() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.rb");
+        let b = dir.path().join("b c.rb");
+        fs::write(&a, "").unwrap();
+        fs::write(&b, "").unwrap();
+        let missing = dir.path().join("missing.rb");
+
+        let lines = format!(
+            "{}\r\n\n{}\n{}\n",
+            a.display(),
+            b.display(),
+            missing.display()
+        );
+        assert_eq!(parse_paths(&lines), vec![a.clone(), b.clone()]);
+
+        let nul_separated = format!("{}\0{}\0", b.display(), a.display());
+        assert_eq!(parse_paths(&nul_separated), vec![b, a]);
+
+        assert!(parse_paths("").is_empty());
+    }
+}
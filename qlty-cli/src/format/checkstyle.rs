@@ -0,0 +1,129 @@
+use super::{escape_xml, fingerprint_attribute};
+use anyhow::Result;
+use qlty_check::Report;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::Level;
+use std::io::Write;
+
+/// Writes a Checkstyle XML report with one file element per path
+#[derive(Debug)]
+pub struct CheckstyleFormatter {
+    report: Report,
+}
+
+impl CheckstyleFormatter {
+    pub fn new(report: Report) -> Self {
+        Self { report }
+    }
+
+    pub fn boxed(report: Report) -> Box<dyn Formatter> {
+        Box::new(Self::new(report))
+    }
+
+    fn convert_level(level: Level) -> &'static str {
+        match level {
+            Level::Unspecified => "info",
+            Level::Note => "info",
+            Level::Fmt => "info",
+            Level::Low => "info",
+            Level::Medium => "warning",
+            Level::High => "error",
+        }
+    }
+}
+
+impl Formatter for CheckstyleFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, r#"<checkstyle version="4.3">"#)?;
+
+        let mut issues_by_path = self
+            .report
+            .issues_by_path()
+            .into_iter()
+            .filter_map(|(path, issues)| path.map(|path| (path, issues)))
+            .collect::<Vec<_>>();
+        issues_by_path.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (path, issues) in issues_by_path {
+            writeln!(
+                writer,
+                r#"  <file name="{}">"#,
+                escape_xml(&path.to_string_lossy())
+            )?;
+
+            for issue in issues {
+                let range = issue.range().unwrap_or_default();
+                let level = Level::try_from(issue.level).unwrap_or(Level::Medium);
+
+                writeln!(
+                    writer,
+                    r#"    <error line="{}" column="{}" severity="{}" message="{}" source="{}"{}/>"#,
+                    range.start_line,
+                    range.start_column,
+                    Self::convert_level(level),
+                    escape_xml(&issue.message),
+                    escape_xml(&format!("{}:{}", issue.tool, issue.rule_key)),
+                    fingerprint_attribute(&issue)
+                )?;
+            }
+
+            writeln!(writer, "  </file>")?;
+        }
+
+        writeln!(writer, "</checkstyle>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_analysis::{workspace_entries::TargetMode, IssueCount};
+    use qlty_types::analysis::v1::{ExecutionVerb, Issue, Location, Range};
+    use std::collections::HashSet;
+
+    fn issue(tool: &str, rule_key: &str, path: &str, line: u32, level: Level) -> Issue {
+        Issue {
+            tool: tool.to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of \"{}\"", rule_key),
+            level: level.into(),
+            fingerprint: format!("{}-{}-{}", tool, rule_key, line),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    start_column: 5,
+                    end_line: line,
+                    end_column: 10,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_checkstyle_formatter() {
+        let report = Report {
+            issues: vec![
+                issue("eslint", "no-unused-vars", "src/b.js", 9, Level::Medium),
+                issue("eslint", "eqeqeq", "src/a.js", 7, Level::High),
+                issue("eslint", "no-console", "src/a.js", 3, Level::Low),
+            ],
+            verb: ExecutionVerb::Check,
+            target_mode: TargetMode::default(),
+            messages: vec![],
+            invocations: vec![],
+            formatted: vec![],
+            fixed: HashSet::new(),
+            fixable: HashSet::new(),
+            counts: IssueCount::default(),
+        };
+
+        let formatter = CheckstyleFormatter::boxed(report);
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+        insta::assert_snapshot!(output);
+    }
+}
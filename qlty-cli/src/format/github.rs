@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Issue, Level};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+const STEP_SUMMARY_ENV: &str = "GITHUB_STEP_SUMMARY";
+
+/// Writes issues as GitHub Actions workflow commands, which GitHub renders as
+/// inline annotations on pull requests
+#[derive(Debug)]
+pub struct GitHubFormatter {
+    issues: Vec<Issue>,
+}
+
+impl GitHubFormatter {
+    pub fn new(issues: Vec<Issue>) -> Self {
+        Self { issues }
+    }
+
+    pub fn boxed(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self::new(issues))
+    }
+
+    /// Appends a Markdown summary of the issues to the job summary file, if there is one
+    pub fn write_step_summary(&self) -> Result<()> {
+        if let Ok(path) = std::env::var(STEP_SUMMARY_ENV) {
+            if !path.is_empty() {
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .with_context(|| format!("Failed to open {}: {}", STEP_SUMMARY_ENV, path))?;
+
+                file.write_all(self.step_summary().as_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn step_summary(&self) -> String {
+        let mut summary = String::from("## Qlty Check\n\n");
+
+        if self.issues.is_empty() {
+            summary.push_str("No issues found.\n\n");
+            return summary;
+        }
+
+        let mut counts: BTreeMap<&str, [usize; 5]> = BTreeMap::new();
+
+        for issue in &self.issues {
+            let index = match Level::try_from(issue.level).unwrap_or(Level::Medium) {
+                Level::High => 0,
+                Level::Medium => 1,
+                Level::Low => 2,
+                Level::Fmt => 3,
+                Level::Note | Level::Unspecified => 4,
+            };
+
+            counts.entry(issue.tool.as_str()).or_default()[index] += 1;
+        }
+
+        summary.push_str("| Plugin | High | Medium | Low | Fmt | Note | Total |\n");
+        summary.push_str("| --- | ---: | ---: | ---: | ---: | ---: | ---: |\n");
+
+        let mut totals = [0; 5];
+
+        for (plugin, levels) in &counts {
+            for (total, count) in totals.iter_mut().zip(levels) {
+                *total += count;
+            }
+
+            summary.push_str(&Self::summary_row(plugin, levels));
+        }
+
+        summary.push_str(&Self::summary_row("**Total**", &totals));
+        summary.push('\n');
+        summary
+    }
+
+    fn summary_row(name: &str, levels: &[usize; 5]) -> String {
+        format!(
+            "| {} | {} | {} |\n",
+            name,
+            levels.iter().join(" | "),
+            levels.iter().sum::<usize>()
+        )
+    }
+
+    fn annotation(issue: &Issue) -> String {
+        let command = match Level::try_from(issue.level).unwrap_or(Level::Medium) {
+            Level::High => "error",
+            Level::Medium => "warning",
+            _ => "notice",
+        };
+
+        let mut properties = vec![];
+
+        // Issues without a path are shown on the workflow run rather than on a file
+        if let Some(path) = issue.path() {
+            properties.push(format!("file={}", escape_property(&path)));
+        }
+
+        if let Some(range) = issue.range() {
+            if range.start_line > 0 {
+                properties.push(format!("line={}", range.start_line));
+                properties.push(format!("endLine={}", range.end_line.max(range.start_line)));
+
+                // Columns are only meaningful to GitHub for single line annotations
+                if range.start_column > 0 && range.end_line <= range.start_line {
+                    properties.push(format!("col={}", range.start_column));
+
+                    if range.end_column > range.start_column {
+                        properties.push(format!("endColumn={}", range.end_column));
+                    }
+                }
+            }
+        }
+
+        properties.push(format!(
+            "title={}",
+            escape_property(&format!("{}:{}", issue.tool, issue.rule_key))
+        ));
+
+        format!(
+            "::{} {}::{}",
+            command,
+            properties.join(","),
+            escape_data(&issue.message)
+        )
+    }
+}
+
+impl Formatter for GitHubFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        for issue in self.issues.iter().sorted() {
+            writeln!(writer, "{}", Self::annotation(issue))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn issue(tool: &str, rule_key: &str, level: Level, range: Range) -> Issue {
+        Issue {
+            tool: tool.to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of {}\n100% sure", rule_key),
+            level: level.into(),
+            location: Some(Location {
+                path: "src/main.rs".to_string(),
+                range: Some(range),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn issues() -> Vec<Issue> {
+        vec![
+            issue(
+                "clippy",
+                "needless_return",
+                Level::Medium,
+                Range {
+                    start_line: 3,
+                    start_column: 5,
+                    end_line: 3,
+                    end_column: 12,
+                    ..Default::default()
+                },
+            ),
+            issue(
+                "clippy",
+                "unused,import",
+                Level::High,
+                Range {
+                    start_line: 1,
+                    start_column: 1,
+                    end_line: 2,
+                    end_column: 4,
+                    ..Default::default()
+                },
+            ),
+            issue(
+                "rustfmt",
+                "fmt",
+                Level::Fmt,
+                Range {
+                    start_line: 10,
+                    end_line: 10,
+                    ..Default::default()
+                },
+            ),
+            Issue {
+                location: None,
+                ..issue("shellcheck", "SC1000", Level::Low, Range::default())
+            },
+        ]
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let formatter = GitHubFormatter::boxed(issues());
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn test_github_step_summary() {
+        let formatter = GitHubFormatter::new(issues());
+        insta::assert_snapshot!(formatter.step_summary());
+
+        let formatter = GitHubFormatter::new(vec![]);
+        assert_eq!(
+            formatter.step_summary(),
+            "## Qlty Check\n\nNo issues found.\n\n"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use qlty_analysis::fingerprint::issue_fingerprint;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Category, Issue, Level};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Writes issues as a GitLab Code Quality report (a subset of the Code Climate issue format)
+#[derive(Debug)]
+pub struct GitLabFormatter {
+    issues: Vec<Issue>,
+}
+
+#[derive(Debug, Serialize)]
+struct CodeQualityIssue {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    check_name: String,
+    description: String,
+    categories: Vec<&'static str>,
+    severity: &'static str,
+    fingerprint: String,
+    location: CodeQualityLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Debug, Serialize)]
+struct CodeQualityLines {
+    begin: u32,
+    end: u32,
+}
+
+impl GitLabFormatter {
+    pub fn new(issues: Vec<Issue>) -> Self {
+        Self { issues }
+    }
+
+    pub fn boxed(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self::new(issues))
+    }
+
+    fn convert_issues(&self) -> Vec<CodeQualityIssue> {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        self.issues
+            .iter()
+            .filter(|issue| issue.path().is_some())
+            .map(|issue| {
+                let base = Self::fingerprint(issue);
+
+                // GitLab requires fingerprints to be unique within a report
+                let occurrence = seen.entry(base.clone()).or_default();
+                let fingerprint = if *occurrence == 0 {
+                    base
+                } else {
+                    format!("{:x}", md5::compute(format!("{}:{}", base, occurrence)))
+                };
+                *occurrence += 1;
+
+                let range = issue.range().unwrap_or_default();
+                let begin = range.start_line.max(1);
+
+                CodeQualityIssue {
+                    ty: "issue",
+                    check_name: format!("{}:{}", issue.tool, issue.rule_key),
+                    description: issue.message.clone(),
+                    categories: vec![Self::convert_category(
+                        Category::try_from(issue.category).unwrap_or(Category::Unspecified),
+                    )],
+                    severity: Self::convert_level(
+                        Level::try_from(issue.level).unwrap_or(Level::Medium),
+                    ),
+                    fingerprint,
+                    location: CodeQualityLocation {
+                        path: issue.path().unwrap_or_default(),
+                        lines: CodeQualityLines {
+                            begin,
+                            end: range.end_line.max(begin),
+                        },
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn fingerprint(issue: &Issue) -> String {
+        if issue.fingerprint.is_empty() {
+            issue_fingerprint(issue)
+        } else {
+            issue.fingerprint.clone()
+        }
+    }
+
+    fn convert_level(level: Level) -> &'static str {
+        match level {
+            Level::Unspecified => "info",
+            Level::Note => "info",
+            Level::Fmt => "minor",
+            Level::Low => "minor",
+            Level::Medium => "major",
+            Level::High => "critical",
+        }
+    }
+
+    fn convert_category(category: Category) -> &'static str {
+        match category {
+            Category::Bug => "Bug Risk",
+            Category::TypeCheck => "Bug Risk",
+            Category::DeadCode => "Bug Risk",
+            Category::AntiPattern => "Bug Risk",
+            Category::Vulnerability => "Security",
+            Category::SecurityHotspot => "Security",
+            Category::Secret => "Security",
+            Category::DependencyAlert => "Security",
+            Category::Structure => "Complexity",
+            Category::Duplication => "Duplication",
+            Category::Performance => "Performance",
+            Category::Accessibility => "Compatibility",
+            Category::Style => "Style",
+            Category::Lint => "Style",
+            Category::Documentation => "Clarity",
+            Category::Coverage => "Clarity",
+            Category::Unspecified => "Style",
+        }
+    }
+}
+
+impl Formatter for GitLabFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.convert_issues())?;
+        writer.write_all(json.as_bytes())?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn issue(rule_key: &str, start_line: u32, end_line: u32, snippet: &str) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            driver: "lint".to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of {}", rule_key),
+            level: Level::Medium.into(),
+            category: Category::Bug.into(),
+            snippet: snippet.to_string(),
+            location: Some(Location {
+                path: "src/index.js".to_string(),
+                range: Some(Range {
+                    start_line,
+                    end_line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_gitlab_formatter() {
+        let issues = vec![
+            issue("no-unused-vars", 3, 3, "let a = 1;"),
+            issue("no-unused-vars", 9, 9, "let a = 1;"),
+            Issue {
+                level: Level::High.into(),
+                category: Category::Structure.into(),
+                ..issue("complexity", 12, 40, "")
+            },
+            Issue {
+                location: None,
+                ..issue("no-config", 0, 0, "")
+            },
+        ];
+
+        let formatter = GitLabFormatter::boxed(issues);
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let before = issue("no-unused-vars", 3, 3, "let a = 1;");
+        let after = issue("no-unused-vars", 7, 7, "let a = 1;");
+        assert_eq!(
+            GitLabFormatter::fingerprint(&before),
+            GitLabFormatter::fingerprint(&after)
+        );
+
+        let fingerprinted = Issue {
+            fingerprint: "0a1b2c".to_string(),
+            ..before
+        };
+        assert_eq!(GitLabFormatter::fingerprint(&fingerprinted), "0a1b2c");
+    }
+}
@@ -0,0 +1,593 @@
+use super::escape_xml as escape;
+use crate::ui::HtmlHighlighter;
+use anyhow::Result;
+use itertools::Itertools;
+use qlty_config::version::QLTY_VERSION;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Issue, Level, Location, Stats};
+use qlty_types::tests::v1::FileCoverage;
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const CONTEXT_LINES: usize = 3;
+const MAX_SNIPPET_LINES: usize = 40;
+
+const STYLESHEET: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 0; color: #1f2328; background: #f6f8fa; }
+header { background: #24292f; color: #fff; padding: 16px 32px; }
+header h1 { margin: 0 0 4px 0; font-size: 20px; }
+header p { margin: 0; opacity: 0.8; }
+main { padding: 16px 32px; }
+section { margin-bottom: 32px; }
+h2 { font-size: 18px; border-bottom: 1px solid #d0d7de; padding-bottom: 4px; }
+table.data { border-collapse: collapse; background: #fff; }
+table.data th, table.data td { border: 1px solid #d0d7de; padding: 4px 8px; }
+table.data td.number { text-align: right; font-variant-numeric: tabular-nums; }
+details.file { background: #fff; border: 1px solid #d0d7de; border-radius: 6px; margin-bottom: 12px; }
+details.file > summary { cursor: pointer; padding: 8px 12px; font-family: monospace; font-weight: 600; }
+.count { background: #eaeef2; border-radius: 10px; padding: 0 8px; font-weight: normal; }
+.issue { border-top: 1px solid #d0d7de; padding: 8px 12px; }
+.issue-header { margin-bottom: 6px; }
+.level { display: inline-block; min-width: 56px; text-align: center; border-radius: 4px; font-size: 12px; font-weight: 600; color: #fff; }
+.level-high { background: #cf222e; }
+.level-medium { background: #bf8700; }
+.level-low { background: #0969da; }
+.level-fmt, .level-note { background: #6e7781; }
+.rule { color: #57606a; font-family: monospace; font-size: 12px; margin-left: 8px; }
+.locations { display: grid; grid-template-columns: repeat(auto-fit, minmax(420px, 1fr)); gap: 8px; }
+.location-path { font-family: monospace; font-size: 12px; color: #57606a; }
+table.code { border-collapse: collapse; width: 100%; font-family: monospace; font-size: 12px; border: 1px solid #d0d7de; }
+table.code td { padding: 0 6px; white-space: pre; vertical-align: top; }
+table.code td.line-number { text-align: right; color: #8c959f; user-select: none; width: 1%; }
+table.code tr.marked td.line-number { color: #1f2328; font-weight: 600; }
+table.code tr.marked { background: #fff8c5; }
+table.code tr.covered td.line-number { background: #dafbe1; }
+table.code tr.uncovered td.line-number { background: #ffebe9; }
+"#;
+
+/// Writes a self-contained HTML report of issues, metrics and coverage
+pub struct HtmlFormatter {
+    root: PathBuf,
+    title: String,
+    issues: Vec<Issue>,
+    stats: Vec<Stats>,
+    file_coverages: Vec<FileCoverage>,
+    highlighter: OnceCell<HtmlHighlighter>,
+    sources: RefCell<HashMap<String, Option<Rc<String>>>>,
+}
+
+impl HtmlFormatter {
+    pub fn new(root: &Path, title: &str) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            title: title.to_string(),
+            issues: vec![],
+            stats: vec![],
+            file_coverages: vec![],
+            highlighter: OnceCell::new(),
+            sources: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_issues(mut self, issues: Vec<Issue>) -> Self {
+        self.issues = issues;
+        self
+    }
+
+    pub fn with_stats(mut self, stats: Vec<Stats>) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    pub fn with_coverage(mut self, file_coverages: Vec<FileCoverage>) -> Self {
+        self.file_coverages = file_coverages;
+        self
+    }
+
+    pub fn boxed(self) -> Box<dyn Formatter> {
+        Box::new(self)
+    }
+
+    fn write_header(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, r#"<html lang="en">"#)?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, r#"<meta charset="utf-8">"#)?;
+        writeln!(writer, "<title>{}</title>", escape(&self.title))?;
+        writeln!(writer, "<style>{}</style>", STYLESHEET)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<header>")?;
+        writeln!(writer, "<h1>{}</h1>", escape(&self.title))?;
+
+        let mut summary = vec![];
+
+        if !self.issues.is_empty() || self.stats.is_empty() {
+            summary.push(pluralize(self.issues.len(), "issue"));
+        }
+
+        if !self.stats.is_empty() {
+            summary.push(pluralize(self.stats.len(), "file"));
+        }
+
+        if let Some(percent) = coverage_percent(self.file_coverages.iter()) {
+            summary.push(format!("{:.1}% line coverage", percent));
+        }
+
+        writeln!(
+            writer,
+            "<p>{} &middot; qlty {}</p>",
+            summary.join(" &middot; "),
+            QLTY_VERSION
+        )?;
+        writeln!(writer, "</header>")?;
+        Ok(())
+    }
+
+    fn write_metrics(&self, writer: &mut dyn Write) -> Result<()> {
+        if self.stats.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(writer, "<section>")?;
+        writeln!(writer, "<h2>Metrics</h2>")?;
+        writeln!(writer, r#"<table class="data">"#)?;
+        writeln!(
+            writer,
+            "<tr><th>name</th><th>classes</th><th>funcs</th><th>fields</th><th>cyclo</th><th>complex</th><th>LCOM</th><th>lines</th><th>LOC</th></tr>"
+        )?;
+
+        for stats in self
+            .stats
+            .iter()
+            .sorted_by(|a, b| a.fully_qualified_name.cmp(&b.fully_qualified_name))
+        {
+            let values = [
+                stats.classes,
+                stats.functions,
+                stats.fields,
+                stats.cyclomatic,
+                stats.complexity,
+                stats.lcom4,
+                stats.lines,
+                stats.code_lines,
+            ];
+
+            write!(
+                writer,
+                "<tr><td>{}</td>",
+                escape(&stats.fully_qualified_name)
+            )?;
+
+            for value in values {
+                write!(
+                    writer,
+                    r#"<td class="number">{}</td>"#,
+                    value.map(|value| value.to_string()).unwrap_or_default()
+                )?;
+            }
+
+            writeln!(writer, "</tr>")?;
+        }
+
+        writeln!(writer, "</table>")?;
+        writeln!(writer, "</section>")?;
+        Ok(())
+    }
+
+    fn write_coverage(&self, writer: &mut dyn Write) -> Result<()> {
+        if self.file_coverages.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(writer, "<section>")?;
+        writeln!(writer, "<h2>Coverage</h2>")?;
+        writeln!(writer, r#"<table class="data">"#)?;
+        writeln!(
+            writer,
+            "<tr><th>file</th><th>covered</th><th>coverable</th><th>coverage</th></tr>"
+        )?;
+
+        for file_coverage in self
+            .file_coverages
+            .iter()
+            .sorted_by(|a, b| a.path.cmp(&b.path))
+        {
+            let (covered, coverable) = coverage_counts(file_coverage);
+
+            writeln!(
+                writer,
+                r#"<tr><td>{}</td><td class="number">{}</td><td class="number">{}</td><td class="number">{}</td></tr>"#,
+                escape(&file_coverage.path),
+                covered,
+                coverable,
+                coverage_percent(std::iter::once(file_coverage))
+                    .map(|percent| format!("{:.1}%", percent))
+                    .unwrap_or_default()
+            )?;
+        }
+
+        writeln!(writer, "</table>")?;
+        writeln!(writer, "</section>")?;
+        Ok(())
+    }
+
+    fn write_issues(&self, writer: &mut dyn Write) -> Result<()> {
+        if self.issues.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(writer, "<section>")?;
+        writeln!(writer, "<h2>Issues</h2>")?;
+
+        let issues_by_path = self
+            .issues
+            .iter()
+            .sorted()
+            .into_group_map_by(|issue| issue.path().unwrap_or_default());
+
+        for (path, issues) in issues_by_path.iter().sorted_by_key(|(path, _)| *path) {
+            let name = if path.is_empty() {
+                "(no file)"
+            } else {
+                path.as_str()
+            };
+
+            writeln!(writer, r#"<details class="file" open>"#)?;
+            writeln!(
+                writer,
+                r#"<summary>{} <span class="count">{}</span></summary>"#,
+                escape(name),
+                issues.len()
+            )?;
+
+            for issue in issues {
+                self.write_issue(writer, issue)?;
+            }
+
+            writeln!(writer, "</details>")?;
+        }
+
+        writeln!(writer, "</section>")?;
+        Ok(())
+    }
+
+    fn write_issue(&self, writer: &mut dyn Write, issue: &Issue) -> Result<()> {
+        let level = match Level::try_from(issue.level).unwrap_or(Level::Medium) {
+            Level::High => "high",
+            Level::Medium => "medium",
+            Level::Low => "low",
+            Level::Fmt => "fmt",
+            Level::Note | Level::Unspecified => "note",
+        };
+
+        let rule = escape(&format!("{}:{}", issue.tool, issue.rule_key));
+        let rule = if issue.documentation_url.is_empty() {
+            rule
+        } else {
+            format!(
+                r#"<a href="{}">{}</a>"#,
+                escape(&issue.documentation_url),
+                rule
+            )
+        };
+
+        if issue.fingerprint.is_empty() {
+            writeln!(writer, r#"<div class="issue">"#)?;
+        } else {
+            writeln!(
+                writer,
+                r#"<div class="issue" data-fingerprint="{}">"#,
+                escape(&issue.fingerprint)
+            )?;
+        }
+        writeln!(
+            writer,
+            r#"<div class="issue-header"><span class="level level-{}">{}</span> {} <span class="rule">{}</span></div>"#,
+            level,
+            level,
+            escape(&issue.message),
+            rule
+        )?;
+
+        let mut snippets = vec![];
+
+        if let Some(location) = &issue.location {
+            snippets.extend(self.snippet(location, &issue.snippet_with_context));
+        }
+
+        // Duplication and similar smells are shown side by side with their other locations
+        for location in &issue.other_locations {
+            snippets.extend(self.snippet(location, ""));
+        }
+
+        if !snippets.is_empty() {
+            let show_paths = !issue.other_locations.is_empty();
+
+            writeln!(writer, r#"<div class="locations">"#)?;
+
+            for (location, snippet) in snippets {
+                writeln!(writer, "<div>")?;
+
+                if show_paths {
+                    let range = location.range.unwrap_or_default();
+                    writeln!(
+                        writer,
+                        r#"<div class="location-path">{}:{}</div>"#,
+                        escape(&location.path),
+                        range.start_line
+                    )?;
+                }
+
+                writer.write_all(snippet.as_bytes())?;
+                writeln!(writer, "</div>")?;
+            }
+
+            writeln!(writer, "</div>")?;
+        }
+
+        writeln!(writer, "</div>")?;
+        Ok(())
+    }
+
+    fn snippet<'a>(&self, location: &'a Location, context: &str) -> Option<(&'a Location, String)> {
+        let range = location.range?;
+        let start_line = range.start_line as usize;
+        let end_line = (range.end_line as usize).max(start_line);
+
+        if start_line == 0 {
+            return None;
+        }
+
+        let hits = self
+            .file_coverages
+            .iter()
+            .find(|file_coverage| file_coverage.path == location.path)
+            .map(|file_coverage| file_coverage.hits.as_slice())
+            .unwrap_or_default();
+
+        let mut html = String::from(r#"<table class="code">"#);
+        html.push('\n');
+
+        let path = Path::new(&location.path);
+
+        if let Some(source) = self.source(&location.path) {
+            let first = start_line.saturating_sub(CONTEXT_LINES).max(1);
+            let last = (end_line + CONTEXT_LINES)
+                .min(source.lines().count())
+                .min(first + MAX_SNIPPET_LINES - 1);
+            let lines = self.highlight(path, &source, first..=last)?;
+
+            for (line_number, line) in (first..=last).zip(lines) {
+                let mut classes = vec![];
+
+                if (start_line..=end_line).contains(&line_number) {
+                    classes.push("marked");
+                }
+
+                match hits.get(line_number - 1) {
+                    Some(hits) if *hits > 0 => classes.push("covered"),
+                    Some(0) => classes.push("uncovered"),
+                    _ => {}
+                }
+
+                html.push_str(&format!(
+                    r#"<tr class="{}"><td class="line-number">{}</td><td>{}</td></tr>"#,
+                    classes.join(" "),
+                    line_number,
+                    line
+                ));
+                html.push('\n');
+            }
+        } else if !context.is_empty() {
+            // The file is not available, so fall back to the snippet captured during analysis
+            for line in self.highlight(path, context, 1..=MAX_SNIPPET_LINES)? {
+                html.push_str(&format!(r#"<tr><td>{}</td></tr>"#, line));
+                html.push('\n');
+            }
+        } else {
+            return None;
+        }
+
+        html.push_str("</table>\n");
+        Some((location, html))
+    }
+
+    fn highlight(
+        &self,
+        path: &Path,
+        contents: &str,
+        line_numbers: RangeInclusive<usize>,
+    ) -> Option<Vec<String>> {
+        self.highlighter
+            .get_or_init(HtmlHighlighter::new)
+            .highlight(path, contents, line_numbers)
+            .ok()
+    }
+
+    fn source(&self, path: &str) -> Option<Rc<String>> {
+        self.sources
+            .borrow_mut()
+            .entry(path.to_string())
+            .or_insert_with(|| {
+                std::fs::read_to_string(self.root.join(path))
+                    .ok()
+                    .map(Rc::new)
+            })
+            .clone()
+    }
+}
+
+impl Formatter for HtmlFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        self.write_header(writer)?;
+        writeln!(writer, "<main>")?;
+        self.write_metrics(writer)?;
+        self.write_coverage(writer)?;
+        self.write_issues(writer)?;
+
+        if self.issues.is_empty() && self.stats.is_empty() && self.file_coverages.is_empty() {
+            writeln!(writer, "<p>No issues found.</p>")?;
+        }
+
+        writeln!(writer, "</main>")?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+}
+
+fn coverage_counts(file_coverage: &FileCoverage) -> (usize, usize) {
+    let coverable = file_coverage.hits.iter().filter(|hits| **hits >= 0);
+    let covered = coverable.clone().filter(|hits| **hits > 0).count();
+    (covered, coverable.count())
+}
+
+fn coverage_percent<'a>(file_coverages: impl Iterator<Item = &'a FileCoverage>) -> Option<f64> {
+    let (covered, coverable) = file_coverages
+        .map(coverage_counts)
+        .fold((0, 0), |(covered, coverable), (c, t)| {
+            (covered + c, coverable + t)
+        });
+
+    if coverable == 0 {
+        None
+    } else {
+        Some(covered as f64 * 100.0 / coverable as f64)
+    }
+}
+
+fn pluralize(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::Range;
+    use tempfile::tempdir;
+
+    fn location(path: &str, start_line: u32, end_line: u32) -> Location {
+        Location {
+            path: path.to_string(),
+            range: Some(Range {
+                start_line,
+                end_line,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_html_formatter() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "fn main() {\n    let x = 1 < 2;\n    println!(\"{}\", x);\n}\n",
+        )
+        .unwrap();
+
+        let issues = vec![
+            Issue {
+                tool: "clippy".to_string(),
+                rule_key: "comparison".to_string(),
+                message: "Comparison is always <true>".to_string(),
+                level: Level::High.into(),
+                fingerprint: "5d41402abc4b2a76b9719d911017c592".to_string(),
+                documentation_url: "https://example.com/comparison".to_string(),
+                location: Some(location("src/main.rs", 2, 2)),
+                other_locations: vec![location("src/main.rs", 3, 3)],
+                ..Default::default()
+            },
+            Issue {
+                tool: "qlty".to_string(),
+                rule_key: "similar-code".to_string(),
+                message: "Found similar code".to_string(),
+                level: Level::Medium.into(),
+                snippet_with_context: "def foo\nend".to_string(),
+                location: Some(location("lib/missing.rb", 10, 11)),
+                ..Default::default()
+            },
+        ];
+
+        let file_coverages = vec![FileCoverage {
+            path: "src/main.rs".to_string(),
+            hits: vec![-1, 1, 0, -1],
+            ..Default::default()
+        }];
+
+        let formatter = HtmlFormatter::new(dir.path(), "qlty check")
+            .with_issues(issues)
+            .with_coverage(file_coverages)
+            .boxed();
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<p>2 issues &middot; 50.0% line coverage &middot; qlty"));
+        assert!(output.contains("Comparison is always &lt;true&gt;"));
+        assert!(output.contains(
+            r#"<div class="issue" data-fingerprint="5d41402abc4b2a76b9719d911017c592">"#
+        ));
+        assert!(
+            output.contains(r#"<a href="https://example.com/comparison">clippy:comparison</a>"#)
+        );
+        assert!(output.contains(r#"<tr class="marked covered"><td class="line-number">2</td>"#));
+        assert!(output.contains(r#"<tr class="uncovered"><td class="line-number">3</td>"#));
+        assert!(output.contains(r#"<div class="location-path">src/main.rs:3</div>"#));
+        assert!(output.contains("lib/missing.rb"));
+        assert!(output.contains("foo"));
+        assert!(!output.contains("<script"));
+    }
+
+    #[test]
+    fn test_html_formatter_snippet_window() {
+        let dir = tempdir().unwrap();
+        let contents = (1..=100)
+            .map(|line| format!("x = {}\n", line))
+            .collect::<String>();
+        std::fs::write(dir.path().join("main.py"), contents).unwrap();
+
+        let issues = vec![Issue {
+            tool: "ruff".to_string(),
+            rule_key: "E501".to_string(),
+            location: Some(location("main.py", 50, 50)),
+            ..Default::default()
+        }];
+
+        let formatter = HtmlFormatter::new(dir.path(), "qlty check").with_issues(issues);
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+
+        assert!(output.contains(r#"<td class="line-number">47</td>"#));
+        assert!(output.contains(r#"<tr class="marked"><td class="line-number">50</td>"#));
+        assert!(output.contains(r#"<td class="line-number">53</td>"#));
+        assert!(!output.contains(r#"<td class="line-number">46</td>"#));
+        assert!(!output.contains(r#"<td class="line-number">54</td>"#));
+    }
+
+    #[test]
+    fn test_html_formatter_metrics() {
+        let stats = vec![Stats {
+            fully_qualified_name: "src/main.rs".to_string(),
+            functions: Some(3),
+            lines: Some(40),
+            ..Default::default()
+        }];
+
+        let formatter = HtmlFormatter::new(Path::new("."), "qlty metrics").with_stats(stats);
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+
+        assert!(output.contains("<p>1 file &middot; qlty"));
+        assert!(output
+            .contains(r#"<td>src/main.rs</td><td class="number"></td><td class="number">3</td>"#));
+        assert!(!output.contains("<h2>Issues</h2>"));
+    }
+}
@@ -0,0 +1,169 @@
+use super::{escape_xml, fingerprint_attribute};
+use anyhow::Result;
+use itertools::Itertools;
+use qlty_check::Report;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::Issue;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Writes a JUnit XML report with one testsuite per plugin, one testcase per file
+/// and one failure per issue
+#[derive(Debug)]
+pub struct JUnitFormatter {
+    report: Report,
+}
+
+impl JUnitFormatter {
+    pub fn new(report: Report) -> Self {
+        Self { report }
+    }
+
+    pub fn boxed(report: Report) -> Box<dyn Formatter> {
+        Box::new(Self::new(report))
+    }
+
+    /// Plugins which ran without reporting any issues are included as empty suites
+    fn suites(&self) -> BTreeMap<&str, BTreeMap<String, Vec<&Issue>>> {
+        let mut suites: BTreeMap<&str, BTreeMap<String, Vec<&Issue>>> = BTreeMap::new();
+
+        for invocation in &self.report.invocations {
+            suites
+                .entry(invocation.invocation.plugin_name.as_str())
+                .or_default();
+        }
+
+        for issue in self.report.issues.iter().sorted() {
+            if let Some(path) = issue.path() {
+                suites
+                    .entry(issue.tool.as_str())
+                    .or_default()
+                    .entry(path)
+                    .or_default()
+                    .push(issue);
+            }
+        }
+
+        suites
+    }
+
+    fn write_failure(writer: &mut dyn Write, path: &str, issue: &Issue) -> std::io::Result<()> {
+        let range = issue.range().unwrap_or_default();
+
+        writeln!(
+            writer,
+            r#"      <failure message="{}" type="{}"{}>{}:{}:{}: {}</failure>"#,
+            escape_xml(&issue.message),
+            escape_xml(&issue.rule_key),
+            fingerprint_attribute(issue),
+            escape_xml(path),
+            range.start_line,
+            range.start_column,
+            escape_xml(&issue.message)
+        )
+    }
+}
+
+impl Formatter for JUnitFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        let suites = self.suites();
+        let tests: usize = suites.values().map(|files| files.len()).sum();
+        let failures: usize = suites
+            .values()
+            .flat_map(|files| files.values())
+            .map(|issues| issues.len())
+            .sum();
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="qlty" tests="{}" failures="{}">"#,
+            tests, failures
+        )?;
+
+        for (plugin, files) in &suites {
+            let failures: usize = files.values().map(|issues| issues.len()).sum();
+
+            writeln!(
+                writer,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" skipped="0">"#,
+                escape_xml(plugin),
+                files.len(),
+                failures
+            )?;
+
+            for (path, issues) in files {
+                writeln!(
+                    writer,
+                    r#"    <testcase name="{}" classname="{}">"#,
+                    escape_xml(path),
+                    escape_xml(plugin)
+                )?;
+
+                for issue in issues {
+                    Self::write_failure(writer, path, issue)?;
+                }
+
+                writeln!(writer, "    </testcase>")?;
+            }
+
+            writeln!(writer, "  </testsuite>")?;
+        }
+
+        writeln!(writer, "</testsuites>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_analysis::{workspace_entries::TargetMode, IssueCount};
+    use qlty_types::analysis::v1::{ExecutionVerb, Level, Location, Range};
+    use std::collections::HashSet;
+
+    fn issue(tool: &str, rule_key: &str, path: &str, line: u32) -> Issue {
+        Issue {
+            tool: tool.to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of {} & <friends>", rule_key),
+            level: Level::Medium.into(),
+            fingerprint: format!("{}-{}-{}", tool, rule_key, line),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    start_column: 1,
+                    end_line: line,
+                    end_column: 10,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_junit_formatter() {
+        let report = Report {
+            issues: vec![
+                issue("eslint", "no-unused-vars", "src/b.js", 9),
+                issue("eslint", "no-unused-vars", "src/a.js", 3),
+                issue("eslint", "eqeqeq", "src/a.js", 7),
+                issue("shellcheck", "SC2086", "bin/setup", 12),
+            ],
+            verb: ExecutionVerb::Check,
+            target_mode: TargetMode::default(),
+            messages: vec![],
+            invocations: vec![],
+            formatted: vec![],
+            fixed: HashSet::new(),
+            fixable: HashSet::new(),
+            counts: IssueCount::default(),
+        };
+
+        let formatter = JUnitFormatter::boxed(report);
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+        insta::assert_snapshot!(output);
+    }
+}
@@ -1,3 +1,90 @@
+use qlty_check::Report;
+use qlty_formats::{Formatter, JsonFormatter};
+use qlty_types::analysis::v1::Issue;
+use qlty_types::tests::v1::FileCoverage;
+use std::path::Path;
+
+mod checkstyle;
+mod github;
+mod gitlab;
+mod html;
+mod junit;
+mod reviewdog;
 mod sarif;
 
+pub use checkstyle::CheckstyleFormatter;
+pub use github::GitHubFormatter;
+pub use gitlab::GitLabFormatter;
+pub use html::HtmlFormatter;
+pub use junit::JUnitFormatter;
+pub use reviewdog::ReviewdogFormatter;
 pub use sarif::SarifFormatter;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+    Github,
+    Gitlab,
+    Html,
+    Junit,
+    Checkstyle,
+    Rdjson,
+    Rdjsonl,
+}
+
+impl OutputFormat {
+    /// Parses only the given formats, for commands which support a subset of them
+    pub fn parser(
+        formats: &'static [&'static str],
+    ) -> impl clap::builder::TypedValueParser<Value = Self> {
+        use clap::builder::TypedValueParser as _;
+
+        clap::builder::PossibleValuesParser::new(formats)
+            .map(|format| <Self as clap::ValueEnum>::from_str(&format, false).unwrap())
+    }
+
+    /// Builds the formatter for a machine-readable format. Text output is
+    /// interactive, so it is left to `TextFormatter` and this returns `None`.
+    pub fn report_formatter(
+        self,
+        report: &Report,
+        root: &Path,
+        file_coverages: &[FileCoverage],
+    ) -> Option<Box<dyn Formatter>> {
+        Some(match self {
+            OutputFormat::Text => return None,
+            OutputFormat::Json => JsonFormatter::boxed(report.issues.clone()),
+            OutputFormat::Sarif => SarifFormatter::boxed(report.clone()),
+            OutputFormat::Github => GitHubFormatter::boxed(report.issues.clone()),
+            OutputFormat::Gitlab => GitLabFormatter::boxed(report.issues.clone()),
+            OutputFormat::Html => HtmlFormatter::new(root, "qlty check")
+                .with_issues(report.issues.clone())
+                .with_coverage(file_coverages.to_vec())
+                .boxed(),
+            OutputFormat::Junit => JUnitFormatter::boxed(report.clone()),
+            OutputFormat::Checkstyle => CheckstyleFormatter::boxed(report.clone()),
+            OutputFormat::Rdjson => ReviewdogFormatter::rdjson(report.issues.clone()),
+            OutputFormat::Rdjsonl => ReviewdogFormatter::rdjsonl(report.issues.clone()),
+        })
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Neither JUnit nor Checkstyle define a fingerprint, so it is added as an extra attribute
+fn fingerprint_attribute(issue: &Issue) -> String {
+    if issue.fingerprint.is_empty() {
+        "".to_string()
+    } else {
+        format!(r#" fingerprint="{}""#, escape_xml(&issue.fingerprint))
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::Result;
+use diffy::{Line, Patch};
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Issue, Level, Range, Suggestion};
+use serde::Serialize;
+use std::io::Write;
+use tracing::debug;
+
+/// Writes issues in reviewdog's Diagnostic Format, either as a single rdjson
+/// document or as rdjsonl with one diagnostic per line
+#[derive(Debug)]
+pub struct ReviewdogFormatter {
+    issues: Vec<Issue>,
+    lines: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticResult {
+    source: Source,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+struct Diagnostic {
+    message: String,
+    location: DiagnosticLocation,
+    severity: &'static str,
+    source: Source,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<Code>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suggestions: Vec<DiagnosticSuggestion>,
+}
+
+#[derive(Debug, Serialize)]
+struct Source {
+    name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Code {
+    value: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticLocation {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range: Option<DiagnosticRange>,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticRange {
+    start: Position,
+    end: Position,
+}
+
+#[derive(Debug, Serialize)]
+struct Position {
+    line: u32,
+    column: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct DiagnosticSuggestion {
+    range: DiagnosticRange,
+    text: String,
+}
+
+impl ReviewdogFormatter {
+    pub fn rdjson(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self {
+            issues,
+            lines: false,
+        })
+    }
+
+    pub fn rdjsonl(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self {
+            issues,
+            lines: true,
+        })
+    }
+
+    fn diagnostic(issue: &Issue) -> Option<Diagnostic> {
+        let path = issue.path()?;
+
+        let code = if issue.rule_key.is_empty() {
+            None
+        } else {
+            Some(Code {
+                value: issue.rule_key.clone(),
+                url: issue.documentation_url.clone(),
+            })
+        };
+
+        Some(Diagnostic {
+            message: issue.message.clone(),
+            location: DiagnosticLocation {
+                range: issue.range().and_then(convert_range),
+                path: path.clone(),
+            },
+            severity: Self::convert_level(Level::try_from(issue.level).unwrap_or(Level::Medium)),
+            source: Source {
+                name: issue.tool.clone(),
+                url: "".to_string(),
+            },
+            code,
+            suggestions: issue
+                .suggestions
+                .first()
+                .map(|suggestion| Self::suggestions(&path, suggestion))
+                .unwrap_or_default(),
+        })
+    }
+
+    fn convert_level(level: Level) -> &'static str {
+        match level {
+            Level::Unspecified => "UNKNOWN_SEVERITY",
+            Level::Note => "INFO",
+            Level::Fmt => "INFO",
+            Level::Low => "INFO",
+            Level::Medium => "WARNING",
+            Level::High => "ERROR",
+        }
+    }
+
+    /// Linters provide replacements, while AI fixes only carry a unified diff
+    fn suggestions(path: &str, suggestion: &Suggestion) -> Vec<DiagnosticSuggestion> {
+        if !suggestion.replacements.is_empty() {
+            suggestion
+                .replacements
+                .iter()
+                .filter(|replacement| {
+                    replacement
+                        .location
+                        .as_ref()
+                        .is_none_or(|location| location.path == path)
+                })
+                .filter_map(|replacement| {
+                    Some(DiagnosticSuggestion {
+                        range: convert_range(replacement.location.as_ref()?.range?)?,
+                        text: replacement.data.clone(),
+                    })
+                })
+                .collect()
+        } else if !suggestion.patch.is_empty() {
+            match Patch::from_str(&suggestion.patch) {
+                Ok(patch) => patch.hunks().iter().filter_map(hunk_suggestion).collect(),
+                Err(error) => {
+                    debug!("Unable to parse suggestion patch: {}", error);
+                    vec![]
+                }
+            }
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl Formatter for ReviewdogFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        let diagnostics = self.issues.iter().filter_map(Self::diagnostic);
+
+        if self.lines {
+            for diagnostic in diagnostics {
+                serde_json::to_writer(&mut *writer, &diagnostic)?;
+                writer.write_all(b"\n")?;
+            }
+        } else {
+            let result = DiagnosticResult {
+                source: Source {
+                    name: "qlty".to_string(),
+                    url: "https://github.com/qlty/qlty".to_string(),
+                },
+                diagnostics: diagnostics.collect(),
+            };
+
+            let json = serde_json::to_string_pretty(&result)?;
+            writer.write_all(json.as_bytes())?;
+            writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn convert_range(range: Range) -> Option<DiagnosticRange> {
+    if range.start_line == 0 {
+        return None;
+    }
+
+    Some(DiagnosticRange {
+        start: Position {
+            line: range.start_line,
+            column: range.start_column,
+        },
+        end: Position {
+            line: range.end_line.max(range.start_line),
+            column: range.end_column,
+        },
+    })
+}
+
+/// Converts the changed lines of a hunk into a whole-line replacement,
+/// dropping the surrounding context lines
+fn hunk_suggestion(hunk: &diffy::Hunk<'_, str>) -> Option<DiagnosticSuggestion> {
+    let lines = hunk.lines();
+    let first = lines
+        .iter()
+        .position(|line| !matches!(line, Line::Context(_)))?;
+    let last = lines
+        .iter()
+        .rposition(|line| !matches!(line, Line::Context(_)))?;
+
+    let start_line = (hunk.old_range().start() + first).max(1);
+    let mut replaced_lines = 0;
+    let mut text = String::new();
+
+    for line in &lines[first..=last] {
+        match line {
+            Line::Context(content) => {
+                replaced_lines += 1;
+                text.push_str(content);
+            }
+            Line::Delete(_) => replaced_lines += 1,
+            Line::Insert(content) => text.push_str(content),
+        }
+    }
+
+    Some(DiagnosticSuggestion {
+        range: DiagnosticRange {
+            start: Position {
+                line: start_line as u32,
+                column: 1,
+            },
+            end: Position {
+                line: (start_line + replaced_lines) as u32,
+                column: 1,
+            },
+        },
+        text,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Replacement};
+
+    fn location(start_line: u32, start_column: u32, end_line: u32, end_column: u32) -> Location {
+        Location {
+            path: "src/index.ts".to_string(),
+            range: Some(Range {
+                start_line,
+                start_column,
+                end_line,
+                end_column,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn issues() -> Vec<Issue> {
+        vec![
+            Issue {
+                tool: "eslint".to_string(),
+                rule_key: "prefer-const".to_string(),
+                message: "'a' is never reassigned. Use 'const' instead.".to_string(),
+                level: Level::High.into(),
+                documentation_url: "https://eslint.org/docs/rules/prefer-const".to_string(),
+                location: Some(location(1, 1, 1, 4)),
+                suggestions: vec![Suggestion {
+                    replacements: vec![Replacement {
+                        data: "const".to_string(),
+                        location: Some(location(1, 1, 1, 4)),
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Issue {
+                tool: "qlty".to_string(),
+                rule_key: "function-complexity".to_string(),
+                message: "Function with high complexity".to_string(),
+                level: Level::Medium.into(),
+                location: Some(location(3, 0, 9, 0)),
+                suggestions: vec![Suggestion {
+                    patch: diffy::create_patch(
+                        "let a = 1;\n\nif (a) {\n  if (b) {\n    go();\n  }\n}\n",
+                        "let a = 1;\n\nif (a && b) {\n  go();\n}\n",
+                    )
+                    .to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Issue {
+                tool: "qlty".to_string(),
+                message: "Issue without a location".to_string(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_rdjson() {
+        let output = String::from_utf8(ReviewdogFormatter::rdjson(issues()).read().unwrap());
+        insta::assert_snapshot!(output.unwrap());
+    }
+
+    #[test]
+    fn test_rdjsonl() {
+        let output = String::from_utf8(ReviewdogFormatter::rdjsonl(issues()).read().unwrap());
+        let output = output.unwrap();
+        assert_eq!(output.lines().count(), 2);
+
+        for line in output.lines() {
+            let diagnostic: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(diagnostic["location"]["path"].is_string());
+        }
+    }
+}
@@ -163,6 +163,10 @@ impl SarifFormatter {
 
         let mut partial_fingerprints = Map::new();
 
+        if !issue.fingerprint.is_empty() {
+            partial_fingerprints.insert("qlty/v1".to_string(), json!(issue.fingerprint));
+        }
+
         if !issue.source_checksum.is_empty() {
             partial_fingerprints.insert(
                 format!("sourceHash/{}", issue.source_checksum_version),
@@ -354,6 +358,7 @@ mod test {
         let comprehensive_issue = Issue {
             rule_key: "test-rule-1".to_string(),
             message: "Test message 1".to_string(),
+            fingerprint: "5d41402abc4b2a76b9719d911017c592".to_string(),
             level: Level::High.into(),
             language: Language::Rust.into(),
             location: Some(Location {
@@ -4,6 +4,7 @@ pub mod auth;
 mod commands;
 mod errors;
 pub mod export;
+mod files_from;
 pub mod format;
 mod initializer;
 mod logging;
@@ -12,6 +13,7 @@ mod success;
 mod telemetry;
 mod ui;
 mod upgrade;
+mod watcher;
 
 pub use arguments::Arguments;
 pub use auth::{clear_auth_token, load_or_retrieve_auth_token, store_auth_token};
@@ -67,122 +69,131 @@ fn handle_result(
             );
             telemetry.track_command_success(&command_success).ok();
 
-            if let Some(count) = command_success.issues_count {
-                let fixed_count = command_success.fixed_count;
-                if count == 0 {
-                    eprintln!("{}", style("✔ No issues").green().bold());
-                } else if fixed_count > 0 {
-                    let remaining = count - fixed_count;
-
-                    if remaining > 0 {
-                        eprintln!(
-                            "{}",
-                            style(format!(
-                                "✖ {}/{} fixed {}",
-                                fixed_count,
-                                count,
-                                if count == 1 { "issue" } else { "issues" }
-                            ))
-                            .red()
-                            .bold()
-                        );
-                    } else {
-                        eprintln!(
-                            "{}",
-                            style(format!(
-                                "✔ {} fixed {}",
-                                count,
-                                if count == 1 { "issue" } else { "issues" }
-                            ))
-                            .green()
-                            .bold()
-                        );
-                    }
-                } else {
-                    eprintln!(
-                        "{}",
-                        style(format!(
-                            "✖ {} {}",
-                            count,
-                            if count == 1 { "issue" } else { "issues" }
-                        ))
-                        .red()
-                        .bold()
-                    );
-                }
-
-                if let Some(count) = command_success.security_issues_count {
-                    if count > 0 {
-                        eprintln!(
-                            "{}",
-                            style(format!(
-                                "✖ {} security {}",
-                                count,
-                                if count == 1 { "issue" } else { "issues" }
-                            ))
-                            .red()
-                            .bold()
-                        );
-                    }
-                }
-
-                if let Some(count) = command_success.unformatted_count {
-                    if count > 0 {
-                        eprintln!(
-                            "{}",
-                            style(format!(
-                                "✖ {} unformatted {}",
-                                count,
-                                if count == 1 { "file" } else { "files" }
-                            ))
-                            .red()
-                            .bold()
-                        );
-                    }
-                }
-            }
-
+            print_command_success(&command_success);
             std::process::exit(command_success.exit_code());
         }
         Err(command_error) => {
-            match command_error {
-                CommandError::InvalidOptions { ref message } => {
-                    error!("Invalid options: {}", message);
-                    eprintln!("{} {}", style("error:").red().bold(), message);
-                    eprintln!();
-                    eprintln!("For more information, try {}.", style("'--help'").bold());
-                }
-                CommandError::Config => {
-                    error!("Config error");
-                    eprintln!("❌ Config error");
-                }
-                CommandError::Lint => {
-                    error!("Lint error");
-                    eprintln!("❌ Lint error");
-                }
-                CommandError::Unknown { ref source } => {
-                    error!("Command failed: {}", command);
-                    error!("{:?}", source);
-                    eprintln!();
-                    eprintln!("{}", style("   ERROR   ").red().bold().reverse());
-                    eprintln!();
-
-                    let error_message = format!("{:?}", source);
-                    let error_message = error_message
-                        .lines()
-                        .map(|line| format!(" {} {}", style(">").red().bold(), line))
-                        .collect::<Vec<_>>()
-                        .join("\n");
-                    eprintln!("{}", error_message);
-                }
+            if let CommandError::Unknown { .. } = command_error {
+                error!("Command failed: {}", command);
             }
 
+            print_command_error(&command_error);
             telemetry.track_command_error(&command_error).ok();
             std::process::exit(command_error.exit_code());
         }
     }
 }
 
+fn print_command_success(command_success: &CommandSuccess) {
+    if let Some(count) = command_success.issues_count {
+        let fixed_count = command_success.fixed_count;
+        if count == 0 {
+            eprintln!("{}", style("✔ No issues").green().bold());
+        } else if fixed_count > 0 {
+            let remaining = count - fixed_count;
+
+            if remaining > 0 {
+                eprintln!(
+                    "{}",
+                    style(format!(
+                        "✖ {}/{} fixed {}",
+                        fixed_count,
+                        count,
+                        if count == 1 { "issue" } else { "issues" }
+                    ))
+                    .red()
+                    .bold()
+                );
+            } else {
+                eprintln!(
+                    "{}",
+                    style(format!(
+                        "✔ {} fixed {}",
+                        count,
+                        if count == 1 { "issue" } else { "issues" }
+                    ))
+                    .green()
+                    .bold()
+                );
+            }
+        } else {
+            eprintln!(
+                "{}",
+                style(format!(
+                    "✖ {} {}",
+                    count,
+                    if count == 1 { "issue" } else { "issues" }
+                ))
+                .red()
+                .bold()
+            );
+        }
+
+        if let Some(count) = command_success.security_issues_count {
+            if count > 0 {
+                eprintln!(
+                    "{}",
+                    style(format!(
+                        "✖ {} security {}",
+                        count,
+                        if count == 1 { "issue" } else { "issues" }
+                    ))
+                    .red()
+                    .bold()
+                );
+            }
+        }
+
+        if let Some(count) = command_success.unformatted_count {
+            if count > 0 {
+                eprintln!(
+                    "{}",
+                    style(format!(
+                        "✖ {} unformatted {}",
+                        count,
+                        if count == 1 { "file" } else { "files" }
+                    ))
+                    .red()
+                    .bold()
+                );
+            }
+        }
+    }
+}
+
+fn print_command_error(command_error: &CommandError) {
+    match command_error {
+        CommandError::InvalidOptions { ref message } => {
+            error!("Invalid options: {}", message);
+            eprintln!("{} {}", style("error:").red().bold(), message);
+            eprintln!();
+            eprintln!("For more information, try {}.", style("'--help'").bold());
+        }
+        CommandError::Config => {
+            error!("Config error");
+            eprintln!("❌ Config error");
+        }
+        CommandError::Lint => {
+            error!("Lint error");
+            eprintln!("❌ Lint error");
+        }
+        CommandError::Unknown { ref source } => {
+            error!("{:?}", source);
+            eprintln!();
+            eprintln!("{}", style("   ERROR   ").red().bold().reverse());
+            eprintln!();
+
+            let error_message = format!("{:?}", source);
+            let error_message = error_message
+                .lines()
+                .map(|line| format!(" {} {}", style(">").red().bold(), line))
+                .collect::<Vec<_>>()
+                .join("\n");
+            eprintln!("{}", error_message);
+        }
+    }
+}
+
 fn setup_panic_hook(telemetry: Telemetry) {
     trace!("Setting up panic hook");
     let telemetry = telemetry.clone();
@@ -5,6 +5,7 @@ mod invocations;
 mod issues;
 mod level;
 mod messages;
+mod profile;
 mod source;
 mod steps;
 mod text;
@@ -12,6 +13,7 @@ mod unformatted;
 
 pub use errors::ErrorsFormatter;
 pub use fixes::ApplyMode;
-pub use highlight::Highlighter;
+pub use highlight::{Highlighter, HtmlHighlighter};
+pub use profile::print_profile;
 pub use steps::Steps;
 pub use text::TextFormatter;
@@ -3,6 +3,7 @@ use console::style;
 use std::{ops::RangeInclusive, path::Path};
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Color, Style, ThemeSet};
+use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
 use syntect::parsing::SyntaxSet;
 use syntect::util::as_24_bit_terminal_escaped;
 use termbg::Theme;
@@ -96,6 +97,61 @@ impl Highlighter {
     }
 }
 
+/// Highlights source as HTML with inline styles, for reports opened in a browser.
+/// Loading the syntaxes and themes is slow, so one instance is shared across files.
+pub struct HtmlHighlighter {
+    syntax_set: SyntaxSet,
+    theme: syntect::highlighting::Theme,
+}
+
+impl Default for HtmlHighlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlHighlighter {
+    pub fn new() -> Self {
+        let mut themes = ThemeSet::load_defaults().themes;
+
+        Self {
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme: themes.remove("InspiredGitHub").unwrap(),
+        }
+    }
+
+    /// Highlights the given 1-based range of lines, starting the parse at the first of them
+    pub fn highlight(
+        &self,
+        path: &Path,
+        contents: &str,
+        line_numbers: RangeInclusive<usize>,
+    ) -> Result<Vec<String>> {
+        let syntax = path
+            .extension()
+            .and_then(|extension| {
+                self.syntax_set
+                    .find_syntax_by_extension(&extension.to_string_lossy())
+            })
+            .or_else(|| self.syntax_set.find_syntax_by_first_line(contents))
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+
+        let mut h = HighlightLines::new(syntax, &self.theme);
+        let mut highlighted_lines = vec![];
+        let first = line_numbers.start().saturating_sub(1);
+        let count = (line_numbers.end() + 1).saturating_sub(*line_numbers.start());
+
+        for line in contents.lines().skip(first).take(count) {
+            let line = format!("{}\n", line);
+            let ranges: Vec<(Style, &str)> = h.highlight_line(&line, &self.syntax_set)?;
+            let html = styled_line_to_highlighted_html(&ranges[..], IncludeBackground::No)?;
+            highlighted_lines.push(html.replace('\n', ""));
+        }
+
+        Ok(highlighted_lines)
+    }
+}
+
 struct TermbgToSyntectColorAdapter;
 
 impl TermbgToSyntectColorAdapter {
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use console::style;
+use qlty_check::executor::profiler::{driver_timings, Span};
+use std::io::Write as _;
+use tabwriter::TabWriter;
+
+const SLOWEST_DRIVERS: usize = 10;
+
+pub fn print_profile(writer: &mut dyn std::io::Write, spans: &[Span]) -> Result<()> {
+    let timings = driver_timings(spans);
+
+    if timings.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "{}", style(" SLOWEST DRIVERS ").bold().reverse())?;
+    writeln!(writer)?;
+
+    let mut tw = TabWriter::new(vec![]);
+
+    writeln!(
+        tw,
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        style("Driver").bold().underlined(),
+        style("Total").bold().underlined(),
+        style("Slowest").bold().underlined(),
+        style("Invocations").bold().underlined(),
+        style("Targets").bold().underlined(),
+        style("Cached").bold().underlined(),
+    )?;
+
+    for timing in timings.iter().take(SLOWEST_DRIVERS) {
+        writeln!(
+            tw,
+            "{}\t{:.2}s\t{:.2}s\t{}\t{}\t{}",
+            timing.label,
+            timing.total.as_secs_f32(),
+            timing.slowest.as_secs_f32(),
+            timing.invocations,
+            timing.targets,
+            timing.cache_hits,
+        )?;
+    }
+
+    tw.flush()?;
+    writeln!(writer, "{}", String::from_utf8(tw.into_inner()?)?)?;
+
+    Ok(())
+}
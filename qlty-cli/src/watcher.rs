@@ -0,0 +1,178 @@
+use crate::{print_command_error, print_command_success, CommandError, CommandSuccess};
+use anyhow::Result;
+use console::style;
+use qlty_analysis::workspace_entries::IgnoreGroupsMatcher;
+use qlty_analysis::{AllSource, FileMatcher, WorkspaceEntryMatcher, WorkspaceEntrySource};
+use qlty_config::config::ignore_group::IgnoreGroup;
+use qlty_config::config::Ignore;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::debug;
+
+// Each poll walks the whole workspace, so this is kept coarse
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// Polls the workspace for modified files, skipping anything excluded by
+/// `.gitignore` or the `ignore` blocks in qlty.toml
+#[derive(Debug)]
+pub struct Watcher {
+    root: PathBuf,
+    ignore_matcher: IgnoreGroupsMatcher,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(root: &Path, ignores: &[Ignore]) -> Result<Self> {
+        let file_ignores = ignores
+            .iter()
+            .filter(|i| i.plugins.is_empty() && i.rules.is_empty() && i.levels.is_empty())
+            .collect();
+
+        let mut watcher = Self {
+            root: root.to_path_buf(),
+            ignore_matcher: IgnoreGroupsMatcher::new(IgnoreGroup::build_from_ignores(
+                &file_ignores,
+            )),
+            snapshot: HashMap::new(),
+        };
+
+        watcher.refresh()?;
+        Ok(watcher)
+    }
+
+    /// Blocks until files are added or modified and the edits have settled,
+    /// returning the absolute paths of the changed files
+    pub fn wait(&mut self) -> Result<Vec<PathBuf>> {
+        let mut changed = vec![];
+        let mut last_change = Instant::now();
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let changes = self.changes()?;
+
+            if !changes.is_empty() {
+                debug!("Detected changes: {:?}", changes);
+                changed.extend(changes);
+                last_change = Instant::now();
+            } else if !changed.is_empty() && last_change.elapsed() >= DEBOUNCE {
+                changed.sort();
+                changed.dedup();
+
+                return Ok(changed
+                    .into_iter()
+                    .map(|path| self.root.join(path))
+                    .filter(|path| path.is_file())
+                    .collect());
+            }
+        }
+    }
+
+    /// Forgets any changes since the last snapshot, such as files rewritten by fixes
+    pub fn refresh(&mut self) -> Result<()> {
+        self.snapshot = self.scan()?;
+        Ok(())
+    }
+
+    fn changes(&mut self) -> Result<Vec<PathBuf>> {
+        let snapshot = self.scan()?;
+
+        let changes = snapshot
+            .iter()
+            .filter(|(path, stamp)| self.snapshot.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        self.snapshot = snapshot;
+        Ok(changes)
+    }
+
+    fn scan(&self) -> Result<Snapshot> {
+        let source = AllSource::new(self.root.clone());
+
+        Ok(source
+            .entries()?
+            .iter()
+            .cloned()
+            .filter_map(|entry| FileMatcher.matches(entry))
+            .filter_map(|entry| self.ignore_matcher.matches(entry))
+            .map(|entry| (entry.path, (entry.content_modified, entry.contents_size)))
+            .collect())
+    }
+}
+
+/// Runs the command once, then again for the changed files each time the workspace
+/// changes. The command receives no paths on the first run.
+pub fn watch(
+    root: &Path,
+    ignores: &[Ignore],
+    mut command: impl FnMut(&[PathBuf]) -> Result<CommandSuccess, CommandError>,
+) -> Result<CommandSuccess, CommandError> {
+    let mut watcher = Watcher::new(root, ignores)?;
+    let mut paths = vec![];
+
+    loop {
+        match command(&paths) {
+            Ok(command_success) => print_command_success(&command_success),
+            Err(command_error) => print_command_error(&command_error),
+        }
+
+        watcher.refresh()?;
+        eprintln!();
+        eprintln!("{}", style("Watching for changes...").dim());
+
+        paths = watcher.wait()?;
+
+        eprintln!(
+            "{}",
+            style(format!(
+                "{} {} changed",
+                paths.len(),
+                if paths.len() == 1 { "file" } else { "files" }
+            ))
+            .bold()
+        );
+        eprintln!();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_changes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::write(root.join("main.rb"), "puts 1").unwrap();
+        fs::write(root.join("other.rb"), "puts 2").unwrap();
+        fs::create_dir(root.join("vendor")).unwrap();
+        fs::write(root.join("vendor/lib.rb"), "puts 3").unwrap();
+
+        let ignores = vec![Ignore {
+            file_patterns: vec!["vendor/**".to_string()],
+            ..Default::default()
+        }];
+
+        let mut watcher = Watcher::new(&root, &ignores).unwrap();
+        assert!(watcher.changes().unwrap().is_empty());
+
+        fs::write(root.join("main.rb"), "puts 10").unwrap();
+        fs::write(root.join("new.rb"), "puts 4").unwrap();
+        fs::write(root.join("vendor/lib.rb"), "puts 30").unwrap();
+        fs::remove_file(root.join("other.rb")).unwrap();
+
+        let mut changes = watcher.changes().unwrap();
+        changes.sort();
+        assert_eq!(
+            changes,
+            vec![PathBuf::from("main.rb"), PathBuf::from("new.rb")]
+        );
+        assert!(watcher.changes().unwrap().is_empty());
+    }
+}
@@ -43,7 +43,12 @@ fn smells_tests() {
 
 #[test]
 fn coverage_tests() {
-    setup_and_run_test_cases("tests/cmd/coverage/**/*.toml");
+    setup_and_run_test_cases("tests/cmd/coverage/*.toml");
+}
+
+#[test]
+fn git_based_coverage_tests() {
+    setup_and_run_diff_test_cases("tests/cmd/coverage/diff_tests/*.toml");
 }
 
 #[test]
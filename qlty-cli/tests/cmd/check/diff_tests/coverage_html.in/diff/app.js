@@ -0,0 +1,11 @@
+const { add } = require("../src/lib");
+
+function total(items) {
+  if (items.length === 0) {
+    return 0;
+  }
+
+  return items.reduce(add, 0);
+}
+
+module.exports = { total };
@@ -0,0 +1,4 @@
+line
+line
+line
+line
@@ -0,0 +1,11 @@
+function init() {
+  if (true) {
+    return;
+  }
+  return;
+  return;
+  return;
+  return;
+  return;
+  return;
+}
@@ -0,0 +1,8 @@
+function foo() {
+  return;
+  return;
+  return;
+  return;
+  return;
+  return;
+}
@@ -1,4 +1,5 @@
 mod builder;
+mod cache;
 mod coverage;
 mod download;
 mod file_type;
@@ -16,8 +17,9 @@ pub use self::ignore::{Ignore, ALL_WILDCARD};
 pub use self::overrides::Override;
 use self::smells::Smells;
 pub use builder::Builder;
+pub use cache::CacheConfig;
 use console::style;
-pub use coverage::Coverage;
+pub use coverage::{Coverage, CoverageThreshold};
 pub use download::{Cpu, DownloadDef, DownloadFileType, OperatingSystem, System};
 pub use file_type::FileType;
 pub use language::Language;
@@ -85,6 +87,11 @@ pub struct QltyConfig {
 
     pub smells: Option<Smells>,
 
+    pub baseline: Option<String>,
+
+    #[serde(default)]
+    pub cache: CacheConfig,
+
     #[serde(default)]
     pub source: Vec<SourceDef>,
 }
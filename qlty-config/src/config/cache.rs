@@ -0,0 +1,25 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct CacheConfig {
+    /// A results cache shared between machines, consulted on a local miss.
+    /// An `http://` or `https://` URL of a server which supports GET and PUT,
+    /// or a directory on a shared filesystem
+    pub remote: Option<String>,
+
+    /// Read from the remote cache without writing results back to it
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Seconds to wait for the remote cache before falling back to running locally
+    pub timeout: Option<u64>,
+
+    /// Prune the global cache after `qlty check` once it grows larger than
+    /// this size, such as `20GB`
+    pub max_size: Option<String>,
+
+    /// Prune tools and results from the global cache after `qlty check` once
+    /// they have not been used for this long, such as `30d`
+    pub max_age: Option<String>,
+}
@@ -5,4 +5,17 @@ use serde::{Deserialize, Serialize};
 pub struct Coverage {
     pub paths: Option<Vec<String>>,
     pub ignores: Option<Vec<String>>,
+
+    /// Minimum total line coverage percentage (0-100)
+    pub minimum: Option<f64>,
+
+    #[serde(default)]
+    pub thresholds: Vec<CoverageThreshold>,
+}
+
+/// Minimum line coverage percentage for the files matching a glob
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct CoverageThreshold {
+    pub path: String,
+    pub minimum: f64,
 }
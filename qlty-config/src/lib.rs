@@ -11,7 +11,7 @@ pub use crate::config::FileType;
 pub use crate::config::QltyConfig;
 use crate::toml_merge::TomlMerge;
 pub use config::issue_transformer;
-pub use library::Library;
+pub use library::{CacheCategory, CacheUsage, EvictionPolicy, Library};
 pub use migration::{MigrateConfig, MigrationSettings};
 pub use user::UserData;
 pub use workspace::Workspace;
@@ -2,10 +2,14 @@ use anyhow::Result;
 use std::{
     env, fs,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 use tracing::error;
 use walkdir::WalkDir;
 
+mod eviction;
+pub use eviction::{CacheCategory, CacheUsage, EvictionPolicy};
+
 #[derive(Debug, Clone)]
 pub struct Library {
     pub local_root: PathBuf,
@@ -199,6 +203,11 @@ impl Library {
         Ok(())
     }
 
+    /// Evicts tools and results from the global cache, across every project
+    pub fn evict(policy: &EvictionPolicy) -> Result<Vec<CacheUsage>> {
+        eviction::evict(&Self::global_cache_root()?, policy, SystemTime::now())
+    }
+
     fn prune_dir(&self, dir: &Path, days: u32) -> Result<()> {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
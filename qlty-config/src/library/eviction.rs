@@ -0,0 +1,478 @@
+use anyhow::Result;
+use fslock::LockFile;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, warn};
+use walkdir::WalkDir;
+
+/// Entries used more recently than this may belong to a check which is still
+/// running, so they are never evicted
+const IN_USE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Suffixes of the files a tool keeps next to its installation directory
+const TOOL_SUFFIXES: &[&str] = &[
+    ".done",
+    ".lock",
+    "-installation-debug-files",
+    "-install.log",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CacheCategory {
+    Tools,
+    Results,
+    Logs,
+    Output,
+}
+
+impl CacheCategory {
+    const ALL: [CacheCategory; 4] = [Self::Tools, Self::Results, Self::Logs, Self::Output];
+}
+
+impl fmt::Display for CacheCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tools => write!(f, "tools"),
+            Self::Results => write!(f, "results"),
+            Self::Logs => write!(f, "logs"),
+            Self::Output => write!(f, "output"),
+        }
+    }
+}
+
+/// Limits on the global cache. Entries unused for longer than `max_age` are
+/// evicted, and then the least recently used entries until the cache fits in
+/// `max_size` bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvictionPolicy {
+    pub max_age: Option<Duration>,
+    pub max_size: Option<u64>,
+}
+
+impl EvictionPolicy {
+    /// The shortest `max_age` which is honored, since recently used entries are never evicted
+    pub const MIN_AGE: Duration = IN_USE_WINDOW;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub category: CacheCategory,
+    pub evicted_entries: usize,
+    pub evicted_bytes: u64,
+    pub remaining_entries: usize,
+    pub remaining_bytes: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    category: CacheCategory,
+    paths: Vec<PathBuf>,
+    /// The lock held while installing a tool, which is kept when it is evicted
+    lockfile: Option<PathBuf>,
+    bytes: u64,
+    last_used: SystemTime,
+}
+
+/// Applies the policy to the cache at `root`, returning usage per category
+pub fn evict(root: &Path, policy: &EvictionPolicy, now: SystemTime) -> Result<Vec<CacheUsage>> {
+    let entries = scan(root)?;
+    let mut evicted = vec![false; entries.len()];
+    let age = |entry: &CacheEntry| now.duration_since(entry.last_used).unwrap_or_default();
+
+    if let Some(max_age) = policy.max_age {
+        for (index, entry) in entries.iter().enumerate() {
+            if age(entry) > max_age.max(IN_USE_WINDOW) {
+                evicted[index] = true;
+            }
+        }
+    }
+
+    if let Some(max_size) = policy.max_size {
+        let mut total: u64 = entries
+            .iter()
+            .zip(&evicted)
+            .filter(|(_, evicted)| !**evicted)
+            .map(|(entry, _)| entry.bytes)
+            .sum();
+
+        // Results are cheaper to recompute than tools are to reinstall, so
+        // tools are only evicted once no other entries are left
+        let mut candidates = (0..entries.len())
+            .filter(|index| !evicted[*index] && age(&entries[*index]) > IN_USE_WINDOW)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|index| {
+            let entry = &entries[*index];
+            (entry.category == CacheCategory::Tools, entry.last_used)
+        });
+
+        for index in candidates {
+            if total <= max_size {
+                break;
+            }
+
+            evicted[index] = true;
+            total -= entries[index].bytes;
+        }
+    }
+
+    let mut usages = CacheCategory::ALL
+        .iter()
+        .map(|category| CacheUsage {
+            category: *category,
+            evicted_entries: 0,
+            evicted_bytes: 0,
+            remaining_entries: 0,
+            remaining_bytes: 0,
+        })
+        .collect::<Vec<_>>();
+
+    for (entry, evicted) in entries.iter().zip(evicted) {
+        let usage = usages
+            .iter_mut()
+            .find(|usage| usage.category == entry.category)
+            .unwrap();
+
+        if evicted && remove(entry) {
+            usage.evicted_entries += 1;
+            usage.evicted_bytes += entry.bytes;
+        } else {
+            usage.remaining_entries += 1;
+            usage.remaining_bytes += entry.bytes;
+        }
+    }
+
+    remove_empty_dirs(&root.join("tools"));
+    Ok(usages)
+}
+
+fn scan(root: &Path) -> Result<Vec<CacheEntry>> {
+    let mut entries = scan_tools(&root.join("tools"))?;
+
+    let repos = root.join("repos");
+    if repos.is_dir() {
+        for repo in fs::read_dir(&repos)? {
+            let repo = repo?.path();
+
+            for (dir, category) in [
+                ("results", CacheCategory::Results),
+                ("logs", CacheCategory::Logs),
+                ("out", CacheCategory::Output),
+            ] {
+                entries.extend(scan_files(&repo.join(dir), category));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Groups each tool installation directory with its done, lock and log files.
+/// A tool touches its done file whenever it is used, so that is when the
+/// installation was last used.
+fn scan_tools(tools: &Path) -> Result<Vec<CacheEntry>> {
+    let mut entries = vec![];
+
+    if !tools.is_dir() {
+        return Ok(entries);
+    }
+
+    for tool in fs::read_dir(tools)? {
+        let tool = tool?.path();
+
+        if !tool.is_dir() {
+            continue;
+        }
+
+        let mut installations: Vec<(String, Vec<PathBuf>)> = vec![];
+
+        for child in fs::read_dir(&tool)? {
+            let path = child?.path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let installation = TOOL_SUFFIXES
+                .iter()
+                .find_map(|suffix| name.strip_suffix(suffix))
+                .unwrap_or(&name)
+                .to_string();
+
+            match installations
+                .iter_mut()
+                .find(|(key, _)| *key == installation)
+            {
+                Some((_, paths)) => paths.push(path),
+                None => installations.push((installation, vec![path])),
+            }
+        }
+
+        for (installation, mut paths) in installations {
+            let lockfile = tool.join(format!("{}.lock", installation));
+            paths.retain(|path| *path != lockfile);
+
+            if paths.is_empty() {
+                continue;
+            }
+
+            let donefile = tool.join(format!("{}.done", installation));
+            let last_used = if donefile.exists() {
+                last_used(&donefile)
+            } else {
+                paths.iter().map(|path| last_used(path)).max().unwrap()
+            };
+
+            entries.push(CacheEntry {
+                category: CacheCategory::Tools,
+                bytes: paths.iter().map(|path| size(path)).sum(),
+                paths,
+                lockfile: Some(lockfile),
+                last_used,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+fn scan_files(dir: &Path, category: CacheCategory) -> Vec<CacheEntry> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| CacheEntry {
+            category,
+            bytes: entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+            last_used: last_used(entry.path()),
+            paths: vec![entry.into_path()],
+            lockfile: None,
+        })
+        .collect()
+}
+
+fn last_used(path: &Path) -> SystemTime {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) => {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            let accessed = metadata.accessed().unwrap_or(SystemTime::UNIX_EPOCH);
+            modified.max(accessed)
+        }
+        Err(_) => SystemTime::UNIX_EPOCH,
+    }
+}
+
+fn size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_type().is_dir())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Removes the entry's files. Tools are only removed while holding their install
+/// lock, and are skipped if another process is installing them.
+fn remove(entry: &CacheEntry) -> bool {
+    let _lock = match &entry.lockfile {
+        Some(path) => match try_lock(path) {
+            Ok(Some(lock)) => Some(lock),
+            Ok(None) => {
+                debug!("Skipping {} which is locked", path.display());
+                return false;
+            }
+            Err(error) => {
+                warn!("Failed to lock {}: {}", path.display(), error);
+                return false;
+            }
+        },
+        None => None,
+    };
+
+    let mut removed = true;
+
+    for path in &entry.paths {
+        let result = if path.is_dir() && !path.is_symlink() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        };
+
+        match result {
+            Ok(_) => debug!("Evicted {} from the cache", path.display()),
+            Err(error) => {
+                warn!("Failed to evict {}: {}", path.display(), error);
+                removed = false;
+            }
+        }
+    }
+
+    removed
+}
+
+fn try_lock(path: &Path) -> Result<Option<LockFile>> {
+    let mut lockfile = LockFile::open(path)?;
+
+    if lockfile.try_lock_with_pid()? {
+        Ok(Some(lockfile))
+    } else {
+        Ok(None)
+    }
+}
+
+fn remove_empty_dirs(dir: &Path) {
+    if let Ok(children) = fs::read_dir(dir) {
+        for child in children.filter_map(|child| child.ok()) {
+            let path = child.path();
+
+            if path.is_dir() && fs::read_dir(&path).is_ok_and(|mut dir| dir.next().is_none()) {
+                fs::remove_dir(&path).ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::{File, FileTimes};
+    use tempfile::tempdir;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn write(path: &Path, bytes: usize, used: SystemTime) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0; bytes]).unwrap();
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_times(FileTimes::new().set_accessed(used).set_modified(used))
+            .unwrap();
+    }
+
+    fn usage(usages: &[CacheUsage], category: CacheCategory) -> &CacheUsage {
+        usages
+            .iter()
+            .find(|usage| usage.category == category)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_evict_by_age() {
+        let root = tempdir().unwrap();
+        let now = SystemTime::now();
+        let tools = root.path().join("tools").join("eslint");
+        let results = root.path().join("repos").join("abc").join("results");
+
+        write(&tools.join("8.0.0-old").join("bin"), 100, now);
+        write(&tools.join("8.0.0-old.done"), 0, now - 40 * DAY);
+        write(&tools.join("8.0.0-old.lock"), 0, now);
+        write(&tools.join("9.0.0-new").join("bin"), 100, now - 40 * DAY);
+        write(&tools.join("9.0.0-new.done"), 0, now - DAY);
+        write(&results.join("issues").join("a.protos"), 10, now - 40 * DAY);
+        write(&results.join("issues").join("b.protos"), 10, now);
+
+        let policy = EvictionPolicy {
+            max_age: Some(30 * DAY),
+            max_size: None,
+        };
+        let usages = evict(root.path(), &policy, now).unwrap();
+
+        assert!(!tools.join("8.0.0-old").exists());
+        assert!(!tools.join("8.0.0-old.done").exists());
+        assert!(tools.join("8.0.0-old.lock").exists());
+        assert!(tools.join("9.0.0-new").exists());
+        assert!(!results.join("issues").join("a.protos").exists());
+
+        assert_eq!(
+            usage(&usages, CacheCategory::Tools),
+            &CacheUsage {
+                category: CacheCategory::Tools,
+                evicted_entries: 1,
+                evicted_bytes: 100,
+                remaining_entries: 1,
+                remaining_bytes: 100,
+            }
+        );
+        assert_eq!(usage(&usages, CacheCategory::Results).evicted_entries, 1);
+    }
+
+    #[test]
+    fn test_evict_by_size() {
+        let root = tempdir().unwrap();
+        let now = SystemTime::now();
+        let tools = root.path().join("tools").join("eslint");
+        let repo = root.path().join("repos").join("abc");
+
+        write(&tools.join("8.0.0-abc").join("bin"), 100, now);
+        write(&tools.join("8.0.0-abc.done"), 0, now - 10 * DAY);
+        write(
+            &repo.join("results").join("issues").join("a.protos"),
+            50,
+            now - 2 * HOUR,
+        );
+        write(&repo.join("logs").join("check.log"), 50, now - DAY);
+
+        let policy = EvictionPolicy {
+            max_age: None,
+            max_size: Some(120),
+        };
+        let usages = evict(root.path(), &policy, now).unwrap();
+
+        // The log and results go before the tool, even though the tool is older
+        assert!(tools.join("8.0.0-abc").exists());
+        assert!(!repo.join("logs").join("check.log").exists());
+        assert!(!repo
+            .join("results")
+            .join("issues")
+            .join("a.protos")
+            .exists());
+        assert_eq!(usage(&usages, CacheCategory::Logs).evicted_bytes, 50);
+        assert_eq!(usage(&usages, CacheCategory::Tools).remaining_bytes, 100);
+
+        let policy = EvictionPolicy {
+            max_age: None,
+            max_size: Some(0),
+        };
+        evict(root.path(), &policy, now).unwrap();
+
+        assert!(!tools.join("8.0.0-abc").exists());
+        assert!(tools.join("8.0.0-abc.lock").exists());
+    }
+
+    #[test]
+    fn test_evict_skips_entries_in_use() {
+        let root = tempdir().unwrap();
+        let now = SystemTime::now();
+        let tools = root.path().join("tools").join("eslint");
+        let logs = root.path().join("repos").join("abc").join("logs");
+
+        write(&tools.join("8.0.0-abc").join("bin"), 100, now);
+        write(&tools.join("8.0.0-abc.done"), 0, now - 40 * DAY);
+        write(&tools.join("8.0.0-abc.lock"), 0, now);
+        write(&logs.join("old.log"), 10, now - 2 * HOUR);
+        write(&logs.join("running.log"), 10, now);
+
+        // Another process is installing the tool
+        let mut lockfile = LockFile::open(&tools.join("8.0.0-abc.lock")).unwrap();
+        lockfile.lock().unwrap();
+
+        let policy = EvictionPolicy {
+            max_age: Some(DAY),
+            max_size: Some(0),
+        };
+        let usages = evict(root.path(), &policy, now).unwrap();
+
+        assert!(tools.join("8.0.0-abc").exists());
+        assert!(!logs.join("old.log").exists());
+        assert!(logs.join("running.log").exists());
+        assert_eq!(usage(&usages, CacheCategory::Tools).remaining_entries, 1);
+        assert_eq!(usage(&usages, CacheCategory::Logs).remaining_entries, 1);
+
+        lockfile.unlock().unwrap();
+        evict(root.path(), &policy, now).unwrap();
+
+        assert!(!tools.join("8.0.0-abc").exists());
+        assert!(tools.join("8.0.0-abc.lock").exists());
+    }
+}
@@ -1,17 +1,31 @@
+mod azure_pipelines;
+mod bitbucket;
 mod buildkite;
 mod circleci;
+mod codebuild;
 mod codefresh;
+mod drone;
 mod github;
 mod gitlab;
+mod jenkins;
 mod semaphore;
+mod travisci;
+mod woodpecker;
 
+pub use azure_pipelines::AzurePipelines;
+pub use bitbucket::Bitbucket;
 pub use buildkite::Buildkite;
 pub use circleci::CircleCI;
+pub use codebuild::CodeBuild;
 pub use codefresh::Codefresh;
+pub use drone::Drone;
 pub use github::GitHub;
 pub use gitlab::GitLab;
+pub use jenkins::Jenkins;
 use qlty_types::tests::v1::CoverageMetadata;
 pub use semaphore::Semaphore;
+pub use travisci::TravisCI;
+pub use woodpecker::Woodpecker;
 
 const QLTY_CI_UPLOADER_TOOL: &str = "QLTY_CI_UPLOADER_TOOL";
 const QLTY_CI_UPLOADER_TOOL_VERSION: &str = "QLTY_CI_UPLOADER_TOOL_VERSION";
@@ -33,10 +47,11 @@ pub trait CI {
     fn pull_url(&self) -> String;
     fn commit_sha(&self) -> String;
 
-    // Information about the commit
-    // TODO: Message
-    // TODO: Author and committer
-    // TODO: Timestamp
+    // Whether the commit is a synthetic merge of a pull request into its base,
+    // in which case commit information is read from the pull request head
+    fn merge_ref(&self) -> bool {
+        false
+    }
 
     // Information about the build configuration
     // Structured as Workflow > Job
@@ -68,11 +83,18 @@ pub fn current() -> Option<Box<dyn CI>> {
 
 pub fn all() -> Vec<Box<dyn CI>> {
     vec![
+        Box::<AzurePipelines>::default(),
+        Box::<Bitbucket>::default(),
         Box::<Buildkite>::default(),
         Box::<CircleCI>::default(),
+        Box::<CodeBuild>::default(),
         Box::<Codefresh>::default(),
+        Box::<Drone>::default(),
         Box::<GitHub>::default(),
         Box::<GitLab>::default(),
+        Box::<Jenkins>::default(),
         Box::<Semaphore>::default(),
+        Box::<TravisCI>::default(),
+        Box::<Woodpecker>::default(),
     ]
 }
@@ -0,0 +1,304 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
+
+#[derive(Debug)]
+pub struct AzurePipelines {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for AzurePipelines {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl AzurePipelines {
+    fn is_pull_request(&self) -> bool {
+        self.env.var("BUILD_REASON").unwrap_or_default() == "PullRequest"
+    }
+}
+
+impl CI for AzurePipelines {
+    fn detect(&self) -> bool {
+        self.env
+            .var("TF_BUILD")
+            .unwrap_or_default()
+            .eq_ignore_ascii_case("true")
+    }
+
+    fn ci_name(&self) -> String {
+        "Azure Pipelines".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        self.env.var("SYSTEM_COLLECTIONURI").unwrap_or_default()
+    }
+
+    fn branch(&self) -> String {
+        let source_branch = if self.is_pull_request() {
+            self.env
+                .var("SYSTEM_PULLREQUEST_SOURCEBRANCH")
+                .unwrap_or_default()
+        } else {
+            self.env.var("BUILD_SOURCEBRANCH").unwrap_or_default()
+        };
+
+        if source_branch.starts_with("refs/tags/") {
+            "".to_string()
+        } else {
+            source_branch
+                .strip_prefix("refs/heads/")
+                .map(str::to_string)
+                .unwrap_or(source_branch)
+        }
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("BUILD_DEFINITIONNAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("SYSTEM_JOBDISPLAYNAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("BUILD_BUILDID").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        let project = self.env.var("SYSTEM_TEAMPROJECT").unwrap_or_default();
+
+        if self.ci_url().is_empty() || project.is_empty() || self.build_id().is_empty() {
+            "".to_string()
+        } else {
+            format!(
+                "{}/{}/_build/results?buildId={}",
+                self.ci_url().trim_end_matches('/'),
+                project,
+                self.build_id()
+            )
+        }
+    }
+
+    fn pull_number(&self) -> String {
+        if !self.is_pull_request() {
+            return "".to_string();
+        }
+
+        // GitHub repositories have a number, Azure Repos only an ID
+        self.env
+            .var("SYSTEM_PULLREQUEST_PULLREQUESTNUMBER")
+            .filter(|number| !number.is_empty())
+            .or_else(|| self.env.var("SYSTEM_PULLREQUEST_PULLREQUESTID"))
+            .unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("BUILD_REPOSITORY_NAME").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("BUILD_REPOSITORY_URI").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // Azure Pipelines doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        // BUILD_SOURCEVERSION is the merge commit for pull requests
+        if self.is_pull_request() {
+            if let Some(sha) = self
+                .env
+                .var("SYSTEM_PULLREQUEST_SOURCECOMMITID")
+                .filter(|sha| !sha.is_empty())
+            {
+                return sha;
+            }
+        }
+
+        self.env.var("BUILD_SOURCEVERSION").unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TF_BUILD".to_string(), "True".to_string());
+        env.insert(
+            "SYSTEM_COLLECTIONURI".to_string(),
+            "https://dev.azure.com/qltysh/".to_string(),
+        );
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "Azure Pipelines");
+        assert_eq!(&ci.ci_url(), "https://dev.azure.com/qltysh/");
+    }
+
+    #[test]
+    fn branch_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_REASON".to_string(), "IndividualCI".to_string());
+        env.insert(
+            "BUILD_SOURCEBRANCH".to_string(),
+            "refs/heads/feature/login".to_string(),
+        );
+        env.insert("BUILD_SOURCEBRANCHNAME".to_string(), "login".to_string());
+        env.insert(
+            "BUILD_SOURCEVERSION".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert("BUILD_REPOSITORY_NAME".to_string(), "qlty".to_string());
+        env.insert(
+            "BUILD_REPOSITORY_URI".to_string(),
+            "https://dev.azure.com/qltysh/qlty/_git/qlty".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature/login");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_name(), "qlty");
+        assert_eq!(
+            &ci.repository_url(),
+            "https://dev.azure.com/qltysh/qlty/_git/qlty"
+        );
+    }
+
+    #[test]
+    fn github_pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_REASON".to_string(), "PullRequest".to_string());
+        env.insert(
+            "BUILD_SOURCEBRANCH".to_string(),
+            "refs/pull/42/merge".to_string(),
+        );
+        env.insert(
+            "BUILD_SOURCEVERSION".to_string(),
+            "0dd7a2a4fd1ab2d3a25ee6d73d6a0e3a62d2a0c5".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_SOURCEBRANCH".to_string(),
+            "feature-branch-1".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_SOURCECOMMITID".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_PULLREQUESTID".to_string(),
+            "1187350905".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_PULLREQUESTNUMBER".to_string(),
+            "42".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn azure_repos_pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_REASON".to_string(), "PullRequest".to_string());
+        env.insert(
+            "BUILD_SOURCEVERSION".to_string(),
+            "0dd7a2a4fd1ab2d3a25ee6d73d6a0e3a62d2a0c5".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_SOURCEBRANCH".to_string(),
+            "refs/heads/feature-branch-1".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_PULLREQUESTID".to_string(),
+            "17".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "17");
+        assert_eq!(&ci.commit_sha(), "0dd7a2a4fd1ab2d3a25ee6d73d6a0e3a62d2a0c5");
+    }
+
+    #[test]
+    fn tag_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_REASON".to_string(), "IndividualCI".to_string());
+        env.insert(
+            "BUILD_SOURCEBRANCH".to_string(),
+            "refs/tags/v1.0.0".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "SYSTEM_COLLECTIONURI".to_string(),
+            "https://dev.azure.com/qltysh/".to_string(),
+        );
+        env.insert("SYSTEM_TEAMPROJECT".to_string(), "qlty".to_string());
+        env.insert("BUILD_DEFINITIONNAME".to_string(), "CI".to_string());
+        env.insert("SYSTEM_JOBDISPLAYNAME".to_string(), "Test".to_string());
+        env.insert("BUILD_BUILDID".to_string(), "1234".to_string());
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "CI");
+        assert_eq!(&ci.job(), "Test");
+        assert_eq!(&ci.build_id(), "1234");
+        assert_eq!(
+            &ci.build_url(),
+            "https://dev.azure.com/qltysh/qlty/_build/results?buildId=1234"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
+
+#[derive(Debug)]
+pub struct Bitbucket {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Bitbucket {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for Bitbucket {
+    fn detect(&self) -> bool {
+        !self
+            .env
+            .var("BITBUCKET_BUILD_NUMBER")
+            .unwrap_or_default()
+            .is_empty()
+    }
+
+    fn ci_name(&self) -> String {
+        "Bitbucket".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        "https://bitbucket.org".to_string()
+    }
+
+    fn branch(&self) -> String {
+        self.env.var("BITBUCKET_BRANCH").unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("BITBUCKET_PIPELINE_UUID").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("BITBUCKET_STEP_UUID").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("BITBUCKET_BUILD_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        if self.repository_url() != "" && self.build_id() != "" {
+            format!(
+                "{}/pipelines/results/{}",
+                self.repository_url(),
+                self.build_id()
+            )
+        } else {
+            "".to_string()
+        }
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("BITBUCKET_PR_ID").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("BITBUCKET_REPO_FULL_NAME").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env
+            .var("BITBUCKET_GIT_HTTP_ORIGIN")
+            .unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        if self.repository_url() != "" && self.pull_number() != "" {
+            format!(
+                "{}/pull-requests/{}",
+                self.repository_url(),
+                self.pull_number()
+            )
+        } else {
+            "".to_string()
+        }
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("BITBUCKET_COMMIT").unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Bitbucket {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_BUILD_NUMBER".to_string(), "33".to_string());
+        let ci = Bitbucket {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "Bitbucket");
+        assert_eq!(&ci.ci_url(), "https://bitbucket.org");
+    }
+
+    #[test]
+    fn branch_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_BRANCH".to_string(), "main".to_string());
+        env.insert(
+            "BITBUCKET_COMMIT".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "BITBUCKET_REPO_FULL_NAME".to_string(),
+            "qltysh/qlty".to_string(),
+        );
+        env.insert(
+            "BITBUCKET_GIT_HTTP_ORIGIN".to_string(),
+            "http://bitbucket.org/qltysh/qlty".to_string(),
+        );
+
+        let ci = Bitbucket {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.pull_url(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_name(), "qltysh/qlty");
+        assert_eq!(&ci.repository_url(), "http://bitbucket.org/qltysh/qlty");
+    }
+
+    #[test]
+    fn pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "BITBUCKET_BRANCH".to_string(),
+            "feature-branch-1".to_string(),
+        );
+        env.insert("BITBUCKET_PR_ID".to_string(), "42".to_string());
+        env.insert(
+            "BITBUCKET_PR_DESTINATION_BRANCH".to_string(),
+            "main".to_string(),
+        );
+        env.insert(
+            "BITBUCKET_COMMIT".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "BITBUCKET_GIT_HTTP_ORIGIN".to_string(),
+            "http://bitbucket.org/qltysh/qlty".to_string(),
+        );
+
+        let ci = Bitbucket {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(
+            &ci.pull_url(),
+            "http://bitbucket.org/qltysh/qlty/pull-requests/42"
+        );
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "BITBUCKET_PIPELINE_UUID".to_string(),
+            "{3d5b5e28-8f6e-4c3f-9d2e-5c1a7b2f9e61}".to_string(),
+        );
+        env.insert(
+            "BITBUCKET_STEP_UUID".to_string(),
+            "{a2c1e0f4-7b3d-4e8a-b6c5-1d9f0e2a3b4c}".to_string(),
+        );
+        env.insert("BITBUCKET_BUILD_NUMBER".to_string(), "33".to_string());
+        env.insert(
+            "BITBUCKET_GIT_HTTP_ORIGIN".to_string(),
+            "http://bitbucket.org/qltysh/qlty".to_string(),
+        );
+
+        let ci = Bitbucket {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "{3d5b5e28-8f6e-4c3f-9d2e-5c1a7b2f9e61}");
+        assert_eq!(&ci.job(), "{a2c1e0f4-7b3d-4e8a-b6c5-1d9f0e2a3b4c}");
+        assert_eq!(&ci.build_id(), "33");
+        assert_eq!(
+            &ci.build_url(),
+            "http://bitbucket.org/qltysh/qlty/pipelines/results/33"
+        );
+    }
+}
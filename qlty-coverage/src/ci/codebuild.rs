@@ -0,0 +1,269 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
+
+#[derive(Debug)]
+pub struct CodeBuild {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for CodeBuild {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CodeBuild {
+    // CODEBUILD_BUILD_ID is "<project name>:<build uuid>"
+    fn project_name(&self) -> String {
+        self.build_id()
+            .split_once(':')
+            .map(|(project, _)| project.to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl CI for CodeBuild {
+    fn detect(&self) -> bool {
+        !self
+            .env
+            .var("CODEBUILD_BUILD_ID")
+            .unwrap_or_default()
+            .is_empty()
+    }
+
+    fn ci_name(&self) -> String {
+        "CodeBuild".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        match self.env.var("AWS_REGION") {
+            Some(region) if !region.is_empty() => {
+                format!(
+                    "https://{}.console.aws.amazon.com/codesuite/codebuild",
+                    region
+                )
+            }
+            _ => "".to_string(),
+        }
+    }
+
+    fn branch(&self) -> String {
+        // Only webhook-triggered builds know the branch
+        let head_ref = self
+            .env
+            .var("CODEBUILD_WEBHOOK_HEAD_REF")
+            .unwrap_or_default();
+
+        head_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn workflow(&self) -> String {
+        self.project_name()
+    }
+
+    fn job(&self) -> String {
+        // CodeBuild doesn't have jobs within a build
+        "".to_string()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("CODEBUILD_BUILD_ID").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        if self.ci_url().is_empty() || self.project_name().is_empty() {
+            "".to_string()
+        } else {
+            format!(
+                "{}/projects/{}/build/{}",
+                self.ci_url(),
+                self.project_name(),
+                self.build_id().replace(':', "%3A")
+            )
+        }
+    }
+
+    fn pull_number(&self) -> String {
+        // Pull request builds have a source version or trigger of "pr/<number>"
+        ["CODEBUILD_SOURCE_VERSION", "CODEBUILD_WEBHOOK_TRIGGER"]
+            .iter()
+            .filter_map(|name| self.env.var(name))
+            .find_map(|value| value.strip_prefix("pr/").map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        // CodeBuild doesn't expose a repository name
+        "".to_string()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env
+            .var("CODEBUILD_SOURCE_REPO_URL")
+            .unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // CodeBuild doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env
+            .var("CODEBUILD_RESOLVED_SOURCE_VERSION")
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_BUILD_ID".to_string(),
+            "qlty:0b5c3a35-96a9-4c6e-a0b4-d3b1b0e5d2c1".to_string(),
+        );
+        env.insert("AWS_REGION".to_string(), "us-east-1".to_string());
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "CodeBuild");
+        assert_eq!(
+            &ci.ci_url(),
+            "https://us-east-1.console.aws.amazon.com/codesuite/codebuild"
+        );
+    }
+
+    #[test]
+    fn branch_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_WEBHOOK_HEAD_REF".to_string(),
+            "refs/heads/main".to_string(),
+        );
+        env.insert(
+            "CODEBUILD_WEBHOOK_TRIGGER".to_string(),
+            "branch/main".to_string(),
+        );
+        env.insert(
+            "CODEBUILD_SOURCE_VERSION".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "CODEBUILD_RESOLVED_SOURCE_VERSION".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "CODEBUILD_SOURCE_REPO_URL".to_string(),
+            "https://github.com/qltysh/qlty.git".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_url(), "https://github.com/qltysh/qlty.git");
+    }
+
+    #[test]
+    fn pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_WEBHOOK_HEAD_REF".to_string(),
+            "refs/heads/feature-branch-1".to_string(),
+        );
+        env.insert(
+            "CODEBUILD_WEBHOOK_BASE_REF".to_string(),
+            "refs/heads/main".to_string(),
+        );
+        env.insert("CODEBUILD_WEBHOOK_TRIGGER".to_string(), "pr/42".to_string());
+        env.insert("CODEBUILD_SOURCE_VERSION".to_string(), "pr/42".to_string());
+        env.insert(
+            "CODEBUILD_RESOLVED_SOURCE_VERSION".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn tag_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_WEBHOOK_HEAD_REF".to_string(),
+            "refs/tags/v1.0.0".to_string(),
+        );
+        env.insert(
+            "CODEBUILD_WEBHOOK_TRIGGER".to_string(),
+            "tag/v1.0.0".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "");
+        assert_eq!(&ci.pull_number(), "");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_BUILD_ID".to_string(),
+            "qlty:0b5c3a35-96a9-4c6e-a0b4-d3b1b0e5d2c1".to_string(),
+        );
+        env.insert("AWS_REGION".to_string(), "us-east-1".to_string());
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "qlty");
+        assert_eq!(&ci.job(), "");
+        assert_eq!(&ci.build_id(), "qlty:0b5c3a35-96a9-4c6e-a0b4-d3b1b0e5d2c1");
+        assert_eq!(
+            &ci.build_url(),
+            "https://us-east-1.console.aws.amazon.com/codesuite/codebuild/projects/qlty/build/qlty%3A0b5c3a35-96a9-4c6e-a0b4-d3b1b0e5d2c1"
+        );
+    }
+}
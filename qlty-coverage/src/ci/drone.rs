@@ -0,0 +1,224 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
+
+#[derive(Debug)]
+pub struct Drone {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Drone {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for Drone {
+    fn detect(&self) -> bool {
+        // Woodpecker sets DRONE_* variables for compatibility
+        self.env.var("DRONE").unwrap_or_default() == "true"
+            && self.env.var("CI").unwrap_or_default() != "woodpecker"
+    }
+
+    fn ci_name(&self) -> String {
+        "Drone".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        let proto = self.env.var("DRONE_SYSTEM_PROTO").unwrap_or_default();
+        let host = self.env.var("DRONE_SYSTEM_HOST").unwrap_or_default();
+
+        if proto.is_empty() || host.is_empty() {
+            "".to_string()
+        } else {
+            format!("{}://{}", proto, host)
+        }
+    }
+
+    fn branch(&self) -> String {
+        if !self.env.var("DRONE_TAG").unwrap_or_default().is_empty() {
+            return "".to_string();
+        }
+
+        // DRONE_BRANCH is the target branch for pull requests
+        self.env
+            .var("DRONE_SOURCE_BRANCH")
+            .filter(|branch| !branch.is_empty())
+            .or_else(|| self.env.var("DRONE_BRANCH"))
+            .unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("DRONE_STAGE_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("DRONE_STEP_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("DRONE_BUILD_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        self.env.var("DRONE_BUILD_LINK").unwrap_or_default()
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("DRONE_PULL_REQUEST").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("DRONE_REPO").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("DRONE_REPO_LINK").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // Drone doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("DRONE_COMMIT_SHA").unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Drone {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI".to_string(), "true".to_string());
+        env.insert("DRONE".to_string(), "true".to_string());
+        env.insert("DRONE_SYSTEM_PROTO".to_string(), "https".to_string());
+        env.insert(
+            "DRONE_SYSTEM_HOST".to_string(),
+            "drone.example.com".to_string(),
+        );
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "Drone");
+        assert_eq!(&ci.ci_url(), "https://drone.example.com");
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI".to_string(), "woodpecker".to_string());
+        env.insert("DRONE".to_string(), "true".to_string());
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(!ci.detect());
+    }
+
+    #[test]
+    fn branch_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_BRANCH".to_string(), "main".to_string());
+        env.insert("DRONE_SOURCE_BRANCH".to_string(), "main".to_string());
+        env.insert(
+            "DRONE_COMMIT_SHA".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert("DRONE_REPO".to_string(), "qltysh/qlty".to_string());
+        env.insert(
+            "DRONE_REPO_LINK".to_string(),
+            "https://github.com/qltysh/qlty".to_string(),
+        );
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_name(), "qltysh/qlty");
+        assert_eq!(&ci.repository_url(), "https://github.com/qltysh/qlty");
+    }
+
+    #[test]
+    fn pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_BRANCH".to_string(), "main".to_string());
+        env.insert(
+            "DRONE_SOURCE_BRANCH".to_string(),
+            "feature-branch-1".to_string(),
+        );
+        env.insert("DRONE_TARGET_BRANCH".to_string(), "main".to_string());
+        env.insert("DRONE_PULL_REQUEST".to_string(), "42".to_string());
+        env.insert(
+            "DRONE_COMMIT_SHA".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn tag_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_TAG".to_string(), "v1.0.0".to_string());
+        env.insert("DRONE_BRANCH".to_string(), "main".to_string());
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_STAGE_NAME".to_string(), "default".to_string());
+        env.insert("DRONE_STEP_NAME".to_string(), "test".to_string());
+        env.insert("DRONE_BUILD_NUMBER".to_string(), "22".to_string());
+        env.insert(
+            "DRONE_BUILD_LINK".to_string(),
+            "https://drone.example.com/qltysh/qlty/22".to_string(),
+        );
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "default");
+        assert_eq!(&ci.job(), "test");
+        assert_eq!(&ci.build_id(), "22");
+        assert_eq!(&ci.build_url(), "https://drone.example.com/qltysh/qlty/22");
+    }
+}
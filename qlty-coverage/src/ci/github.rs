@@ -110,6 +110,11 @@ impl CI for GitHub {
     fn commit_sha(&self) -> String {
         self.env.var("GITHUB_SHA").unwrap_or_default()
     }
+
+    fn merge_ref(&self) -> bool {
+        // Pull request workflows check out refs/pull/N/merge, and GITHUB_SHA is that merge commit
+        !self.pull_number().is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -188,6 +193,7 @@ mod test {
         assert_eq!(&ci.pull_number(), "");
         assert_eq!(&ci.pull_url(), "");
         assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert!(!ci.merge_ref());
     }
 
     #[test]
@@ -216,6 +222,7 @@ mod test {
         assert_eq!(&ci.pull_number(), "42");
         assert_eq!(&ci.pull_url(), "https://github.com/qltysh/qlty/pull/42");
         assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert!(ci.merge_ref());
     }
 
     #[test]
@@ -0,0 +1,235 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
+
+#[derive(Debug)]
+pub struct Jenkins {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Jenkins {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl Jenkins {
+    // Multibranch pipelines set CHANGE_*, the GitHub Pull Request Builder plugin sets ghprb*
+    fn first_var(&self, names: &[&str]) -> String {
+        names
+            .iter()
+            .filter_map(|name| self.env.var(name))
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+impl CI for Jenkins {
+    fn detect(&self) -> bool {
+        !self.env.var("JENKINS_URL").unwrap_or_default().is_empty()
+    }
+
+    fn ci_name(&self) -> String {
+        "Jenkins".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        self.env.var("JENKINS_URL").unwrap_or_default()
+    }
+
+    fn branch(&self) -> String {
+        let branch = self.first_var(&[
+            "CHANGE_BRANCH",
+            "ghprbSourceBranch",
+            "BRANCH_NAME",
+            "GIT_BRANCH",
+        ]);
+
+        // GIT_BRANCH is prefixed with the remote name, e.g. origin/main
+        branch
+            .strip_prefix("origin/")
+            .map(str::to_string)
+            .unwrap_or(branch)
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("JOB_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("STAGE_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        // BUILD_TAG is jenkins-${JOB_NAME}-${BUILD_NUMBER}, so unique across jobs
+        self.first_var(&["BUILD_TAG", "BUILD_NUMBER"])
+    }
+
+    fn build_url(&self) -> String {
+        self.env.var("BUILD_URL").unwrap_or_default()
+    }
+
+    fn pull_number(&self) -> String {
+        self.first_var(&["CHANGE_ID", "ghprbPullId"])
+    }
+
+    fn repository_name(&self) -> String {
+        // Jenkins doesn't expose a repository name
+        "".to_string()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("GIT_URL").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        self.first_var(&["CHANGE_URL", "ghprbPullLink"])
+    }
+
+    fn commit_sha(&self) -> String {
+        self.first_var(&["ghprbActualCommit", "GIT_COMMIT"])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Jenkins {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "JENKINS_URL".to_string(),
+            "https://jenkins.example.com/".to_string(),
+        );
+        let ci = Jenkins {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "Jenkins");
+        assert_eq!(&ci.ci_url(), "https://jenkins.example.com/");
+    }
+
+    #[test]
+    fn freestyle_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("GIT_BRANCH".to_string(), "origin/main".to_string());
+        env.insert(
+            "GIT_COMMIT".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "GIT_URL".to_string(),
+            "https://github.com/qltysh/qlty.git".to_string(),
+        );
+
+        let ci = Jenkins {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.pull_url(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_url(), "https://github.com/qltysh/qlty.git");
+    }
+
+    #[test]
+    fn multibranch_pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BRANCH_NAME".to_string(), "PR-42".to_string());
+        env.insert("CHANGE_ID".to_string(), "42".to_string());
+        env.insert("CHANGE_BRANCH".to_string(), "feature-branch-1".to_string());
+        env.insert(
+            "CHANGE_URL".to_string(),
+            "https://github.com/qltysh/qlty/pull/42".to_string(),
+        );
+        env.insert(
+            "GIT_COMMIT".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+
+        let ci = Jenkins {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.pull_url(), "https://github.com/qltysh/qlty/pull/42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn ghprb_pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("GIT_BRANCH".to_string(), "origin/pr/42/merge".to_string());
+        env.insert("ghprbPullId".to_string(), "42".to_string());
+        env.insert(
+            "ghprbSourceBranch".to_string(),
+            "feature-branch-1".to_string(),
+        );
+        env.insert(
+            "ghprbActualCommit".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert(
+            "GIT_COMMIT".to_string(),
+            "0dd7a2a4fd1ab2d3a25ee6d73d6a0e3a62d2a0c5".to_string(),
+        );
+
+        let ci = Jenkins {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("JOB_NAME".to_string(), "qlty/main".to_string());
+        env.insert("STAGE_NAME".to_string(), "Test".to_string());
+        env.insert("BUILD_NUMBER".to_string(), "17".to_string());
+        env.insert("BUILD_TAG".to_string(), "jenkins-qlty-main-17".to_string());
+        env.insert(
+            "BUILD_URL".to_string(),
+            "https://jenkins.example.com/job/qlty/job/main/17/".to_string(),
+        );
+
+        let ci = Jenkins {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "qlty/main");
+        assert_eq!(&ci.job(), "Test");
+        assert_eq!(&ci.build_id(), "jenkins-qlty-main-17");
+        assert_eq!(
+            &ci.build_url(),
+            "https://jenkins.example.com/job/qlty/job/main/17/"
+        );
+    }
+}
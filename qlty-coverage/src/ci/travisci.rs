@@ -1 +1,224 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
 
+#[derive(Debug)]
+pub struct TravisCI {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for TravisCI {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl TravisCI {
+    fn is_pull_request(&self) -> bool {
+        let pull_request = self.env.var("TRAVIS_PULL_REQUEST").unwrap_or_default();
+        !pull_request.is_empty() && pull_request != "false"
+    }
+}
+
+impl CI for TravisCI {
+    fn detect(&self) -> bool {
+        self.env.var("TRAVIS").unwrap_or_default() == "true"
+    }
+
+    fn ci_name(&self) -> String {
+        "Travis CI".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        match self.env.var("TRAVIS_APP_HOST") {
+            Some(host) if !host.is_empty() => format!("https://{}", host),
+            _ => "".to_string(),
+        }
+    }
+
+    fn branch(&self) -> String {
+        if !self.env.var("TRAVIS_TAG").unwrap_or_default().is_empty() {
+            "".to_string()
+        } else if self.is_pull_request() {
+            self.env
+                .var("TRAVIS_PULL_REQUEST_BRANCH")
+                .unwrap_or_default()
+        } else {
+            self.env.var("TRAVIS_BRANCH").unwrap_or_default()
+        }
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("TRAVIS_BUILD_STAGE_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("TRAVIS_JOB_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("TRAVIS_BUILD_ID").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        self.env.var("TRAVIS_BUILD_WEB_URL").unwrap_or_default()
+    }
+
+    fn pull_number(&self) -> String {
+        if self.is_pull_request() {
+            self.env.var("TRAVIS_PULL_REQUEST").unwrap_or_default()
+        } else {
+            "".to_string()
+        }
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("TRAVIS_REPO_SLUG").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        // Travis CI doesn't expose a repository URL
+        "".to_string()
+    }
+
+    fn pull_url(&self) -> String {
+        // Travis CI doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        // TRAVIS_COMMIT is the merge commit for pull requests
+        if self.is_pull_request() {
+            self.env.var("TRAVIS_PULL_REQUEST_SHA").unwrap_or_default()
+        } else {
+            self.env.var("TRAVIS_COMMIT").unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = TravisCI {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TRAVIS".to_string(), "true".to_string());
+        env.insert("TRAVIS_APP_HOST".to_string(), "travis-ci.com".to_string());
+        let ci = TravisCI {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "Travis CI");
+        assert_eq!(&ci.ci_url(), "https://travis-ci.com");
+    }
+
+    #[test]
+    fn branch_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TRAVIS_BRANCH".to_string(), "main".to_string());
+        env.insert("TRAVIS_PULL_REQUEST".to_string(), "false".to_string());
+        env.insert("TRAVIS_PULL_REQUEST_BRANCH".to_string(), "".to_string());
+        env.insert(
+            "TRAVIS_COMMIT".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert("TRAVIS_REPO_SLUG".to_string(), "qltysh/qlty".to_string());
+
+        let ci = TravisCI {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_name(), "qltysh/qlty");
+    }
+
+    #[test]
+    fn pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TRAVIS_BRANCH".to_string(), "main".to_string());
+        env.insert("TRAVIS_PULL_REQUEST".to_string(), "42".to_string());
+        env.insert(
+            "TRAVIS_PULL_REQUEST_BRANCH".to_string(),
+            "feature-branch-1".to_string(),
+        );
+        env.insert(
+            "TRAVIS_COMMIT".to_string(),
+            "0dd7a2a4fd1ab2d3a25ee6d73d6a0e3a62d2a0c5".to_string(),
+        );
+        env.insert(
+            "TRAVIS_PULL_REQUEST_SHA".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+
+        let ci = TravisCI {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn tag_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TRAVIS_BRANCH".to_string(), "v1.0.0".to_string());
+        env.insert("TRAVIS_TAG".to_string(), "v1.0.0".to_string());
+        env.insert("TRAVIS_PULL_REQUEST".to_string(), "false".to_string());
+
+        let ci = TravisCI {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TRAVIS_BUILD_STAGE_NAME".to_string(), "Test".to_string());
+        env.insert("TRAVIS_JOB_NAME".to_string(), "unit".to_string());
+        env.insert("TRAVIS_BUILD_ID".to_string(), "1234".to_string());
+        env.insert(
+            "TRAVIS_BUILD_WEB_URL".to_string(),
+            "https://app.travis-ci.com/qltysh/qlty/builds/1234".to_string(),
+        );
+
+        let ci = TravisCI {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "Test");
+        assert_eq!(&ci.job(), "unit");
+        assert_eq!(&ci.build_id(), "1234");
+        assert_eq!(
+            &ci.build_url(),
+            "https://app.travis-ci.com/qltysh/qlty/builds/1234"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    ci::CI,
+    env::{EnvSource, SystemEnv},
+};
+
+#[derive(Debug)]
+pub struct Woodpecker {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Woodpecker {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for Woodpecker {
+    fn detect(&self) -> bool {
+        self.env.var("CI").unwrap_or_default() == "woodpecker"
+    }
+
+    fn ci_name(&self) -> String {
+        "Woodpecker".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        self.env.var("CI_SYSTEM_URL").unwrap_or_default()
+    }
+
+    fn branch(&self) -> String {
+        if !self.env.var("CI_COMMIT_TAG").unwrap_or_default().is_empty() {
+            return "".to_string();
+        }
+
+        // CI_COMMIT_BRANCH is the target branch for pull requests
+        self.env
+            .var("CI_COMMIT_SOURCE_BRANCH")
+            .filter(|branch| !branch.is_empty())
+            .or_else(|| self.env.var("CI_COMMIT_BRANCH"))
+            .unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("CI_WORKFLOW_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("CI_STEP_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("CI_PIPELINE_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        self.env.var("CI_PIPELINE_URL").unwrap_or_default()
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("CI_COMMIT_PULL_REQUEST").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("CI_REPO").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("CI_REPO_URL").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // Woodpecker doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("CI_COMMIT_SHA").unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert!(!ci.detect());
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI".to_string(), "woodpecker".to_string());
+        env.insert(
+            "CI_SYSTEM_URL".to_string(),
+            "https://ci.example.com".to_string(),
+        );
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert!(ci.detect());
+        assert_eq!(&ci.ci_name(), "Woodpecker");
+        assert_eq!(&ci.ci_url(), "https://ci.example.com");
+    }
+
+    #[test]
+    fn branch_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_BRANCH".to_string(), "main".to_string());
+        env.insert(
+            "CI_COMMIT_SHA".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+        env.insert("CI_REPO".to_string(), "qltysh/qlty".to_string());
+        env.insert(
+            "CI_REPO_URL".to_string(),
+            "https://codeberg.org/qltysh/qlty".to_string(),
+        );
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+        assert_eq!(&ci.repository_name(), "qltysh/qlty");
+        assert_eq!(&ci.repository_url(), "https://codeberg.org/qltysh/qlty");
+    }
+
+    #[test]
+    fn pull_request_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_BRANCH".to_string(), "main".to_string());
+        env.insert(
+            "CI_COMMIT_SOURCE_BRANCH".to_string(),
+            "feature-branch-1".to_string(),
+        );
+        env.insert("CI_COMMIT_TARGET_BRANCH".to_string(), "main".to_string());
+        env.insert("CI_COMMIT_PULL_REQUEST".to_string(), "42".to_string());
+        env.insert(
+            "CI_COMMIT_SHA".to_string(),
+            "77948d72a8b5ea21bb335e8e674bad99413da7a2".to_string(),
+        );
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature-branch-1");
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(&ci.commit_sha(), "77948d72a8b5ea21bb335e8e674bad99413da7a2");
+    }
+
+    #[test]
+    fn tag_build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_TAG".to_string(), "v1.0.0".to_string());
+        env.insert("CI_COMMIT_BRANCH".to_string(), "main".to_string());
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_WORKFLOW_NAME".to_string(), "test".to_string());
+        env.insert("CI_STEP_NAME".to_string(), "unit".to_string());
+        env.insert("CI_PIPELINE_NUMBER".to_string(), "8".to_string());
+        env.insert(
+            "CI_PIPELINE_URL".to_string(),
+            "https://ci.example.com/repos/7/pipeline/8".to_string(),
+        );
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "test");
+        assert_eq!(&ci.job(), "unit");
+        assert_eq!(&ci.build_id(), "8");
+        assert_eq!(&ci.build_url(), "https://ci.example.com/repos/7/pipeline/8");
+    }
+}
@@ -4,9 +4,14 @@ use anyhow::{bail, Result};
 use core::str;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
+use std::io::Read;
 use std::path::Path;
 use std::str::FromStr;
 
+mod sniff;
+
+const SNIFF_BYTES: usize = 8192;
+
 #[derive(clap::ValueEnum, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum Formats {
     Simplecov,
@@ -16,6 +21,10 @@ pub enum Formats {
     Lcov,
     Jacoco,
     Qlty,
+    Istanbul,
+    Coveragepy,
+    Opencover,
+    Llvm,
 }
 
 impl std::fmt::Display for Formats {
@@ -28,6 +37,10 @@ impl std::fmt::Display for Formats {
             Formats::Lcov => write!(f, "lcov"),
             Formats::Jacoco => write!(f, "jacoco"),
             Formats::Qlty => write!(f, "qlty"),
+            Formats::Istanbul => write!(f, "istanbul"),
+            Formats::Coveragepy => write!(f, "coveragepy"),
+            Formats::Opencover => write!(f, "opencover"),
+            Formats::Llvm => write!(f, "llvm"),
         }
     }
 }
@@ -36,6 +49,35 @@ impl TryFrom<&Path> for Formats {
     type Error = anyhow::Error;
 
     fn try_from(path: &Path) -> Result<Self> {
+        let candidates = sniff::candidates(&read_head(path));
+
+        match candidates.as_slice() {
+            [] => Self::from_extension(path),
+            [format] => Ok(*format),
+            _ => {
+                // Fall back to a format named in the path, e.g. clover.xml
+                let path_str = path.to_string_lossy();
+                let named: Vec<_> = candidates
+                    .iter()
+                    .filter(|format| path_str.contains(&format.to_string()))
+                    .collect();
+
+                match named.as_slice() {
+                    [format] => Ok(**format),
+                    _ => bail!(
+                        "Could not determine the format of coverage report {}, it could be any of: {}\nSpecify the format with --format=FORMAT",
+                        path.display(),
+                        candidates.iter().map(Formats::to_string).collect::<Vec<_>>().join(", ")
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl Formats {
+    // Used when the contents are unreadable or unrecognized
+    fn from_extension(path: &Path) -> Result<Self> {
         match path.extension().and_then(std::ffi::OsStr::to_str) {
             Some("info") | Some("lcov") => Ok(Formats::Lcov),
             Some("json") => Ok(Formats::Simplecov),
@@ -51,15 +93,25 @@ impl TryFrom<&Path> for Formats {
                     Ok(Formats::Cobertura)
                 }
             }
-            Some(other) => bail!("Unknown file extension for coverage report: {}\nSpecify the format with --report-format=FORMAT", other),
+            Some(other) => bail!("Unknown file extension for coverage report: {}\nSpecify the format with --format=FORMAT", other),
             None => bail!(
-                "Could not determine a report format by file extension: {}\nSpecify the format with --report-format=FORMAT",
+                "Could not determine a report format by file extension: {}\nSpecify the format with --format=FORMAT",
                 path.display()
             ),
         }
     }
 }
 
+fn read_head(path: &Path) -> String {
+    let mut buffer = vec![0; SNIFF_BYTES];
+
+    let bytes_read = std::fs::File::open(path)
+        .and_then(|mut file| file.read(&mut buffer))
+        .unwrap_or(0);
+
+    String::from_utf8_lossy(&buffer[..bytes_read]).into_owned()
+}
+
 impl FromStr for Formats {
     type Err = anyhow::Error;
 
@@ -72,6 +124,10 @@ impl FromStr for Formats {
             "lcov" => Ok(Formats::Lcov),
             "jacoco" => Ok(Formats::Jacoco),
             "qlty" => Ok(Formats::Qlty),
+            "istanbul" => Ok(Formats::Istanbul),
+            "coveragepy" => Ok(Formats::Coveragepy),
+            "opencover" => Ok(Formats::Opencover),
+            "llvm" => Ok(Formats::Llvm),
             _ => bail!("Unsupported coverage report format: {}", s),
         }
     }
@@ -86,5 +142,9 @@ pub fn parser_for(&format: &Formats) -> Box<dyn Parser> {
         Formats::Lcov => Box::new(parser::Lcov::new()),
         Formats::Jacoco => Box::new(parser::Jacoco::new()),
         Formats::Qlty => Box::new(parser::Qlty::new()),
+        Formats::Istanbul => Box::new(parser::Istanbul::new()),
+        Formats::Coveragepy => Box::new(parser::Coveragepy::new()),
+        Formats::Opencover => Box::new(parser::OpenCover::new()),
+        Formats::Llvm => Box::new(parser::Llvm::new()),
     }
 }
@@ -0,0 +1,257 @@
+use crate::formats::Formats;
+
+/// Returns the formats the start of a coverage report could belong to, based on
+/// its root XML element, its top-level JSON keys or its first lines of text.
+pub fn candidates(head: &str) -> Vec<Formats> {
+    let trimmed = head.trim_start_matches('\u{feff}').trim_start();
+
+    if trimmed.starts_with('<') {
+        xml_candidates(trimmed)
+    } else if trimmed.starts_with('{') {
+        json_candidates(trimmed)
+    } else {
+        text_candidates(trimmed)
+    }
+}
+
+fn xml_candidates(head: &str) -> Vec<Formats> {
+    let Some(root) = root_element(head) else {
+        return vec![];
+    };
+
+    let name = root
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default();
+
+    match name {
+        "CoverageSession" => vec![Formats::Opencover],
+        "report" => vec![Formats::Jacoco],
+        "coverage" => {
+            let clover = root.contains("clover=") || head.contains("<project");
+            let cobertura = root.contains("line-rate=")
+                || head.contains("<packages")
+                || head.contains("<sources");
+
+            match (clover, cobertura) {
+                (true, false) => vec![Formats::Clover],
+                (false, true) => vec![Formats::Cobertura],
+                _ => vec![Formats::Clover, Formats::Cobertura],
+            }
+        }
+        _ => vec![],
+    }
+}
+
+// The contents of the first tag which is not a declaration, comment or DOCTYPE
+fn root_element(head: &str) -> Option<&str> {
+    let mut rest = head;
+
+    loop {
+        rest = rest.trim_start();
+
+        if rest.starts_with("<?") {
+            rest = &rest[rest.find("?>")? + 2..];
+        } else if rest.starts_with("<!--") {
+            rest = &rest[rest.find("-->")? + 3..];
+        } else if rest.starts_with("<!") {
+            rest = &rest[rest.find('>')? + 1..];
+        } else if let Some(tag) = rest.strip_prefix('<') {
+            return Some(&tag[..tag.find('>').unwrap_or(tag.len())]);
+        } else {
+            return None;
+        }
+    }
+}
+
+fn json_candidates(head: &str) -> Vec<Formats> {
+    let keys = JsonKeys::scan(head);
+    let mut formats = vec![];
+
+    if keys.top_level("data") {
+        formats.push(Formats::Llvm);
+    }
+
+    if keys.top_level("path") && keys.top_level("hits") {
+        formats.push(Formats::Qlty);
+    }
+
+    if keys.nested("statementMap") {
+        formats.push(Formats::Istanbul);
+    }
+
+    if keys.top_level("files")
+        && keys.top_level("meta")
+        && (keys.nested("show_contexts") || keys.nested("branch_coverage"))
+    {
+        formats.push(Formats::Coveragepy);
+    }
+
+    if keys.nested("simplecov_version")
+        || keys.top_level("command_name")
+        || keys.top_level("coverage")
+        || (keys.nested("coverage") && keys.nested("timestamp"))
+    {
+        formats.push(Formats::Simplecov);
+    }
+
+    formats
+}
+
+fn text_candidates(head: &str) -> Vec<Formats> {
+    let mut lines = head.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    match lines.next() {
+        Some(line) if line.starts_with("mode:") => vec![Formats::Coverprofile],
+        Some(line) if line.starts_with("TN:") || line.starts_with("SF:") => vec![Formats::Lcov],
+        _ => vec![],
+    }
+}
+
+/// Object keys found one and two levels deep in a possibly truncated JSON document.
+#[derive(Debug, Default)]
+struct JsonKeys {
+    top_level: Vec<String>,
+    nested: Vec<String>,
+}
+
+impl JsonKeys {
+    fn scan(text: &str) -> Self {
+        let mut keys = Self::default();
+        // One entry per open container, true for objects
+        let mut stack: Vec<bool> = vec![];
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => stack.push(true),
+                '[' => stack.push(false),
+                '}' | ']' => {
+                    stack.pop();
+
+                    if stack.is_empty() {
+                        break;
+                    }
+                }
+                '"' => {
+                    let mut string = String::new();
+
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '\\' => string.extend(chars.next()),
+                            '"' => break,
+                            _ => string.push(c),
+                        }
+                    }
+
+                    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                        chars.next();
+                    }
+
+                    let is_key = chars.peek() == Some(&':') && stack.last() == Some(&true);
+
+                    if is_key && stack.len() == 1 {
+                        keys.top_level.push(string);
+                    } else if is_key && stack.len() == 2 {
+                        keys.nested.push(string);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        keys
+    }
+
+    fn top_level(&self, key: &str) -> bool {
+        self.top_level.iter().any(|k| k == key)
+    }
+
+    fn nested(&self, key: &str) -> bool {
+        self.nested.iter().any(|k| k == key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml_candidates() {
+        assert_eq!(
+            candidates(
+                r#"<?xml version="1.0"?>
+                <!DOCTYPE report PUBLIC "-//JACOCO//DTD Report 1.0//EN" "report.dtd">
+                <report name="basic">"#
+            ),
+            vec![Formats::Jacoco]
+        );
+        assert_eq!(
+            candidates(
+                r#"<?xml version="1.0"?>
+                <!--DOCTYPE coverage SYSTEM "coverage-03.dtd"-->
+                <coverage line-rate="0.9" branch-rate="0.75">"#
+            ),
+            vec![Formats::Cobertura]
+        );
+        assert_eq!(
+            candidates(r#"<coverage generated="1710280183"><project timestamp="1710280183">"#),
+            vec![Formats::Clover]
+        );
+        assert_eq!(
+            candidates(r#"<CoverageSession xmlns:xsd="http://www.w3.org/2001/XMLSchema">"#),
+            vec![Formats::Opencover]
+        );
+        assert_eq!(
+            candidates("<coverage></coverage>"),
+            vec![Formats::Clover, Formats::Cobertura]
+        );
+        assert_eq!(candidates("<html>"), vec![]);
+    }
+
+    #[test]
+    fn test_json_candidates() {
+        assert_eq!(
+            candidates(r#"{"data":[{"files":[{"filename":"main.rs"}]}],"type":"llvm"#),
+            vec![Formats::Llvm]
+        );
+        assert_eq!(
+            candidates(r#"{"src/a.js": {"path": "src/a.js", "statementMap": {"0": {"#),
+            vec![Formats::Istanbul]
+        );
+        assert_eq!(
+            candidates(
+                r#"{"meta": {"format": 3, "branch_coverage": true, "show_contexts": false}, "files": {"#
+            ),
+            vec![Formats::Coveragepy]
+        );
+        assert_eq!(
+            candidates(r#"{"meta": {"simplecov_version": "0.21.2"}, "coverage": {"#),
+            vec![Formats::Simplecov]
+        );
+        assert_eq!(
+            candidates(r#"{"RSpec": {"coverage": {"a.rb": {"lines": [1]}}, "timestamp": 1}}"#),
+            vec![Formats::Simplecov]
+        );
+        assert_eq!(
+            candidates(r#"{"timestamp": 1738692160, "command_name": "RSpec", "files": ["#),
+            vec![Formats::Simplecov]
+        );
+        assert_eq!(
+            candidates("{\"path\":\"a.rb\",\"hits\":[\"1\"]}\n{\"path\":\"b.rb\"}"),
+            vec![Formats::Qlty]
+        );
+        assert_eq!(candidates(r#"{"\"data\"": "a: {\"meta\": 1}"}"#), vec![]);
+    }
+
+    #[test]
+    fn test_text_candidates() {
+        assert_eq!(candidates("TN:\nSF:lib/a.js\n"), vec![Formats::Lcov]);
+        assert_eq!(candidates("SF:lib/a.js\nDA:1,1\n"), vec![Formats::Lcov]);
+        assert_eq!(
+            candidates("mode: set\ngithub.com/a/b.go:1.1,2.2 1 1\n"),
+            vec![Formats::Coverprofile]
+        );
+        assert_eq!(candidates(""), vec![]);
+    }
+}
@@ -1,24 +1,61 @@
 use anyhow::{Context, Result};
-use git2::Repository;
+use git2::{Commit, Oid, Repository};
+use tracing::warn;
 
 #[derive(Debug, Clone)]
 pub struct CommitMetadata {
+    pub commit_sha: String,
     pub commit_time: git2::Time,
     pub author_time: git2::Time,
     pub committer_name: String,
     pub committer_email: String,
     pub author_name: String,
     pub author_email: String,
+    pub commit_headline: String,
     pub commit_message: String,
 }
 
-pub fn retrieve_commit_metadata() -> Result<CommitMetadata> {
+/// Reads commit metadata from the local repository for `commit_sha`, or for HEAD if it is
+/// empty. Returns `None` if the commit is not present locally (e.g. a shallow clone), rather
+/// than describing a different commit.
+///
+/// When `merge_ref` is set the commit is a synthetic merge of a pull request
+/// (e.g. GitHub's refs/pull/N/merge), so the pull request's head commit is described instead.
+pub fn retrieve_commit_metadata(
+    commit_sha: &str,
+    merge_ref: bool,
+) -> Result<Option<CommitMetadata>> {
     let repo = Repository::discover(".")
         .with_context(|| "Error opening git repository for retrieving commit metadata")?;
 
-    let head = repo.head()?;
-    let oid = head.peel_to_commit()?.id();
-    let commit = repo.find_commit(oid)?;
+    commit_metadata(&repo, commit_sha, merge_ref)
+}
+
+fn commit_metadata(
+    repo: &Repository,
+    commit_sha: &str,
+    merge_ref: bool,
+) -> Result<Option<CommitMetadata>> {
+    let Some(commit) = find_commit(repo, commit_sha)? else {
+        return Ok(None);
+    };
+
+    let commit = if merge_ref && commit.parent_count() == 2 {
+        // The first parent is the base branch, the second is the pull request head
+        match commit.parent(1) {
+            Ok(parent) => parent,
+            Err(err) => {
+                warn!(
+                    "Pull request head of {} not found in the local git repository: {}",
+                    commit.id(),
+                    err
+                );
+                return Ok(None);
+            }
+        }
+    } else {
+        commit
+    };
 
     let commit_time = commit.time();
 
@@ -31,15 +68,131 @@ pub fn retrieve_commit_metadata() -> Result<CommitMetadata> {
     let author_email = author.email().unwrap_or("Unknown").to_string();
     let author_time = author.when();
 
+    let commit_headline = commit.summary().unwrap_or("").to_string();
     let commit_message = commit.message().unwrap_or("").to_string();
 
-    Ok(CommitMetadata {
+    Ok(Some(CommitMetadata {
+        commit_sha: commit.id().to_string(),
         commit_time,
         author_time,
         committer_name,
         committer_email,
         author_name,
         author_email,
+        commit_headline,
         commit_message,
-    })
+    }))
+}
+
+fn find_commit<'repo>(repo: &'repo Repository, commit_sha: &str) -> Result<Option<Commit<'repo>>> {
+    if commit_sha.is_empty() {
+        return Ok(Some(repo.head()?.peel_to_commit()?));
+    }
+
+    match Oid::from_str(commit_sha).and_then(|oid| repo.find_commit(oid)) {
+        Ok(commit) => Ok(Some(commit)),
+        Err(err) => {
+            warn!(
+                "Commit {} not found in the local git repository, omitting its metadata: {}",
+                commit_sha, err
+            );
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Signature, Time};
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, name: &str, time: i64, message: &str, parents: &[Oid]) -> Oid {
+        let signature =
+            Signature::new(name, &format!("{}@example.com", name), &Time::new(time, 0)).unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parents = parents
+            .iter()
+            .map(|oid| repo.find_commit(*oid).unwrap())
+            .collect::<Vec<_>>();
+
+        repo.commit(
+            None,
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents.iter().collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_commit_metadata_head() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let oid = commit(&repo, "alice", 1_700_000_000, "Add parser\n\nDetails", &[]);
+        repo.reference("refs/heads/main", oid, true, "").unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let metadata = commit_metadata(&repo, "", false).unwrap().unwrap();
+        assert_eq!(metadata.commit_sha, oid.to_string());
+        assert_eq!(metadata.author_name, "alice");
+        assert_eq!(metadata.author_email, "alice@example.com");
+        assert_eq!(metadata.committer_name, "alice");
+        assert_eq!(metadata.commit_headline, "Add parser");
+        assert_eq!(metadata.commit_message, "Add parser\n\nDetails");
+        assert_eq!(metadata.commit_time.seconds(), 1_700_000_000);
+        assert_eq!(metadata.author_time.seconds(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_commit_metadata_sha() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let first = commit(&repo, "alice", 1_700_000_000, "First", &[]);
+        let second = commit(&repo, "bob", 1_700_000_100, "Second", &[first]);
+        repo.reference("refs/heads/main", second, true, "").unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let metadata = commit_metadata(&repo, &first.to_string(), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.commit_sha, first.to_string());
+        assert_eq!(metadata.author_name, "alice");
+        assert_eq!(metadata.commit_headline, "First");
+
+        let missing = "0000000000000000000000000000000000000001";
+        assert!(commit_metadata(&repo, missing, false).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_commit_metadata_merge_ref() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let base = commit(&repo, "alice", 1_700_000_000, "Base", &[]);
+        let head = commit(&repo, "bob", 1_700_000_100, "Feature", &[base]);
+        let merge = commit(
+            &repo,
+            "GitHub",
+            1_700_000_200,
+            "Merge feature into main",
+            &[base, head],
+        );
+
+        let metadata = commit_metadata(&repo, &merge.to_string(), true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.commit_sha, head.to_string());
+        assert_eq!(metadata.author_name, "bob");
+        assert_eq!(metadata.commit_headline, "Feature");
+        assert_eq!(metadata.commit_time.seconds(), 1_700_000_100);
+
+        let metadata = commit_metadata(&repo, &merge.to_string(), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.commit_sha, merge.to_string());
+        assert_eq!(metadata.author_name, "GitHub");
+    }
 }
@@ -3,14 +3,18 @@ mod env;
 pub mod export;
 pub mod formats;
 pub mod git;
+pub mod merge;
 pub mod parser;
+pub mod patch;
 pub mod print;
 pub mod publish;
+pub mod threshold;
 pub mod token;
 pub mod transform;
 mod transformer;
 mod utils;
 pub mod validate;
+pub mod writer;
 
 #[macro_use]
 mod macros;
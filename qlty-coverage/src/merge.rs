@@ -0,0 +1,156 @@
+use crate::transformer::ComputeSummary;
+use crate::Transformer;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
+use std::collections::HashMap;
+
+/// Combines file coverages for the same path into a single record, summing
+/// hits line by line. Paths keep the order in which they first appear.
+pub fn merge(file_coverages: Vec<FileCoverage>) -> Vec<FileCoverage> {
+    let mut merged: Vec<FileCoverage> = vec![];
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for file_coverage in file_coverages {
+        if let Some(position) = positions.get(&file_coverage.path) {
+            merge_into(&mut merged[*position], file_coverage);
+        } else {
+            positions.insert(file_coverage.path.clone(), merged.len());
+            merged.push(file_coverage);
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|file_coverage| {
+            if file_coverage.summary.is_some() {
+                ComputeSummary::new().transform(file_coverage).unwrap()
+            } else {
+                file_coverage
+            }
+        })
+        .collect()
+}
+
+/// A line executed in either report is covered, and a line executable in
+/// either report is at least uncovered. Otherwise the line stays omitted.
+pub fn combine_hits(current: i64, other: i64) -> i64 {
+    if current >= 0 || other >= 0 {
+        current.max(0) + other.max(0)
+    } else {
+        current.max(other)
+    }
+}
+
+fn merge_into(target: &mut FileCoverage, other: FileCoverage) {
+    if target.hits.len() < other.hits.len() {
+        target.hits.resize(other.hits.len(), -1);
+    }
+
+    for (index, hit) in other.hits.into_iter().enumerate() {
+        target.hits[index] = combine_hits(target.hits[index], hit);
+    }
+
+    for branch in other.branches {
+        merge_branch(&mut target.branches, branch);
+    }
+
+    for function in other.functions {
+        merge_function(&mut target.functions, function);
+    }
+}
+
+fn merge_branch(branches: &mut Vec<BranchCoverage>, other: BranchCoverage) {
+    match branches.iter_mut().find(|branch| {
+        branch.line == other.line && branch.block == other.block && branch.branch == other.branch
+    }) {
+        Some(branch) => branch.hits += other.hits,
+        None => branches.push(other),
+    }
+}
+
+fn merge_function(functions: &mut Vec<FunctionCoverage>, other: FunctionCoverage) {
+    match functions
+        .iter_mut()
+        .find(|function| function.name == other.name && function.line == other.line)
+    {
+        Some(function) => function.hits += other.hits,
+        None => functions.push(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_hits() {
+        assert_eq!(combine_hits(1, 2), 3);
+        assert_eq!(combine_hits(0, 2), 2);
+        assert_eq!(combine_hits(-1, 0), 0);
+        assert_eq!(combine_hits(-1, 3), 3);
+        assert_eq!(combine_hits(-1, -1), -1);
+        assert_eq!(combine_hits(-2, -1), -1);
+        assert_eq!(combine_hits(-2, -2), -2);
+    }
+
+    #[test]
+    fn test_merge() {
+        let file_coverages = vec![
+            FileCoverage {
+                path: "src/lib.rs".to_string(),
+                hits: vec![1, 0, -1],
+                branches: vec![BranchCoverage {
+                    line: 2,
+                    block: 0,
+                    branch: 0,
+                    hits: 0,
+                }],
+                functions: vec![FunctionCoverage {
+                    name: "main".to_string(),
+                    line: 1,
+                    hits: 1,
+                }],
+                ..Default::default()
+            },
+            FileCoverage {
+                path: "src/main.rs".to_string(),
+                hits: vec![1],
+                ..Default::default()
+            },
+            FileCoverage {
+                path: "src/lib.rs".to_string(),
+                hits: vec![2, 1, -1, 0],
+                branches: vec![
+                    BranchCoverage {
+                        line: 2,
+                        block: 0,
+                        branch: 0,
+                        hits: 4,
+                    },
+                    BranchCoverage {
+                        line: 2,
+                        block: 0,
+                        branch: 1,
+                        hits: 0,
+                    },
+                ],
+                functions: vec![FunctionCoverage {
+                    name: "main".to_string(),
+                    line: 1,
+                    hits: 2,
+                }],
+                ..Default::default()
+            },
+        ];
+
+        let merged = merge(file_coverages);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].path, "src/lib.rs");
+        assert_eq!(merged[0].hits, vec![3, 1, -1, 0]);
+        assert_eq!(merged[0].branches.len(), 2);
+        assert_eq!(merged[0].branches[0].hits, 4);
+        assert_eq!(merged[0].functions.len(), 1);
+        assert_eq!(merged[0].functions[0].hits, 3);
+        assert_eq!(merged[1].path, "src/main.rs");
+    }
+}
@@ -4,17 +4,25 @@ use std::path::Path;
 
 mod clover;
 mod cobertura;
+mod coveragepy;
 mod coverprofile;
+mod istanbul;
 mod jacoco;
 mod lcov;
+mod llvm;
+mod opencover;
 mod qlty;
 mod simplecov;
 
 pub use clover::Clover;
 pub use cobertura::Cobertura;
+pub use coveragepy::Coveragepy;
 pub use coverprofile::Coverprofile;
+pub use istanbul::Istanbul;
 pub use jacoco::Jacoco;
 pub use lcov::Lcov;
+pub use llvm::Llvm;
+pub use opencover::OpenCover;
 pub use qlty::Qlty;
 pub use simplecov::Simplecov;
 
@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::{Context, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
 use std::io::BufReader;
 use std::str::FromStr;
 use xml::reader::{EventReader, XmlEvent};
@@ -11,7 +11,9 @@ pub struct Clover {}
 struct CloverFile {
     name: String,
     path: Option<String>,
-    lines: Vec<(i64, i64)>, // (line_num, count)
+    lines: Vec<(i64, i64)>,             // (line_num, count)
+    conditionals: Vec<(i64, i64, i64)>, // (line_num, truecount, falsecount)
+    methods: Vec<(i64, String, i64)>,   // (line_num, name, count)
     loc: Option<i64>,
 }
 
@@ -60,6 +62,8 @@ impl Clover {
                                     name: file_name,
                                     path: file_path,
                                     lines: Vec::new(),
+                                    conditionals: Vec::new(),
+                                    methods: Vec::new(),
                                     loc: None,
                                 });
                             }
@@ -68,6 +72,10 @@ impl Clover {
                             if let Some(ref mut file) = current_file {
                                 let mut num = 0;
                                 let mut count = 0;
+                                let mut line_type = String::new();
+                                let mut method_name = None;
+                                let mut truecount = 0;
+                                let mut falsecount = 0;
 
                                 for attr in attributes {
                                     match attr.name.local_name.as_str() {
@@ -85,12 +93,35 @@ impl Clover {
                                                 format!("Failed to parse 'count' attribute value: {}", attr.value)
                                             })?;
                                         }
+                                        "type" => line_type = attr.value,
+                                        "name" => method_name = Some(attr.value),
+                                        "signature" => {
+                                            method_name.get_or_insert(attr.value);
+                                        }
+                                        "truecount" => {
+                                            truecount = i64::from_str(&attr.value).unwrap_or(0)
+                                        }
+                                        "falsecount" => {
+                                            falsecount = i64::from_str(&attr.value).unwrap_or(0)
+                                        }
                                         _ => {}
                                     }
                                 }
 
                                 if num > 0 {
                                     file.lines.push((num, count));
+
+                                    match line_type.as_str() {
+                                        "cond" => {
+                                            file.conditionals.push((num, truecount, falsecount))
+                                        }
+                                        "method" => file.methods.push((
+                                            num,
+                                            method_name.unwrap_or_default(),
+                                            count,
+                                        )),
+                                        _ => {}
+                                    }
                                 }
                             }
                         }
@@ -164,9 +195,38 @@ impl Parser for Clover {
                 }
             }
 
+            // Each conditional has a true and a false branch, taken truecount and falsecount times
+            let branches =
+                file.conditionals
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(block, (num, truecount, falsecount))| {
+                        [*truecount, *falsecount].into_iter().enumerate().map(
+                            move |(branch, hits)| BranchCoverage {
+                                line: *num as u32,
+                                block: block as u32,
+                                branch: branch as u32,
+                                hits,
+                            },
+                        )
+                    })
+                    .collect();
+
+            let functions = file
+                .methods
+                .into_iter()
+                .map(|(num, name, count)| FunctionCoverage {
+                    name,
+                    line: num as u32,
+                    hits: count,
+                })
+                .collect();
+
             let file_coverage = FileCoverage {
                 path: file.path.unwrap_or(file.name),
                 hits: line_hits,
+                branches,
+                functions,
                 ..Default::default()
             };
 
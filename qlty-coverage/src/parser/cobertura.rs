@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::{Context, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
@@ -28,6 +28,20 @@ struct Classes {
 #[derive(Debug, Deserialize, Clone)]
 struct Class {
     filename: String,
+    #[serde(default)]
+    methods: Option<Methods>,
+    lines: Lines,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct Methods {
+    #[serde(default)]
+    method: Option<Vec<Method>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct Method {
+    name: String,
     lines: Lines,
 }
 
@@ -41,6 +55,19 @@ struct Lines {
 struct Line {
     number: String,
     hits: String,
+    #[serde(rename = "condition-coverage", default)]
+    condition_coverage: Option<String>,
+}
+
+impl Line {
+    // condition-coverage="50% (1/2)"
+    fn conditions(&self) -> Option<(u32, u32)> {
+        let condition_coverage = self.condition_coverage.as_ref()?;
+        let (_, counts) = condition_coverage.split_once('(')?;
+        let (covered, total) = counts.trim_end_matches(')').split_once('/')?;
+
+        Some((covered.trim().parse().ok()?, total.trim().parse().ok()?))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -59,10 +86,22 @@ impl Parser for Cobertura {
 
         // BTreeMap allows us to index by filename while sorting at the same time
         let mut lines_by_filename: BTreeMap<String, Vec<Line>> = BTreeMap::new();
+        let mut functions_by_filename: BTreeMap<String, Vec<FunctionCoverage>> = BTreeMap::new();
         let mut file_coverages = vec![];
 
         for package in source.packages.package.iter() {
             for class in package.classes.class.iter() {
+                if let Some(methods) = class.methods.as_ref().and_then(|m| m.method.as_ref()) {
+                    for method in methods {
+                        if let Some(function) = Self::function_coverage(method) {
+                            functions_by_filename
+                                .entry(class.filename.clone())
+                                .or_default()
+                                .push(function);
+                        }
+                    }
+                }
+
                 if let Some(ref lines) = class.lines.line {
                     for line in lines {
                         lines_by_filename
@@ -78,6 +117,7 @@ impl Parser for Cobertura {
 
         for (filename, lines) in lines_by_filename {
             let mut line_hits = Vec::new();
+            let mut branches = Vec::new();
             let mut sorted_lines = lines.clone();
             sorted_lines.sort_by_key(|line| line.number.parse::<i32>().unwrap_or_default());
 
@@ -94,13 +134,30 @@ impl Parser for Cobertura {
                         } else {
                             line_hits[line_number - 1] += hits;
                         }
+
+                        if let Some((covered, total)) = line.conditions() {
+                            // Cobertura only reports how many of the conditions were taken,
+                            // so the covered ones are listed first
+                            for branch in 0..total {
+                                branches.push(BranchCoverage {
+                                    line: line_number as u32,
+                                    block: 0,
+                                    branch,
+                                    hits: if branch < covered { 1 } else { 0 },
+                                });
+                            }
+                        }
                     }
                 }
             }
 
+            let functions = functions_by_filename.remove(&filename).unwrap_or_default();
+
             let file_coverage = FileCoverage {
                 path: filename,
                 hits: line_hits,
+                branches,
+                functions,
                 ..Default::default()
             };
 
@@ -111,6 +168,24 @@ impl Parser for Cobertura {
     }
 }
 
+impl Cobertura {
+    fn function_coverage(method: &Method) -> Option<FunctionCoverage> {
+        let first_line = method
+            .lines
+            .line
+            .as_ref()?
+            .iter()
+            .filter_map(|line| Some((line.number.parse::<u32>().ok()?, line)))
+            .min_by_key(|(number, _)| *number)?;
+
+        Some(FunctionCoverage {
+            name: method.name.clone(),
+            line: first_line.0,
+            hits: first_line.1.hits.parse::<i64>().unwrap_or(0),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +229,16 @@ mod tests {
         - "3"
         - "3"
         - "3"
+      functions:
+        - name: "<init>"
+          line: 10
+          hits: "3"
+        - name: doSearch
+          line: 23
+          hits: "3"
+        - name: main
+          line: 16
+          hits: "3"
     - path: search/BinarySearch.java
       hits:
         - "-1"
@@ -187,6 +272,28 @@ mod tests {
         - "9"
         - "-1"
         - "3"
+      branches:
+        - line: 18
+          hits: "1"
+        - line: 18
+          branch: 1
+          hits: "1"
+        - line: 23
+          hits: "1"
+        - line: 23
+          branch: 1
+        - line: 25
+          hits: "1"
+        - line: 25
+          branch: 1
+          hits: "1"
+      functions:
+        - name: "<init>"
+          line: 12
+          hits: "3"
+        - name: find
+          line: 16
+          hits: "3"
     - path: search/ISortedArraySearch.java
     - path: search/LinearSearch.java
       hits:
@@ -230,6 +337,30 @@ mod tests {
         - "-1"
         - "-1"
         - "5"
+      branches:
+        - line: 13
+          hits: "1"
+        - line: 13
+          branch: 1
+        - line: 15
+          hits: "1"
+        - line: 15
+          branch: 1
+          hits: "1"
+        - line: 17
+          hits: "1"
+        - line: 17
+          branch: 1
+      functions:
+        - name: "<init>"
+          line: 9
+          hits: "3"
+        - name: find
+          line: 13
+          hits: "9"
+        - name: "<init>"
+          line: 9
+          hits: "3"
     "#);
     }
 }
@@ -0,0 +1,221 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+// Output of `coverage json`
+#[derive(Debug, Deserialize)]
+struct CoveragepySource {
+    files: BTreeMap<String, CoveragepyFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoveragepyFile {
+    #[serde(default)]
+    executed_lines: Vec<u32>,
+    #[serde(default)]
+    missing_lines: Vec<u32>,
+    #[serde(default)]
+    executed_branches: Vec<(i64, i64)>,
+    #[serde(default)]
+    missing_branches: Vec<(i64, i64)>,
+    // Only present in format 3 and newer
+    #[serde(default)]
+    functions: BTreeMap<String, CoveragepyRegion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoveragepyRegion {
+    #[serde(default)]
+    executed_lines: Vec<u32>,
+    #[serde(default)]
+    missing_lines: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Coveragepy {}
+
+impl Coveragepy {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn file_coverage(path: &str, file: &CoveragepyFile) -> FileCoverage {
+        let last_line = file
+            .executed_lines
+            .iter()
+            .chain(file.missing_lines.iter())
+            .max()
+            .copied()
+            .unwrap_or(0);
+
+        let mut hits = vec![-1; last_line as usize];
+
+        for line in &file.missing_lines {
+            hits[*line as usize - 1] = 0;
+        }
+
+        for line in &file.executed_lines {
+            hits[*line as usize - 1] = 1;
+        }
+
+        FileCoverage {
+            path: path.to_string(),
+            hits,
+            branches: Self::branches(file),
+            functions: Self::functions(file),
+            ..Default::default()
+        }
+    }
+
+    // Arcs are (from, to) pairs, where a negative "to" is an exit from the
+    // code object. Each source line with arcs becomes one block.
+    fn branches(file: &CoveragepyFile) -> Vec<BranchCoverage> {
+        let mut arcs_by_line: BTreeMap<i64, Vec<(i64, i64)>> = BTreeMap::new();
+
+        for (from, to) in &file.executed_branches {
+            arcs_by_line.entry(*from).or_default().push((*to, 1));
+        }
+
+        for (from, to) in &file.missing_branches {
+            arcs_by_line.entry(*from).or_default().push((*to, 0));
+        }
+
+        let mut branches = vec![];
+
+        for (block, (line, mut arcs)) in arcs_by_line.into_iter().enumerate() {
+            if line <= 0 {
+                continue;
+            }
+
+            arcs.sort();
+
+            for (branch, (_, hits)) in arcs.into_iter().enumerate() {
+                branches.push(BranchCoverage {
+                    line: line as u32,
+                    block: block as u32,
+                    branch: branch as u32,
+                    hits,
+                });
+            }
+        }
+
+        branches
+    }
+
+    // The empty name is the module-level code outside of any function
+    fn functions(file: &CoveragepyFile) -> Vec<FunctionCoverage> {
+        let mut functions: Vec<_> = file
+            .functions
+            .iter()
+            .filter(|(name, _)| !name.is_empty())
+            .filter_map(|(name, region)| {
+                let line = region
+                    .executed_lines
+                    .iter()
+                    .chain(region.missing_lines.iter())
+                    .min()?;
+
+                Some(FunctionCoverage {
+                    name: name.clone(),
+                    line: *line,
+                    hits: if region.executed_lines.is_empty() {
+                        0
+                    } else {
+                        1
+                    },
+                })
+            })
+            .collect();
+
+        functions.sort_by(|a, b| a.line.cmp(&b.line).then_with(|| a.name.cmp(&b.name)));
+        functions
+    }
+}
+
+impl Parser for Coveragepy {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let source: CoveragepySource =
+            serde_json::from_str(text).with_context(|| "Failed to parse JSON text")?;
+
+        Ok(source
+            .files
+            .iter()
+            .map(|(path, file)| Self::file_coverage(path, file))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coveragepy_results() {
+        let input = include_str!("../../tests/fixtures/coveragepy/coverage.json");
+        let parsed_results = Coveragepy::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: app/__init__.py
+        - path: app/calculator.py
+          hits:
+            - "1"
+            - "1"
+            - "0"
+            - "-1"
+            - "1"
+            - "1"
+            - "1"
+            - "0"
+            - "1"
+            - "-1"
+            - "1"
+          branches:
+            - line: 2
+            - line: 2
+              branch: 1
+              hits: "1"
+            - line: 7
+              block: 1
+            - line: 7
+              block: 1
+              branch: 1
+              hits: "1"
+          functions:
+            - name: add
+              line: 2
+              hits: "1"
+            - name: divide
+              line: 7
+              hits: "1"
+        "#);
+    }
+
+    #[test]
+    fn coveragepy_without_branches() {
+        let input = r#"
+        {
+            "meta": {"version": "6.5.0", "timestamp": "2024-01-01T00:00:00", "branch_coverage": false, "show_contexts": false},
+            "files": {
+                "app/models.py": {
+                    "executed_lines": [1, 2, 4],
+                    "summary": {"covered_lines": 3, "num_statements": 4, "percent_covered": 75.0, "missing_lines": 1, "excluded_lines": 1},
+                    "missing_lines": [5],
+                    "excluded_lines": [7]
+                }
+            },
+            "totals": {"covered_lines": 3, "num_statements": 4, "percent_covered": 75.0, "missing_lines": 1, "excluded_lines": 1}
+        }
+        "#;
+        let parsed_results = Coveragepy::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: app/models.py
+          hits:
+            - "1"
+            - "1"
+            - "-1"
+            - "1"
+            - "0"
+        "#);
+    }
+}
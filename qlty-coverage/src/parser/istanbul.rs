@@ -0,0 +1,202 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+// Istanbul's coverage-final.json, keyed by file path
+type IstanbulSource = BTreeMap<String, IstanbulFile>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IstanbulFile {
+    path: Option<String>,
+    #[serde(default)]
+    statement_map: HashMap<String, Location>,
+    #[serde(default)]
+    s: HashMap<String, i64>,
+    #[serde(default)]
+    fn_map: HashMap<String, Function>,
+    #[serde(default)]
+    f: HashMap<String, i64>,
+    #[serde(default)]
+    branch_map: HashMap<String, Branch>,
+    #[serde(default)]
+    b: HashMap<String, Vec<i64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Location {
+    start: Position,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Function {
+    name: String,
+    decl: Option<Location>,
+    loc: Option<Location>,
+    line: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    loc: Option<Location>,
+    line: Option<u32>,
+}
+
+impl Function {
+    fn start_line(&self) -> Option<u32> {
+        self.decl
+            .as_ref()
+            .and_then(|decl| decl.start.line)
+            .or_else(|| self.loc.as_ref().and_then(|loc| loc.start.line))
+            .or(self.line)
+    }
+}
+
+impl Branch {
+    fn start_line(&self) -> Option<u32> {
+        self.loc
+            .as_ref()
+            .and_then(|loc| loc.start.line)
+            .or(self.line)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Istanbul {}
+
+impl Istanbul {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn file_coverage(key: &str, file: &IstanbulFile) -> FileCoverage {
+        // A line is as covered as the most executed statement starting on it
+        let mut line_hits: BTreeMap<u32, i64> = BTreeMap::new();
+
+        for (id, location) in &file.statement_map {
+            if let Some(line) = location.start.line {
+                let hits = file.s.get(id).copied().unwrap_or(0);
+                let entry = line_hits.entry(line).or_insert(hits);
+                *entry = (*entry).max(hits);
+            }
+        }
+
+        let mut hits = vec![-1; line_hits.keys().last().copied().unwrap_or(0) as usize];
+
+        for (line, count) in line_hits {
+            if line > 0 {
+                hits[line as usize - 1] = count;
+            }
+        }
+
+        let mut functions: Vec<_> = file
+            .fn_map
+            .iter()
+            .filter_map(|(id, function)| {
+                Some(FunctionCoverage {
+                    name: function.name.clone(),
+                    line: function.start_line()?,
+                    hits: file.f.get(id).copied().unwrap_or(0),
+                })
+            })
+            .collect();
+        functions.sort_by(|a, b| a.line.cmp(&b.line).then_with(|| a.name.cmp(&b.name)));
+
+        let mut branch_ids: Vec<_> = file.branch_map.keys().collect();
+        branch_ids.sort_by_key(|id| id.parse::<u32>().unwrap_or(u32::MAX));
+
+        let mut branches = vec![];
+
+        for (block, id) in branch_ids.into_iter().enumerate() {
+            let Some(line) = file.branch_map[id].start_line() else {
+                continue;
+            };
+
+            for (branch, hits) in file.b.get(id).into_iter().flatten().enumerate() {
+                branches.push(BranchCoverage {
+                    line,
+                    block: block as u32,
+                    branch: branch as u32,
+                    hits: *hits,
+                });
+            }
+        }
+
+        FileCoverage {
+            path: file.path.clone().unwrap_or_else(|| key.to_string()),
+            hits,
+            branches,
+            functions,
+            ..Default::default()
+        }
+    }
+}
+
+impl Parser for Istanbul {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let source: IstanbulSource =
+            serde_json::from_str(text).with_context(|| "Failed to parse JSON text")?;
+
+        Ok(source
+            .iter()
+            .map(|(key, file)| Self::file_coverage(key, file))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn istanbul_results() {
+        let input = include_str!("../../tests/fixtures/istanbul/coverage-final.json");
+        let parsed_results = Istanbul::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: /home/runner/work/app/src/math.js
+          hits:
+            - "1"
+            - "4"
+            - "-1"
+            - "-1"
+            - "-1"
+            - "2"
+            - "0"
+            - "-1"
+            - "2"
+            - "-1"
+            - "-1"
+            - "1"
+          branches:
+            - line: 6
+            - line: 6
+              branch: 1
+              hits: "2"
+          functions:
+            - name: add
+              line: 1
+              hits: "4"
+            - name: divide
+              line: 5
+              hits: "2"
+        - path: /home/runner/work/app/src/unused.js
+          hits:
+            - "0"
+            - "-1"
+            - "0"
+        "#);
+    }
+
+    #[test]
+    fn istanbul_empty() {
+        let parsed_results = Istanbul::new().parse_text("{}").unwrap();
+        assert!(parsed_results.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::{Context, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
 use serde::Deserialize;
 use serde_xml_rs;
 
@@ -13,9 +13,53 @@ struct JacocoSource {
 #[derive(Debug, Deserialize)]
 struct Package {
     name: String,
+    #[serde(default)]
+    class: Vec<Class>,
     sourcefile: Vec<Sourcefile>,
 }
 
+#[derive(Debug, Deserialize)]
+struct Class {
+    name: String,
+    sourcefilename: Option<String>,
+    #[serde(default)]
+    method: Vec<Method>,
+}
+
+impl Class {
+    // Older JaCoCo reports omit the sourcefilename attribute, so fall back to the
+    // outermost class name, e.g. "be/apo/basic/Application$Inner" -> "Application"
+    fn belongs_to(&self, sourcefile: &Sourcefile) -> bool {
+        if let Some(sourcefilename) = &self.sourcefilename {
+            return sourcefilename == &sourcefile.name;
+        }
+
+        let simple_name = self.name.rsplit('/').next().unwrap_or(&self.name);
+        let outer_name = simple_name.split('$').next().unwrap_or(simple_name);
+
+        sourcefile
+            .name
+            .rsplit_once('.')
+            .map_or(sourcefile.name.as_str(), |(stem, _)| stem)
+            == outer_name
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Method {
+    name: String,
+    line: Option<u32>,
+    #[serde(default)]
+    counter: Vec<Counter>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Counter {
+    #[serde(rename = "type")]
+    counter_type: String,
+    covered: i64,
+}
+
 #[derive(Debug, Deserialize)]
 struct Sourcefile {
     name: String,
@@ -26,6 +70,10 @@ struct Sourcefile {
 struct Line {
     nr: i64,
     ci: i64,
+    #[serde(default)]
+    mb: u32,
+    #[serde(default)]
+    cb: u32,
 }
 
 pub struct Jacoco {}
@@ -51,6 +99,8 @@ impl Parser for Jacoco {
         for package in source.package.iter() {
             for sourcefile in package.sourcefile.iter() {
                 let mut line_hits = Vec::new();
+                let mut branches = Vec::new();
+
                 if let Some(lines) = sourcefile.line.as_ref() {
                     for line in lines {
                         // Fill in any missing lines with -1 to indicate that are omitted
@@ -59,14 +109,46 @@ impl Parser for Jacoco {
                         }
 
                         line_hits.push(line.ci);
+
+                        // JaCoCo only counts covered (cb) and missed (mb) branches per line
+                        for branch in 0..(line.cb + line.mb) {
+                            branches.push(BranchCoverage {
+                                line: line.nr as u32,
+                                block: 0,
+                                branch,
+                                hits: if branch < line.cb { 1 } else { 0 },
+                            });
+                        }
                     }
                 }
 
+                let functions = package
+                    .class
+                    .iter()
+                    .filter(|class| class.belongs_to(sourcefile))
+                    .flat_map(|class| class.method.iter())
+                    .filter_map(|method| {
+                        let covered = method
+                            .counter
+                            .iter()
+                            .find(|counter| counter.counter_type == "METHOD")?
+                            .covered;
+
+                        Some(FunctionCoverage {
+                            name: method.name.clone(),
+                            line: method.line.unwrap_or(0),
+                            hits: covered,
+                        })
+                    })
+                    .collect();
+
                 let path = format!("{}/{}", package.name, sourcefile.name);
 
                 let file_coverage = FileCoverage {
                     path,
                     hits: line_hits,
+                    branches,
+                    functions,
                     ..Default::default()
                 };
 
@@ -102,6 +184,12 @@ mod tests {
             - "-1"
             - "0"
             - "0"
+          functions:
+            - name: "<init>"
+              line: 7
+              hits: "1"
+            - name: main
+              line: 10
         - path: be/apo/basic/rest/EchoService.java
           hits:
             - "-1"
@@ -116,6 +204,12 @@ mod tests {
             - "-1"
             - "-1"
             - "0"
+          functions:
+            - name: "<init>"
+              line: 8
+              hits: "1"
+            - name: echo
+              line: 12
         - path: be/apo/basic/rest/model/Poney.java
           hits:
             - "-1"
@@ -135,6 +229,15 @@ mod tests {
             - "-1"
             - "-1"
             - "0"
+          functions:
+            - name: "<init>"
+              line: 3
+            - name: getHead
+              line: 9
+            - name: setHead
+              line: 13
+            - name: getId
+              line: 17
         - path: be/apo/basic/rest/model/Empty.java
         "###);
     }
@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::Result;
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -27,9 +27,25 @@ impl Parser for Lcov {
                 };
 
                 let mut line_numbers_to_hits = HashMap::new();
+                let mut functions: Vec<FunctionCoverage> = vec![];
+                let mut function_names_to_hits: HashMap<String, i64> = HashMap::new();
 
                 for lcov_line in lcov_lines.by_ref() {
-                    if let Some(lcov_line) = lcov_line.strip_prefix(DA) {
+                    if let Some(lcov_line) = lcov_line.strip_prefix(FN) {
+                        if let Some(function) = Self::parse_function(lcov_line) {
+                            functions.push(function);
+                        }
+                    } else if let Some(lcov_line) = lcov_line.strip_prefix(FNDA) {
+                        if let Some((hits_count, name)) = lcov_line.split_once(',') {
+                            let hits_count = hits_count.parse::<i64>().unwrap_or(0);
+                            *function_names_to_hits.entry(name.to_string()).or_insert(0) +=
+                                hits_count;
+                        }
+                    } else if let Some(lcov_line) = lcov_line.strip_prefix(BRDA) {
+                        if let Some(branch) = Self::parse_branch(lcov_line) {
+                            file_coverage.branches.push(branch);
+                        }
+                    } else if let Some(lcov_line) = lcov_line.strip_prefix(DA) {
                         let mut split = lcov_line.split(',');
 
                         let line_number = split.next().unwrap();
@@ -51,7 +67,12 @@ impl Parser for Lcov {
                     line_hits[(line_number - 1) as usize] = hits as i64;
                 }
 
+                for function in functions.iter_mut() {
+                    function.hits = *function_names_to_hits.get(&function.name).unwrap_or(&0);
+                }
+
                 file_coverage.hits = line_hits;
+                file_coverage.functions = functions;
                 file_coverages.push(file_coverage);
             }
         }
@@ -60,7 +81,48 @@ impl Parser for Lcov {
     }
 }
 
+impl Lcov {
+    // FN:<line number>,<function name> or, since lcov 2.0,
+    // FN:<start line>,<end line>,<function name>
+    fn parse_function(text: &str) -> Option<FunctionCoverage> {
+        let (line_number, rest) = text.split_once(',')?;
+        let line = line_number.parse::<u32>().ok()?;
+
+        let name = match rest.split_once(',') {
+            Some((end_line, name)) if end_line.parse::<u32>().is_ok() => name,
+            _ => rest,
+        };
+
+        Some(FunctionCoverage {
+            name: name.to_string(),
+            line,
+            hits: 0,
+        })
+    }
+
+    // BRDA:<line number>,<block number>,<branch number>,<taken>
+    // where <taken> is "-" when the enclosing block was never executed
+    fn parse_branch(text: &str) -> Option<BranchCoverage> {
+        let mut split = text.split(',');
+
+        let line = split.next()?.parse::<u32>().ok()?;
+        let block = split.next()?.parse::<u32>().unwrap_or(0);
+        let branch = split.next()?.parse::<u32>().unwrap_or(0);
+        let hits = split.next()?.parse::<i64>().unwrap_or(0);
+
+        Some(BranchCoverage {
+            line,
+            block,
+            branch,
+            hits,
+        })
+    }
+}
+
 const SF: &str = "SF:";
+const FN: &str = "FN:";
+const FNDA: &str = "FNDA:";
+const BRDA: &str = "BRDA:";
 const DA: &str = "DA:";
 const END_OF_RECORD: &str = "end_of_record";
 
@@ -88,6 +150,43 @@ DA:5,10
         "#);
     }
 
+    #[test]
+    fn test_branches_and_functions() {
+        let input = r#"
+SF:src/lib.rs
+FN:1,main
+FN:4,helper
+FNDA:3,main
+FNDA:0,helper
+DA:1,3
+DA:2,3
+DA:4,0
+BRDA:2,0,0,2
+BRDA:2,0,1,-
+end_of_record
+"#;
+
+        insta::assert_yaml_snapshot!(Lcov::new().parse_text(input).unwrap(), @r#"
+        - path: src/lib.rs
+          hits:
+            - "3"
+            - "3"
+            - "-1"
+            - "0"
+          branches:
+            - line: 2
+              hits: "2"
+            - line: 2
+              branch: 1
+          functions:
+            - name: main
+              line: 1
+              hits: "3"
+            - name: helper
+              line: 4
+        "#);
+    }
+
     #[test]
     fn test_fixture() {
         let input = include_str!("../../tests/fixtures/lcov/sample.lcov");
@@ -146,6 +245,87 @@ DA:5,10
         - "-1"
         - "-1"
         - "2"
+      branches:
+        - line: 11
+          block: 1
+          hits: "1"
+        - line: 11
+          block: 1
+          branch: 1
+        - line: 15
+          block: 2
+          hits: "5"
+        - line: 15
+          block: 2
+          branch: 1
+        - line: 19
+          block: 3
+          hits: "1"
+        - line: 19
+          block: 3
+          branch: 1
+          hits: "1"
+        - line: 21
+          block: 4
+          hits: "1"
+        - line: 21
+          block: 4
+          branch: 1
+        - line: 32
+          block: 5
+        - line: 32
+          block: 5
+          branch: 1
+          hits: "2"
+        - line: 52
+          block: 6
+        - line: 52
+          block: 6
+          branch: 1
+          hits: "2"
+        - line: 73
+          block: 7
+          hits: "5"
+        - line: 73
+          block: 7
+          branch: 1
+        - line: 91
+          block: 8
+        - line: 91
+          block: 8
+          branch: 1
+          hits: "5"
+      functions:
+        - name: Formatter
+          line: 10
+          hits: "1"
+        - name: (anonymous_2)
+          line: 14
+          hits: "5"
+        - name: (anonymous_3)
+          line: 18
+          hits: "2"
+        - name: (anonymous_4)
+          line: 28
+          hits: "2"
+        - name: (anonymous_5)
+          line: 31
+          hits: "2"
+        - name: (anonymous_6)
+          line: 51
+          hits: "2"
+        - name: (anonymous_7)
+          line: 65
+          hits: "2"
+        - name: (anonymous_8)
+          line: 68
+          hits: "5"
+        - name: (anonymous_9)
+          line: 83
+          hits: "5"
+        - name: (anonymous_10)
+          line: 86
+          hits: "817"
     "#);
     }
 
@@ -0,0 +1,239 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Output of `llvm-cov export -format=text`
+#[derive(Debug, Deserialize)]
+struct LlvmSource {
+    data: Vec<LlvmExport>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlvmExport {
+    #[serde(default)]
+    files: Vec<LlvmFile>,
+    #[serde(default)]
+    functions: Vec<LlvmFunction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlvmFile {
+    filename: String,
+    #[serde(default)]
+    segments: Vec<Vec<Value>>,
+    #[serde(default)]
+    branches: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlvmFunction {
+    name: String,
+    count: i64,
+    #[serde(default)]
+    regions: Vec<Vec<Value>>,
+    #[serde(default)]
+    filenames: Vec<String>,
+}
+
+// [line, col, count, has_count, is_region_entry, is_gap_region]
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    line: u32,
+    count: i64,
+    has_count: bool,
+    is_region_entry: bool,
+    is_gap_region: bool,
+}
+
+impl Segment {
+    fn from_json(values: &[Value]) -> Option<Self> {
+        Some(Self {
+            line: values.first()?.as_u64()? as u32,
+            count: values.get(2)?.as_i64()?,
+            has_count: values.get(3)?.as_bool()?,
+            is_region_entry: values.get(4)?.as_bool()?,
+            is_gap_region: values.get(5).and_then(Value::as_bool).unwrap_or(false),
+        })
+    }
+
+    fn is_start_of_region(&self) -> bool {
+        !self.is_gap_region && self.has_count && self.is_region_entry
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Llvm {}
+
+impl Llvm {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    // Mirrors llvm-cov's LineCoverageStats: a line takes the count of the region
+    // wrapping into it, or the largest count of a region starting on it.
+    fn line_hits(segments: &[Segment]) -> Vec<i64> {
+        let Some(last_line) = segments.iter().map(|segment| segment.line).max() else {
+            return vec![];
+        };
+
+        let mut hits = vec![-1; last_line as usize];
+        let mut wrapped: Option<Segment> = None;
+        let mut index = 0;
+
+        for line in 1..=last_line {
+            let start = index;
+
+            while index < segments.len() && segments[index].line == line {
+                index += 1;
+            }
+
+            let line_segments = &segments[start..index];
+            let region_starts = line_segments
+                .iter()
+                .filter(|segment| segment.is_start_of_region())
+                .count();
+            let start_of_skipped_region = line_segments
+                .first()
+                .is_some_and(|segment| !segment.has_count && segment.is_region_entry);
+            let mapped = !start_of_skipped_region
+                && (wrapped.is_some_and(|segment| segment.has_count) || region_starts > 0);
+
+            if mapped {
+                let mut count = wrapped.map_or(0, |segment| segment.count);
+
+                for segment in line_segments {
+                    if segment.is_start_of_region() {
+                        count = count.max(segment.count);
+                    }
+                }
+
+                hits[line as usize - 1] = count;
+            }
+
+            if let Some(last) = line_segments.last() {
+                wrapped = Some(*last);
+            }
+        }
+
+        hits
+    }
+
+    // [line_start, col_start, line_end, col_end, true_count, false_count, ...]
+    fn branches(file: &LlvmFile) -> Vec<BranchCoverage> {
+        let mut branches = vec![];
+
+        for (block, values) in file.branches.iter().enumerate() {
+            let line = values.first().and_then(Value::as_u64);
+            let true_count = values.get(4).and_then(Value::as_i64);
+            let false_count = values.get(5).and_then(Value::as_i64);
+
+            if let (Some(line), Some(true_count), Some(false_count)) =
+                (line, true_count, false_count)
+            {
+                for (branch, hits) in [true_count, false_count].into_iter().enumerate() {
+                    branches.push(BranchCoverage {
+                        line: line as u32,
+                        block: block as u32,
+                        branch: branch as u32,
+                        hits,
+                    });
+                }
+            }
+        }
+
+        branches
+    }
+}
+
+impl Parser for Llvm {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let source: LlvmSource =
+            serde_json::from_str(text).with_context(|| "Failed to parse JSON text")?;
+
+        let mut file_coverages: BTreeMap<String, FileCoverage> = BTreeMap::new();
+
+        for export in &source.data {
+            for file in &export.files {
+                let mut segments: Vec<_> = file
+                    .segments
+                    .iter()
+                    .filter_map(|values| Segment::from_json(values))
+                    .collect();
+                segments.sort_by_key(|segment| segment.line);
+
+                file_coverages.insert(
+                    file.filename.clone(),
+                    FileCoverage {
+                        path: file.filename.clone(),
+                        hits: Self::line_hits(&segments),
+                        branches: Self::branches(file),
+                        ..Default::default()
+                    },
+                );
+            }
+
+            for function in &export.functions {
+                let line = function
+                    .regions
+                    .first()
+                    .and_then(|region| region.first())
+                    .and_then(Value::as_u64);
+
+                let file_coverage = function
+                    .filenames
+                    .first()
+                    .and_then(|filename| file_coverages.get_mut(filename));
+
+                if let (Some(line), Some(file_coverage)) = (line, file_coverage) {
+                    file_coverage.functions.push(FunctionCoverage {
+                        name: function.name.clone(),
+                        line: line as u32,
+                        hits: function.count,
+                    });
+                }
+            }
+        }
+
+        Ok(file_coverages.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn llvm_results() {
+        let input = include_str!("../../tests/fixtures/llvm/sample.json");
+        let parsed_results = Llvm::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: /home/runner/work/app/src/main.rs
+          hits:
+            - "1"
+            - "1"
+            - "1"
+            - "-1"
+            - "1"
+            - "1"
+            - "1"
+            - "0"
+            - "0"
+            - "1"
+          branches:
+            - line: 7
+            - line: 7
+              branch: 1
+              hits: "1"
+          functions:
+            - name: _ZN4main3add17h0123456789abcdefE
+              line: 1
+              hits: "1"
+            - name: _ZN4main4main17hfedcba9876543210E
+              line: 5
+              hits: "1"
+        "#);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage, FunctionCoverage};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "CoverageSession")]
+struct OpenCoverSource {
+    #[serde(rename = "Modules", default)]
+    modules: Modules,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct Modules {
+    #[serde(rename = "Module", default)]
+    module: Vec<Module>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Module {
+    #[serde(rename = "Files", default)]
+    files: Files,
+    #[serde(rename = "Classes", default)]
+    classes: Classes,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct Files {
+    #[serde(rename = "File", default)]
+    file: Vec<File>,
+}
+
+#[derive(Debug, Deserialize)]
+struct File {
+    uid: String,
+    #[serde(rename = "fullPath")]
+    full_path: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct Classes {
+    #[serde(rename = "Class", default)]
+    class: Vec<Class>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Class {
+    #[serde(rename = "Methods", default)]
+    methods: Methods,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct Methods {
+    #[serde(rename = "Method", default)]
+    method: Vec<Method>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Method {
+    #[serde(rename = "Name", default)]
+    name: String,
+    #[serde(rename = "FileRef")]
+    file_ref: Option<FileRef>,
+    #[serde(rename = "SequencePoints", default)]
+    sequence_points: SequencePoints,
+    #[serde(rename = "BranchPoints", default)]
+    branch_points: BranchPoints,
+    #[serde(rename = "MethodPoint")]
+    method_point: Option<Point>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileRef {
+    uid: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct SequencePoints {
+    #[serde(rename = "SequencePoint", default)]
+    sequence_point: Vec<Point>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct BranchPoints {
+    #[serde(rename = "BranchPoint", default)]
+    branch_point: Vec<Point>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Point {
+    vc: i64,
+    sl: Option<u32>,
+    offset: Option<u32>,
+    path: Option<u32>,
+}
+
+#[derive(Debug, Default)]
+struct FileData {
+    line_hits: BTreeMap<u32, i64>,
+    // (line, offset) -> [(path, hits)]
+    branch_points: BTreeMap<(u32, u32), Vec<(u32, i64)>>,
+    functions: Vec<FunctionCoverage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OpenCover {}
+
+impl OpenCover {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    // "System.Void Example.Calculator::Add(System.Int32)" -> "Example.Calculator::Add(System.Int32)"
+    fn function_name(name: &str) -> String {
+        match name.split_once(' ') {
+            Some((_, qualified_name)) if qualified_name.contains("::") => {
+                qualified_name.to_string()
+            }
+            _ => name.to_string(),
+        }
+    }
+
+    fn add_method(data: &mut FileData, method: &Method) {
+        for point in &method.sequence_points.sequence_point {
+            if let Some(line) = point.sl {
+                let hits = data.line_hits.entry(line).or_insert(point.vc);
+                *hits = (*hits).max(point.vc);
+            }
+        }
+
+        for point in &method.branch_points.branch_point {
+            if let Some(line) = point.sl {
+                data.branch_points
+                    .entry((line, point.offset.unwrap_or(0)))
+                    .or_default()
+                    .push((point.path.unwrap_or(0), point.vc));
+            }
+        }
+
+        let first_line = method
+            .sequence_points
+            .sequence_point
+            .iter()
+            .filter_map(|point| point.sl)
+            .min();
+
+        if let Some(line) = first_line {
+            data.functions.push(FunctionCoverage {
+                name: Self::function_name(&method.name),
+                line,
+                hits: method.method_point.as_ref().map_or(0, |point| point.vc),
+            });
+        }
+    }
+
+    fn file_coverage(path: &str, data: FileData) -> FileCoverage {
+        let mut hits = vec![-1; data.line_hits.keys().last().copied().unwrap_or(0) as usize];
+
+        for (line, count) in data.line_hits {
+            if line > 0 {
+                hits[line as usize - 1] = count;
+            }
+        }
+
+        let mut branches = vec![];
+
+        for (block, ((line, _), mut paths)) in data.branch_points.into_iter().enumerate() {
+            paths.sort();
+
+            for (branch, (_, hits)) in paths.into_iter().enumerate() {
+                branches.push(BranchCoverage {
+                    line,
+                    block: block as u32,
+                    branch: branch as u32,
+                    hits,
+                });
+            }
+        }
+
+        let mut functions = data.functions;
+        functions.sort_by(|a, b| a.line.cmp(&b.line).then_with(|| a.name.cmp(&b.name)));
+
+        FileCoverage {
+            path: path.to_string(),
+            hits,
+            branches,
+            functions,
+            ..Default::default()
+        }
+    }
+}
+
+impl Parser for OpenCover {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let source: OpenCoverSource =
+            serde_xml_rs::from_str(text).with_context(|| "Failed to parse XML text")?;
+
+        let mut data_by_path: BTreeMap<String, FileData> = BTreeMap::new();
+
+        for module in &source.modules.module {
+            // File uids are only unique within a module
+            let paths: HashMap<&str, &str> = module
+                .files
+                .file
+                .iter()
+                .map(|file| (file.uid.as_str(), file.full_path.as_str()))
+                .collect();
+
+            for class in &module.classes.class {
+                for method in &class.methods.method {
+                    let path = method
+                        .file_ref
+                        .as_ref()
+                        .and_then(|file_ref| paths.get(file_ref.uid.as_str()));
+
+                    if let Some(path) = path {
+                        let data = data_by_path.entry(path.to_string()).or_default();
+                        Self::add_method(data, method);
+                    }
+                }
+            }
+        }
+
+        Ok(data_by_path
+            .into_iter()
+            .map(|(path, data)| Self::file_coverage(&path, data))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opencover_results() {
+        let input = include_str!("../../tests/fixtures/opencover/sample.xml");
+        let parsed_results = OpenCover::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: /home/runner/work/app/src/Example/Calculator.cs
+          hits:
+            - "-1"
+            - "-1"
+            - "-1"
+            - "-1"
+            - "-1"
+            - "3"
+            - "3"
+            - "3"
+            - "-1"
+            - "-1"
+            - "2"
+            - "2"
+            - "0"
+            - "2"
+          branches:
+            - line: 12
+            - line: 12
+              branch: 1
+              hits: "2"
+          functions:
+            - name: "Example.Calculator::Add(System.Int32,System.Int32)"
+              line: 6
+              hits: "3"
+            - name: "Example.Calculator::Divide(System.Int32,System.Int32)"
+              line: 11
+              hits: "2"
+        "#);
+    }
+
+    #[test]
+    fn test_function_name() {
+        assert_eq!(
+            OpenCover::function_name(
+                "System.Int32 Example.Calculator::Add(System.Int32,System.Int32)"
+            ),
+            "Example.Calculator::Add(System.Int32,System.Int32)"
+        );
+        assert_eq!(OpenCover::function_name("Main"), "Main");
+    }
+}
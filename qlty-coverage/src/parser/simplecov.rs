@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::{Context, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
 use semver::Version;
 use serde_json::{Map, Value};
 
@@ -108,6 +108,7 @@ impl SimplecovFormatter for SimplecovJson {
                         file_coverages.push(FileCoverage {
                             path: filename_str.to_string(),
                             hits: line_hits,
+                            branches: Simplecov::parse_branch_coverage(file),
                             ..Default::default()
                         });
                     }
@@ -127,6 +128,7 @@ impl Simplecov {
                 FileCoverage {
                     path: key.to_string(),
                     hits: line_hits,
+                    branches: Self::parse_branch_coverage(value),
                     ..Default::default()
                 }
             })
@@ -144,6 +146,62 @@ impl Simplecov {
         }
     }
 
+    // Branches come in two shapes:
+    // - Ruby's Coverage result (.resultset.json), keyed by condition then by branch:
+    //   {"[:if, 0, 3, 4, 3, 21]": {"[:then, 1, 3, 4, 3, 10]": 0, "[:else, 2, 3, 4, 3, 21]": 1}}
+    // - simplecov_json_formatter, as a flat list:
+    //   [{"type": "then", "start_line": 3, "end_line": 3, "coverage": 0}]
+    fn parse_branch_coverage(data: &Value) -> Vec<BranchCoverage> {
+        match data.get("branches") {
+            Some(Value::Object(conditions)) => conditions
+                .iter()
+                .enumerate()
+                .flat_map(|(block, (condition, branches))| {
+                    let line = Self::parse_branch_start_line(condition).unwrap_or(0);
+
+                    branches
+                        .as_object()
+                        .into_iter()
+                        .flat_map(|branches| branches.values())
+                        .enumerate()
+                        .map(move |(branch, hits)| BranchCoverage {
+                            line,
+                            block: block as u32,
+                            branch: branch as u32,
+                            hits: hits.as_i64().unwrap_or(0),
+                        })
+                })
+                .collect(),
+            Some(Value::Array(branches)) => branches
+                .iter()
+                .filter_map(|branch| {
+                    Some((
+                        branch.get("start_line")?.as_u64()? as u32,
+                        branch.get("coverage")?.as_i64()?,
+                    ))
+                })
+                .enumerate()
+                .map(|(branch, (line, hits))| BranchCoverage {
+                    line,
+                    block: 0,
+                    branch: branch as u32,
+                    hits,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    // "[:if, 0, 3, 4, 3, 21]" is [type, id, start_line, start_column, end_line, end_column]
+    fn parse_branch_start_line(key: &str) -> Option<u32> {
+        key.trim_matches(|c| c == '[' || c == ']')
+            .split(',')
+            .nth(2)?
+            .trim()
+            .parse()
+            .ok()
+    }
+
     fn parse_lines(value: &Value) -> i64 {
         match value {
             Value::Number(n) => n.as_i64().unwrap_or(-1),
@@ -205,6 +263,43 @@ mod test {
         "#);
     }
 
+    #[test]
+    fn simplecov_report_with_branches() {
+        let input = r#"
+        {
+            "meta": {
+                "simplecov_version": "0.22.0"
+            },
+            "coverage": {
+                "sample.rb": {
+                    "lines": [1, 1, 0, null],
+                    "branches": {
+                        "[:if, 0, 2, 4, 4, 20]": {
+                            "[:then, 1, 2, 4, 2, 10]": 1,
+                            "[:else, 2, 3, 4, 3, 10]": 0
+                        }
+                    }
+                }
+            },
+            "groups": {}
+        }
+        "#;
+        let results = Simplecov::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(results, @r#"
+        - path: sample.rb
+          hits:
+            - "1"
+            - "1"
+            - "0"
+            - "-1"
+          branches:
+            - line: 2
+              hits: "1"
+            - line: 2
+              branch: 1
+        "#);
+    }
+
     #[test]
     fn simplecov_legacy_report() {
         let input = r#"
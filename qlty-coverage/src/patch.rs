@@ -0,0 +1,241 @@
+use crate::merge::merge;
+use qlty_analysis::code::FileIndex;
+use qlty_types::analysis::v1::{Category, Issue, Level, Location, Range};
+use qlty_types::tests::v1::FileCoverage;
+use serde::Serialize;
+use std::path::Path;
+
+const TOOL: &str = "qlty";
+const DRIVER: &str = "coverage";
+const RULE_KEY: &str = "uncovered-code";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PatchCoverage {
+    pub files: Vec<FilePatchCoverage>,
+    pub totals: PatchCoverageTotals,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FilePatchCoverage {
+    pub path: String,
+    pub covered_lines: u64,
+    pub uncovered_lines: u64,
+    pub coverage_percentage: f64,
+    pub uncovered_line_numbers: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PatchCoverageTotals {
+    pub covered_lines: u64,
+    pub uncovered_lines: u64,
+    pub coverage_percentage: f64,
+}
+
+impl PatchCoverage {
+    /// Computes coverage of only the lines in `added_lines`. Lines which are
+    /// not executable (omitted) in the coverage data are not counted.
+    pub fn compute(file_coverages: &[FileCoverage], added_lines: &FileIndex) -> Self {
+        let mut files = vec![];
+        let mut totals = PatchCoverageTotals::default();
+
+        for file_coverage in merge(file_coverages.to_vec()) {
+            let file = Self::compute_file(&file_coverage.path, &file_coverage.hits, added_lines);
+
+            if file.covered_lines + file.uncovered_lines == 0 {
+                continue;
+            }
+
+            totals.covered_lines += file.covered_lines;
+            totals.uncovered_lines += file.uncovered_lines;
+            files.push(file);
+        }
+
+        totals.coverage_percentage = percentage(totals.covered_lines, totals.uncovered_lines);
+
+        Self { files, totals }
+    }
+
+    pub fn total_lines(&self) -> u64 {
+        self.totals.covered_lines + self.totals.uncovered_lines
+    }
+
+    /// Converts uncovered lines into issues, one per run of consecutive lines.
+    pub fn issues(&self) -> Vec<Issue> {
+        let mut issues = vec![];
+
+        for file in &self.files {
+            let mut lines = file.uncovered_line_numbers.iter().copied().peekable();
+
+            while let Some(start_line) = lines.next() {
+                let mut end_line = start_line;
+
+                while lines.peek() == Some(&(end_line + 1)) {
+                    end_line = lines.next().unwrap();
+                }
+
+                issues.push(Self::uncovered_issue(&file.path, start_line, end_line));
+            }
+        }
+
+        issues
+    }
+
+    fn uncovered_issue(path: &str, start_line: u32, end_line: u32) -> Issue {
+        let message = if start_line == end_line {
+            "Added line is not covered by tests".to_string()
+        } else {
+            format!(
+                "{} added lines are not covered by tests",
+                end_line - start_line + 1
+            )
+        };
+
+        Issue {
+            tool: TOOL.to_string(),
+            driver: DRIVER.to_string(),
+            rule_key: RULE_KEY.to_string(),
+            message,
+            level: Level::Low.into(),
+            category: Category::Coverage.into(),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line,
+                    end_line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn compute_file(path: &str, hits: &[i64], added_lines: &FileIndex) -> FilePatchCoverage {
+        let mut file = FilePatchCoverage {
+            path: path.to_string(),
+            ..Default::default()
+        };
+
+        for (index, hit) in hits.iter().enumerate() {
+            let line_number = index as u32 + 1;
+
+            if !added_lines.matches_line_range(Path::new(path), line_number..=line_number) {
+                continue;
+            }
+
+            match hit {
+                hit if *hit > 0 => file.covered_lines += 1,
+                0 => {
+                    file.uncovered_lines += 1;
+                    file.uncovered_line_numbers.push(line_number);
+                }
+                _ => {}
+            }
+        }
+
+        file.coverage_percentage = percentage(file.covered_lines, file.uncovered_lines);
+        file
+    }
+}
+
+fn percentage(covered: u64, uncovered: u64) -> f64 {
+    let total = covered + uncovered;
+
+    if total == 0 {
+        0.0
+    } else {
+        (covered as f64 / total as f64) * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_coverage(path: &str, hits: Vec<i64>) -> FileCoverage {
+        FileCoverage {
+            path: path.to_string(),
+            hits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compute_only_added_lines() {
+        let mut added_lines = FileIndex::new();
+        added_lines.insert_line(Path::new("src/lib.rs"), 2);
+        added_lines.insert_line(Path::new("src/lib.rs"), 3);
+        added_lines.insert_line(Path::new("src/lib.rs"), 4);
+
+        let file_coverages = vec![
+            file_coverage("src/lib.rs", vec![1, 1, 0, -1, 0]),
+            file_coverage("src/main.rs", vec![0, 0]),
+        ];
+
+        let patch = PatchCoverage::compute(&file_coverages, &added_lines);
+
+        assert_eq!(patch.files.len(), 1);
+        assert_eq!(patch.files[0].path, "src/lib.rs");
+        assert_eq!(patch.files[0].covered_lines, 1);
+        assert_eq!(patch.files[0].uncovered_lines, 1);
+        assert_eq!(patch.files[0].uncovered_line_numbers, vec![3]);
+        assert_eq!(patch.totals.coverage_percentage, 50.0);
+    }
+
+    #[test]
+    fn test_compute_new_file() {
+        let mut added_lines = FileIndex::new();
+        added_lines.insert_file(Path::new("src/new.rs"));
+
+        let file_coverages = vec![file_coverage("src/new.rs", vec![1, -1, 0, 3])];
+
+        let patch = PatchCoverage::compute(&file_coverages, &added_lines);
+
+        assert_eq!(patch.totals.covered_lines, 2);
+        assert_eq!(patch.totals.uncovered_lines, 1);
+        assert_eq!(patch.total_lines(), 3);
+    }
+
+    #[test]
+    fn test_compute_combines_reports() {
+        let mut added_lines = FileIndex::new();
+        added_lines.insert_file(Path::new("src/lib.rs"));
+
+        let file_coverages = vec![
+            file_coverage("src/lib.rs", vec![0, 0, -1]),
+            file_coverage("src/lib.rs", vec![1, 0, -1, 0]),
+        ];
+
+        let patch = PatchCoverage::compute(&file_coverages, &added_lines);
+
+        assert_eq!(patch.totals.covered_lines, 1);
+        assert_eq!(patch.totals.uncovered_lines, 2);
+        assert_eq!(patch.files[0].uncovered_line_numbers, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_issues_group_consecutive_lines() {
+        let mut added_lines = FileIndex::new();
+        added_lines.insert_file(Path::new("src/new.rs"));
+
+        let file_coverages = vec![file_coverage("src/new.rs", vec![0, 0, 1, 0, -1, 0])];
+
+        let patch = PatchCoverage::compute(&file_coverages, &added_lines);
+        let issues = patch.issues();
+
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0].rule_key, "uncovered-code");
+        assert_eq!(issues[0].category, Category::Coverage as i32);
+        assert_eq!(issues[0].message, "2 added lines are not covered by tests");
+        assert_eq!(issues[0].path(), Some("src/new.rs".to_string()));
+
+        let ranges = issues
+            .iter()
+            .map(|issue| {
+                let range = issue.range().unwrap();
+                (range.start_line, range.end_line)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![(1, 2), (4, 4), (6, 6)]);
+        assert_eq!(issues[1].message, "Added line is not covered by tests");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::patch::PatchCoverage;
 use crate::publish::Report;
 use anyhow::Result;
 use cli_table::{
@@ -9,6 +10,7 @@ use serde_json::{self};
 
 pub fn print_report_as_text(report: &Report) -> Result<()> {
     let mut total = CoverageSummary::default();
+    let mut total_branches = CoverageSummary::default();
 
     let mut rows: Vec<_> = report
         .file_coverages
@@ -17,6 +19,10 @@ pub fn print_report_as_text(report: &Report) -> Result<()> {
         .map(|file_coverage| {
             total += *file_coverage.summary.as_ref().unwrap();
 
+            if let Some(branch_summary) = file_coverage.branch_summary {
+                total_branches += branch_summary;
+            }
+
             vec![
                 file_coverage.path.cell(),
                 file_coverage
@@ -38,6 +44,9 @@ pub fn print_report_as_text(report: &Report) -> Result<()> {
                 (file_coverage.summary.as_ref().unwrap().percent() as u32)
                     .cell()
                     .justify(Justify::Right),
+                branch_percent(file_coverage.branch_summary.as_ref())
+                    .cell()
+                    .justify(Justify::Right),
             ]
         })
         .collect();
@@ -60,6 +69,10 @@ pub fn print_report_as_text(report: &Report) -> Result<()> {
             .cell()
             .bold(true)
             .justify(Justify::Right),
+        branch_percent(Some(&total_branches))
+            .cell()
+            .bold(true)
+            .justify(Justify::Right),
     ]);
 
     let table = rows
@@ -69,6 +82,7 @@ pub fn print_report_as_text(report: &Report) -> Result<()> {
             "covered".cell().justify(Justify::Right),
             "missed".cell().justify(Justify::Right),
             "%".cell().justify(Justify::Right),
+            "branch %".cell().justify(Justify::Right),
         ])
         .border(Border::builder().build())
         .separator(
@@ -82,6 +96,13 @@ pub fn print_report_as_text(report: &Report) -> Result<()> {
     Ok(())
 }
 
+fn branch_percent(branch_summary: Option<&CoverageSummary>) -> String {
+    match branch_summary {
+        Some(summary) if summary.total > 0 => (summary.percent() as u32).to_string(),
+        _ => "-".to_string(),
+    }
+}
+
 pub fn print_file_coverages_as_text(file_coverages: &[FileCoverage]) -> Result<()> {
     let rows: Vec<_> = file_coverages
         .iter()
@@ -112,3 +133,103 @@ pub fn print_file_coverages_as_json(file_coverages: &Vec<FileCoverage>) -> Resul
     println!("{}", serde_json::to_string_pretty(file_coverages)?);
     Ok(())
 }
+
+pub fn print_patch_coverage_as_text(patch: &PatchCoverage) -> Result<()> {
+    let mut rows: Vec<_> = patch
+        .files
+        .iter()
+        .map(|file| {
+            vec![
+                file.path.clone().cell(),
+                file.covered_lines.cell().justify(Justify::Right),
+                file.uncovered_lines.cell().justify(Justify::Right),
+                (file.coverage_percentage as u32)
+                    .cell()
+                    .justify(Justify::Right),
+                format_line_ranges(&file.uncovered_line_numbers).cell(),
+            ]
+        })
+        .collect();
+
+    rows.push(vec![
+        "TOTAL".cell().bold(true),
+        patch
+            .totals
+            .covered_lines
+            .cell()
+            .bold(true)
+            .justify(Justify::Right),
+        patch
+            .totals
+            .uncovered_lines
+            .cell()
+            .bold(true)
+            .justify(Justify::Right),
+        (patch.totals.coverage_percentage as u32)
+            .cell()
+            .bold(true)
+            .justify(Justify::Right),
+        "".cell(),
+    ]);
+
+    let table = rows
+        .table()
+        .title(vec![
+            "name".cell(),
+            "covered".cell().justify(Justify::Right),
+            "missed".cell().justify(Justify::Right),
+            "%".cell().justify(Justify::Right),
+            "missing lines".cell(),
+        ])
+        .border(Border::builder().build())
+        .separator(
+            Separator::builder()
+                .title(Some(HorizontalLine::default()))
+                .column(Some(VerticalLine::default()))
+                .build(),
+        );
+
+    print_stdout(table)?;
+    Ok(())
+}
+
+pub fn print_patch_coverage_as_json(patch: &PatchCoverage) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(patch)?);
+    Ok(())
+}
+
+// Collapses sorted line numbers into ranges, e.g. "3-5, 9"
+fn format_line_ranges(line_numbers: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+
+    for line_number in line_numbers {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *line_number => *end = *line_number,
+            _ => ranges.push((*line_number, *line_number)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line_ranges() {
+        assert_eq!(format_line_ranges(&[]), "");
+        assert_eq!(format_line_ranges(&[3]), "3");
+        assert_eq!(format_line_ranges(&[3, 4, 5, 9, 11, 12]), "3-5, 9, 11-12");
+    }
+}
@@ -1,6 +1,6 @@
 use qlty_types::tests::v1::FileCoverage;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct CoverageMetrics {
@@ -9,6 +9,12 @@ pub struct CoverageMetrics {
     pub omitted_lines: u64,
     pub total_lines: u64,
     pub coverage_percentage: f64,
+    pub covered_branches: u64,
+    pub uncovered_branches: u64,
+    pub branch_coverage_percentage: f64,
+    pub covered_functions: u64,
+    pub uncovered_functions: u64,
+    pub function_coverage_percentage: f64,
 }
 
 impl CoverageMetrics {
@@ -68,11 +74,41 @@ impl CoverageMetrics {
         let total_lines = covered_lines + uncovered_lines + omitted_lines;
         let coverable_lines = covered_lines + uncovered_lines;
 
-        let coverage_percentage = if coverable_lines > 0 {
-            (covered_lines as f64 / coverable_lines as f64) * 100.0
-        } else {
-            0.0
-        };
+        let coverage_percentage = Self::percentage(covered_lines, coverable_lines);
+
+        // Branches and functions reported for the same path in several files are
+        // combined by summing the hits of each (line, block, branch) and (line, name)
+        let mut branch_hits: BTreeMap<(&str, u32, u32, u32), i64> = BTreeMap::new();
+        let mut function_hits: BTreeMap<(&str, u32, &str), i64> = BTreeMap::new();
+
+        for file_coverage in file_coverages {
+            for branch in &file_coverage.branches {
+                *branch_hits
+                    .entry((
+                        file_coverage.path.as_str(),
+                        branch.line,
+                        branch.block,
+                        branch.branch,
+                    ))
+                    .or_default() += branch.hits;
+            }
+
+            for function in &file_coverage.functions {
+                *function_hits
+                    .entry((
+                        file_coverage.path.as_str(),
+                        function.line,
+                        function.name.as_str(),
+                    ))
+                    .or_default() += function.hits;
+            }
+        }
+
+        let covered_branches = branch_hits.values().filter(|&&hits| hits > 0).count() as u64;
+        let uncovered_branches = branch_hits.len() as u64 - covered_branches;
+
+        let covered_functions = function_hits.values().filter(|&&hits| hits > 0).count() as u64;
+        let uncovered_functions = function_hits.len() as u64 - covered_functions;
 
         Self {
             covered_lines,
@@ -80,6 +116,34 @@ impl CoverageMetrics {
             omitted_lines,
             total_lines,
             coverage_percentage,
+            covered_branches,
+            uncovered_branches,
+            branch_coverage_percentage: Self::percentage(
+                covered_branches,
+                covered_branches + uncovered_branches,
+            ),
+            covered_functions,
+            uncovered_functions,
+            function_coverage_percentage: Self::percentage(
+                covered_functions,
+                covered_functions + uncovered_functions,
+            ),
+        }
+    }
+
+    pub fn total_branches(&self) -> u64 {
+        self.covered_branches + self.uncovered_branches
+    }
+
+    pub fn total_functions(&self) -> u64 {
+        self.covered_functions + self.uncovered_functions
+    }
+
+    fn percentage(covered: u64, total: u64) -> f64 {
+        if total > 0 {
+            (covered as f64 / total as f64) * 100.0
+        } else {
+            0.0
         }
     }
 }
@@ -87,6 +151,7 @@ impl CoverageMetrics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qlty_types::tests::v1::{BranchCoverage, FunctionCoverage};
 
     #[test]
     fn test_empty_coverage() {
@@ -201,4 +266,53 @@ mod tests {
         assert_eq!(metrics.total_lines, 3);
         assert_eq!(metrics.coverage_percentage, 0.0);
     }
+
+    #[test]
+    fn test_combining_branches_and_functions_same_file() {
+        let branch = |branch, hits| BranchCoverage {
+            line: 3,
+            block: 0,
+            branch,
+            hits,
+        };
+
+        let function = |hits| FunctionCoverage {
+            name: "main".to_string(),
+            line: 1,
+            hits,
+        };
+
+        let file_coverage1 = FileCoverage {
+            path: "src/main.rs".to_string(),
+            hits: vec![1, 0, 1],
+            branches: vec![branch(0, 1), branch(1, 0)],
+            functions: vec![function(0)],
+            ..Default::default()
+        };
+
+        let file_coverage2 = FileCoverage {
+            path: "src/main.rs".to_string(),
+            hits: vec![1, 0, 1],
+            branches: vec![branch(0, 0), branch(1, 2)],
+            functions: vec![function(1)],
+            ..Default::default()
+        };
+
+        let file_coverage3 = FileCoverage {
+            path: "src/lib.rs".to_string(),
+            hits: vec![1, 1, 1],
+            branches: vec![branch(0, 0), branch(1, 0)],
+            ..Default::default()
+        };
+
+        let metrics = CoverageMetrics::calculate(&[file_coverage1, file_coverage2, file_coverage3]);
+
+        assert_eq!(metrics.covered_branches, 2);
+        assert_eq!(metrics.uncovered_branches, 2);
+        assert_eq!(metrics.total_branches(), 4);
+        assert_eq!(metrics.branch_coverage_percentage, 50.0);
+        assert_eq!(metrics.covered_functions, 1);
+        assert_eq!(metrics.uncovered_functions, 0);
+        assert_eq!(metrics.function_coverage_percentage, 100.0);
+    }
 }
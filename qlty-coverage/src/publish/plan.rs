@@ -1,3 +1,4 @@
+use crate::transformer::FixPaths;
 use crate::Transformer;
 use qlty_types::tests::v1::{CoverageMetadata, ReportFile};
 
@@ -7,4 +8,5 @@ pub struct Plan {
     pub report_files: Vec<ReportFile>,
     pub transformers: Vec<Box<dyn Transformer>>,
     pub skip_missing_files: bool,
+    pub(crate) path_fixer: Option<FixPaths>,
 }
@@ -4,6 +4,7 @@ use crate::publish::Settings;
 use crate::transformer::AddPrefix;
 use crate::transformer::AppendMetadata;
 use crate::transformer::ComputeSummary;
+use crate::transformer::FixPaths;
 use crate::transformer::IgnorePaths;
 use crate::transformer::StripDotSlashPrefix;
 use crate::transformer::StripPrefix;
@@ -34,19 +35,23 @@ impl Planner {
 
     pub fn compute(&self) -> Result<Plan> {
         let metadata = self.compute_metadata()?;
+        let path_fixer = FixPaths::new_from_git_root()?;
 
         Ok(Plan {
             metadata: metadata.clone(),
             report_files: self.compute_report_files()?,
-            transformers: self.compute_transformers(&metadata)?,
+            transformers: self.compute_transformers(&metadata, &path_fixer)?,
             skip_missing_files: self.settings.skip_missing_files,
+            path_fixer: Some(path_fixer),
         })
     }
 
     fn compute_metadata(&self) -> Result<CoverageMetadata> {
         let now = OffsetDateTime::now_utc();
 
-        let mut metadata = if let Some(ci) = crate::ci::current() {
+        let ci = crate::ci::current();
+
+        let mut metadata = if let Some(ci) = &ci {
             ci.metadata()
         } else {
             CoverageMetadata {
@@ -83,7 +88,20 @@ impl Planner {
             metadata.pull_request_number = pull_request_number;
         }
 
-        let commit_metadata = retrieve_commit_metadata()?;
+        // An overridden SHA is taken as-is, otherwise it may point at a pull request merge commit
+        let merge_ref = self.settings.override_commit_sha.is_none()
+            && ci.as_ref().is_some_and(|ci| ci.merge_ref());
+
+        // Metadata is left empty when the commit is not in the local clone
+        let Some(commit_metadata) = retrieve_commit_metadata(&metadata.commit_sha, merge_ref)?
+        else {
+            return Ok(metadata);
+        };
+
+        if metadata.commit_sha.is_empty() {
+            metadata.commit_sha = commit_metadata.commit_sha;
+        }
+        metadata.commit_headline = commit_metadata.commit_headline;
         metadata.commit_message = commit_metadata.commit_message;
         metadata.committer_email = commit_metadata.committer_email;
         metadata.committer_name = commit_metadata.committer_name;
@@ -127,6 +145,7 @@ impl Planner {
     fn compute_transformers(
         &self,
         metadata: &CoverageMetadata,
+        path_fixer: &FixPaths,
     ) -> Result<Vec<Box<dyn Transformer>>> {
         let mut transformers: Vec<Box<dyn Transformer>> = vec![];
 
@@ -140,16 +159,26 @@ impl Planner {
 
         transformers.push(Box::new(StripDotSlashPrefix));
 
-        if self.config.coverage.ignores.is_some() {
-            transformers.push(Box::new(IgnorePaths::new(
-                self.config.coverage.ignores.as_ref().unwrap(),
-            )?));
+        let ignore_paths = match &self.config.coverage.ignores {
+            Some(ignores) => Some(IgnorePaths::new(ignores)?),
+            None => None,
+        };
+
+        if let Some(ignore_paths) = &ignore_paths {
+            transformers.push(Box::new(ignore_paths.clone()));
         }
 
         if let Some(prefix) = self.settings.add_prefix.clone() {
             transformers.push(Box::new(AddPrefix::new(&prefix)));
         }
 
+        transformers.push(Box::new(path_fixer.clone()));
+
+        // A fixed path may only match an ignore once it has been rewritten
+        if let Some(ignore_paths) = ignore_paths {
+            transformers.push(Box::new(ignore_paths));
+        }
+
         transformers.push(Box::new(AppendMetadata::new(metadata)));
         Ok(transformers)
     }
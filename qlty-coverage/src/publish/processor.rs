@@ -1,7 +1,7 @@
 use crate::publish::{metrics::CoverageMetrics, Plan, Report, Results};
 use anyhow::Result;
 use qlty_types::tests::v1::FileCoverage;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 pub struct Processor {
@@ -61,6 +61,7 @@ impl Processor {
             }
         }
 
+        let ambiguous_files = self.ambiguous_files(&missing_files);
         let totals = CoverageMetrics::calculate(&transformed_file_coverages);
 
         Ok(Report {
@@ -69,10 +70,31 @@ impl Processor {
             file_coverages: transformed_file_coverages,
             totals,
             missing_files,
+            ambiguous_files,
             found_files,
         })
     }
 
+    fn ambiguous_files(&self, missing_files: &HashSet<String>) -> HashMap<String, Vec<String>> {
+        let path_fixer = match &self.plan.path_fixer {
+            Some(path_fixer) => path_fixer,
+            None => return HashMap::new(),
+        };
+
+        missing_files
+            .iter()
+            .filter_map(|path| {
+                let candidates = path_fixer.candidates(path);
+
+                if candidates.len() > 1 {
+                    Some((path.clone(), candidates))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     fn transform(&self, file_coverage: FileCoverage) -> Option<FileCoverage> {
         let mut file_coverage: Option<FileCoverage> = Some(file_coverage.clone());
 
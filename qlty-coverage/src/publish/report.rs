@@ -1,4 +1,7 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 use crate::export::CoverageExport;
 use crate::publish::CoverageMetrics;
@@ -18,6 +21,10 @@ pub struct Report {
     #[serde(skip_serializing)]
     pub missing_files: HashSet<String>,
 
+    /// Missing files which match more than one workspace file, with the candidates
+    #[serde(skip_serializing)]
+    pub ambiguous_files: HashMap<String, Vec<String>>,
+
     pub totals: CoverageMetrics,
 }
 
@@ -0,0 +1,209 @@
+use crate::publish::CoverageMetrics;
+use anyhow::{bail, Result};
+use globset::{Glob, GlobMatcher};
+use qlty_config::config::Coverage;
+use qlty_types::tests::v1::FileCoverage;
+use serde::Serialize;
+
+/// Minimum line coverage requirements for a whole report and for the files
+/// matching path globs, as configured under `[coverage]` in qlty.toml.
+#[derive(Debug, Clone, Default)]
+pub struct ThresholdChecker {
+    minimum: Option<f64>,
+    globs: Vec<(String, GlobMatcher, f64)>,
+}
+
+impl ThresholdChecker {
+    /// The total minimum from the command line takes precedence over the config.
+    pub fn new(config: &Coverage, minimum: Option<f64>) -> Result<Self> {
+        let minimum = minimum.or(config.minimum);
+
+        if let Some(minimum) = minimum {
+            Self::validate_minimum("total coverage", minimum)?;
+        }
+
+        let mut globs = vec![];
+
+        for threshold in &config.thresholds {
+            Self::validate_minimum(&threshold.path, threshold.minimum)?;
+            let matcher = Glob::new(&threshold.path)?.compile_matcher();
+            globs.push((threshold.path.clone(), matcher, threshold.minimum));
+        }
+
+        Ok(Self { minimum, globs })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.minimum.is_none() && self.globs.is_empty()
+    }
+
+    pub fn check(&self, file_coverages: &[FileCoverage]) -> ThresholdResults {
+        let mut results = vec![];
+
+        if let Some(minimum) = self.minimum {
+            results.push(ThresholdResult::new(None, minimum, file_coverages));
+        }
+
+        for (path, matcher, minimum) in &self.globs {
+            let matching: Vec<_> = file_coverages
+                .iter()
+                .filter(|file_coverage| matcher.is_match(&file_coverage.path))
+                .cloned()
+                .collect();
+
+            results.push(ThresholdResult::new(Some(path), *minimum, &matching));
+        }
+
+        ThresholdResults { results }
+    }
+
+    fn validate_minimum(name: &str, minimum: f64) -> Result<()> {
+        if !(0.0..=100.0).contains(&minimum) {
+            bail!(
+                "The minimum for {} must be between 0 and 100, got {}",
+                name,
+                minimum
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ThresholdResults {
+    pub results: Vec<ThresholdResult>,
+}
+
+impl ThresholdResults {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|result| result.passed)
+    }
+
+    pub fn failures(&self) -> Vec<&ThresholdResult> {
+        self.results
+            .iter()
+            .filter(|result| !result.passed)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ThresholdResult {
+    /// The glob the result applies to, or none for the whole report
+    pub path: Option<String>,
+    pub minimum: f64,
+    pub covered_lines: u64,
+    pub coverable_lines: u64,
+    pub coverage_percentage: f64,
+    pub passed: bool,
+}
+
+impl ThresholdResult {
+    fn new(path: Option<&str>, minimum: f64, file_coverages: &[FileCoverage]) -> Self {
+        let metrics = CoverageMetrics::calculate(file_coverages);
+        let coverable_lines = metrics.covered_lines + metrics.uncovered_lines;
+
+        Self {
+            path: path.map(str::to_string),
+            minimum,
+            covered_lines: metrics.covered_lines,
+            coverable_lines,
+            coverage_percentage: metrics.coverage_percentage,
+            // Globs matching no coverable lines have nothing to enforce
+            passed: coverable_lines == 0 || metrics.coverage_percentage >= minimum,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qlty_config::config::CoverageThreshold;
+
+    fn file_coverage(path: &str, hits: Vec<i64>) -> FileCoverage {
+        FileCoverage {
+            path: path.to_string(),
+            hits,
+            ..Default::default()
+        }
+    }
+
+    fn config(minimum: Option<f64>, thresholds: &[(&str, f64)]) -> Coverage {
+        Coverage {
+            minimum,
+            thresholds: thresholds
+                .iter()
+                .map(|(path, minimum)| CoverageThreshold {
+                    path: path.to_string(),
+                    minimum: *minimum,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn file_coverages() -> Vec<FileCoverage> {
+        vec![
+            file_coverage("src/core/a.rs", vec![1, 1, 1, 0, -1]),
+            file_coverage("src/api/b.rs", vec![1, 0, 0, 0]),
+            file_coverage("lib/c.rs", vec![1, 1]),
+        ]
+    }
+
+    #[test]
+    fn test_check_total() {
+        let checker = ThresholdChecker::new(&config(Some(60.0), &[]), None).unwrap();
+        let results = checker.check(&file_coverages());
+
+        assert!(results.passed());
+        assert_eq!(results.results.len(), 1);
+        assert_eq!(results.results[0].path, None);
+        assert_eq!(results.results[0].covered_lines, 6);
+        assert_eq!(results.results[0].coverable_lines, 10);
+
+        let checker = ThresholdChecker::new(&config(Some(60.0), &[]), Some(70.0)).unwrap();
+        let results = checker.check(&file_coverages());
+
+        assert!(!results.passed());
+        assert_eq!(results.results[0].minimum, 70.0);
+    }
+
+    #[test]
+    fn test_check_globs() {
+        let checker = ThresholdChecker::new(
+            &config(
+                None,
+                &[
+                    ("src/core/**", 75.0),
+                    ("src/api/**", 50.0),
+                    ("docs/**", 90.0),
+                ],
+            ),
+            None,
+        )
+        .unwrap();
+        let results = checker.check(&file_coverages());
+
+        assert!(!results.passed());
+        assert_eq!(results.results.len(), 3);
+
+        let failures = results.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path.as_deref(), Some("src/api/**"));
+        assert_eq!(failures[0].coverage_percentage, 25.0);
+
+        // No files match docs/**, so there is nothing to enforce
+        assert!(results.results[2].passed);
+        assert_eq!(results.results[2].coverable_lines, 0);
+    }
+
+    #[test]
+    fn test_invalid_minimum() {
+        assert!(ThresholdChecker::new(&config(Some(101.0), &[]), None).is_err());
+        assert!(ThresholdChecker::new(&config(None, &[("src/**", -1.0)]), None).is_err());
+        assert!(ThresholdChecker::new(&config(None, &[]), None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -3,6 +3,6 @@ use qlty_types::tests::v1::ReportFile;
 
 #[derive(Debug, Clone, Default)]
 pub struct Plan {
-    pub report_file: ReportFile,
+    pub report_files: Vec<ReportFile>,
     pub transformers: Vec<Box<dyn Transformer>>,
 }
@@ -22,20 +22,25 @@ impl Planner {
 
     pub fn compute(&self) -> Result<Plan> {
         Ok(Plan {
-            report_file: self.compute_report_file()?,
+            report_files: self.compute_report_files()?,
             transformers: self.compute_transformers()?,
         })
     }
 
-    fn compute_report_file(&self) -> Result<ReportFile> {
-        let (path, format) =
-            extract_path_and_format(&self.settings.path, self.settings.report_format)?;
+    fn compute_report_files(&self) -> Result<Vec<ReportFile>> {
+        let mut report_files = vec![];
 
-        Ok(ReportFile {
-            path: path_to_string(path),
-            format: format.to_string(),
-            ..Default::default()
-        })
+        for path in &self.settings.paths {
+            let (path, format) = extract_path_and_format(path, self.settings.report_format)?;
+
+            report_files.push(ReportFile {
+                path: path_to_string(path),
+                format: format.to_string(),
+                ..Default::default()
+            });
+        }
+
+        Ok(report_files)
     }
 
     fn compute_transformers(&self) -> Result<Vec<Box<dyn Transformer>>> {
@@ -1,7 +1,8 @@
 use crate::formats::{parser_for, Formats};
+use crate::merge::merge;
 use crate::transform::Plan;
 use anyhow::{bail, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{FileCoverage, ReportFile};
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -14,9 +15,24 @@ impl Processor {
         Self { plan: plan.clone() }
     }
 
+    /// Parses and transforms each report file. When there are several
+    /// report files, coverage for the same path is merged into one record.
     pub fn compute(&self) -> Result<Vec<FileCoverage>> {
-        // let mut file_coverages = vec![];
-        let report_file_path = PathBuf::from(&self.plan.report_file.path);
+        let mut file_coverages = vec![];
+
+        for report_file in &self.plan.report_files {
+            file_coverages.extend(self.compute_report_file(report_file)?);
+        }
+
+        if self.plan.report_files.len() > 1 {
+            Ok(merge(file_coverages))
+        } else {
+            Ok(file_coverages)
+        }
+    }
+
+    fn compute_report_file(&self, report_file: &ReportFile) -> Result<Vec<FileCoverage>> {
+        let report_file_path = PathBuf::from(&report_file.path);
 
         if !report_file_path.exists() {
             bail!(
@@ -25,7 +41,7 @@ impl Processor {
             );
         }
 
-        let format = Formats::from_str(&self.plan.report_file.format)?;
+        let format = Formats::from_str(&report_file.format)?;
         let parser = parser_for(&format);
         let file_coverages = parser.parse_file(&report_file_path)?;
 
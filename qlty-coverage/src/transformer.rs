@@ -1,8 +1,14 @@
 use anyhow::Result;
 use globset::{Glob, GlobSet, GlobSetBuilder};
+use qlty_analysis::utils::fs::path_to_string;
+use qlty_analysis::{AllSource, FileMatcher, WorkspaceEntryFinder};
 use qlty_config::Workspace;
 use qlty_types::tests::v1::{CoverageMetadata, CoverageSummary, FileCoverage};
+use std::collections::HashMap;
+use std::path::{Component, Path};
+use std::sync::{Arc, OnceLock};
 use std::{fmt::Debug, path::PathBuf};
+use tracing::{debug, warn};
 
 pub trait Transformer: Debug + Send + Sync + 'static {
     fn transform(&self, file_coverage: FileCoverage) -> Option<FileCoverage>;
@@ -22,6 +28,27 @@ impl ComputeSummary {
     pub fn new() -> Self {
         Self {}
     }
+
+    // Branches and functions have no omitted state: they were either hit or not
+    fn summarize(hits: impl Iterator<Item = i64>) -> CoverageSummary {
+        let mut covered = 0;
+        let mut missed = 0;
+
+        for hit in hits {
+            if hit > 0 {
+                covered += 1;
+            } else {
+                missed += 1;
+            }
+        }
+
+        CoverageSummary {
+            covered,
+            missed,
+            omit: 0,
+            total: covered + missed,
+        }
+    }
 }
 
 impl Transformer for ComputeSummary {
@@ -47,6 +74,18 @@ impl Transformer for ComputeSummary {
             total: covered + missed + omit,
         });
 
+        if !file_coverage.branches.is_empty() {
+            file_coverage.branch_summary = Some(Self::summarize(
+                file_coverage.branches.iter().map(|branch| branch.hits),
+            ));
+        }
+
+        if !file_coverage.functions.is_empty() {
+            file_coverage.function_summary = Some(Self::summarize(
+                file_coverage.functions.iter().map(|function| function.hits),
+            ));
+        }
+
         Some(file_coverage)
     }
 
@@ -200,9 +239,225 @@ impl Transformer for StripDotSlashPrefix {
     }
 }
 
+/// Rewrites paths which don't exist in the workspace to the unique workspace
+/// file sharing the longest path suffix, e.g. `/app/src/lib.rb` to `src/lib.rb`.
+#[derive(Debug, Clone)]
+pub struct FixPaths {
+    root: PathBuf,
+    index: Arc<OnceLock<PathIndex>>,
+}
+
+#[derive(Debug, Default)]
+struct PathIndex {
+    files_by_name: HashMap<String, Vec<String>>,
+}
+
+const MIN_MATCHING_COMPONENTS: usize = 2;
+
+impl FixPaths {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            index: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn new_from_git_root() -> Result<Self> {
+        Ok(Self::new(Workspace::assert_within_git_directory()?))
+    }
+
+    /// Workspace files which share the longest path suffix with `path`. The
+    /// file name and at least its parent directory must match, as files which
+    /// only share a name (such as `vendor/lib.rb` and `src/lib.rb`) are unrelated.
+    pub fn candidates(&self, path: &str) -> Vec<String> {
+        let components = Self::components(path);
+
+        let file_name = match components.last() {
+            Some(file_name) => file_name,
+            None => return vec![],
+        };
+
+        let files = match self.index().files_by_name.get(*file_name) {
+            Some(files) => files,
+            None => return vec![],
+        };
+
+        let mut longest = 0;
+        let mut candidates = vec![];
+
+        for file in files {
+            let matching = Self::components(file)
+                .iter()
+                .rev()
+                .zip(components.iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+
+            if matching < MIN_MATCHING_COMPONENTS {
+                continue;
+            }
+
+            if matching > longest {
+                longest = matching;
+                candidates.clear();
+            }
+
+            if matching == longest {
+                candidates.push(file.clone());
+            }
+        }
+
+        candidates.sort();
+        candidates
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.root.join(path).exists()
+    }
+
+    fn components(path: &str) -> Vec<&str> {
+        Path::new(path)
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Walking the workspace is deferred until a path is actually missing
+    fn index(&self) -> &PathIndex {
+        self.index.get_or_init(|| {
+            let mut index = PathIndex::default();
+            let mut finder = WorkspaceEntryFinder::new(
+                Arc::new(AllSource::new(self.root.clone())),
+                Box::new(FileMatcher),
+            );
+
+            match finder.workspace_entries() {
+                Ok(workspace_entries) => {
+                    for workspace_entry in workspace_entries {
+                        if let Some(file_name) = workspace_entry.path.file_name() {
+                            index
+                                .files_by_name
+                                .entry(file_name.to_string_lossy().to_string())
+                                .or_default()
+                                .push(path_to_string(&workspace_entry.path));
+                        }
+                    }
+                }
+                Err(err) => warn!("Unable to list workspace files to fix paths: {}", err),
+            }
+
+            index
+        })
+    }
+}
+
+impl Transformer for FixPaths {
+    fn transform(&self, file_coverage: FileCoverage) -> Option<FileCoverage> {
+        if self.exists(&file_coverage.path) {
+            return Some(file_coverage);
+        }
+
+        let mut file_coverage = file_coverage;
+        let candidates = self.candidates(&file_coverage.path);
+
+        if candidates.len() == 1 {
+            debug!("Fixed path {} to {}", file_coverage.path, candidates[0]);
+            file_coverage.path = candidates[0].clone();
+        } else if candidates.len() > 1 {
+            debug!(
+                "Unable to fix path {}, it matches {} files",
+                file_coverage.path,
+                candidates.len()
+            );
+        }
+
+        Some(file_coverage)
+    }
+
+    fn clone_box(&self) -> Box<dyn Transformer> {
+        Box::new(self.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qlty_types::tests::v1::{BranchCoverage, FunctionCoverage};
+
+    #[test]
+    fn test_compute_summary_transformer() {
+        let file_coverage = FileCoverage {
+            path: "lib/file.rb".to_string(),
+            hits: vec![-1, 1, 0, 3],
+            branches: vec![
+                BranchCoverage {
+                    line: 2,
+                    block: 0,
+                    branch: 0,
+                    hits: 2,
+                },
+                BranchCoverage {
+                    line: 2,
+                    block: 0,
+                    branch: 1,
+                    hits: 0,
+                },
+            ],
+            functions: vec![FunctionCoverage {
+                name: "call".to_string(),
+                line: 1,
+                hits: 1,
+            }],
+            ..Default::default()
+        };
+
+        let file_coverage = ComputeSummary::new().transform(file_coverage).unwrap();
+
+        assert_eq!(
+            file_coverage.summary,
+            Some(CoverageSummary {
+                covered: 2,
+                missed: 1,
+                omit: 1,
+                total: 4,
+            })
+        );
+        assert_eq!(
+            file_coverage.branch_summary,
+            Some(CoverageSummary {
+                covered: 1,
+                missed: 1,
+                omit: 0,
+                total: 2,
+            })
+        );
+        assert_eq!(
+            file_coverage.function_summary,
+            Some(CoverageSummary {
+                covered: 1,
+                missed: 0,
+                omit: 0,
+                total: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_compute_summary_transformer_without_branches() {
+        let file_coverage = FileCoverage {
+            path: "lib/file.rb".to_string(),
+            hits: vec![1],
+            ..Default::default()
+        };
+
+        let file_coverage = ComputeSummary::new().transform(file_coverage).unwrap();
+
+        assert!(file_coverage.branch_summary.is_none());
+        assert!(file_coverage.function_summary.is_none());
+    }
 
     #[test]
     fn test_strip_prefix_transformer() {
@@ -223,4 +478,77 @@ mod tests {
         let file_coverage = transformer.transform(file_coverage).unwrap();
         assert_eq!(file_coverage.path, "app/deep/nested/file.rb".to_string());
     }
+
+    fn fix_paths_workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+
+        for path in [
+            "src/lib.rb",
+            "packages/api/src/index.js",
+            "packages/web/src/index.js",
+            "packages/web/src/app.js",
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        dir
+    }
+
+    fn fix_path(transformer: &FixPaths, path: &str) -> String {
+        let file_coverage = FileCoverage {
+            path: path.to_string(),
+            ..Default::default()
+        };
+
+        transformer.transform(file_coverage).unwrap().path
+    }
+
+    #[test]
+    fn test_fix_paths_transformer() {
+        let dir = fix_paths_workspace();
+        let transformer = FixPaths::new(dir.path().to_path_buf());
+
+        // Present paths are left alone
+        assert_eq!(fix_path(&transformer, "src/lib.rb"), "src/lib.rb");
+
+        // Absolute paths from inside a container
+        assert_eq!(fix_path(&transformer, "/app/src/lib.rb"), "src/lib.rb");
+
+        // Paths relative to a package root
+        assert_eq!(
+            fix_path(&transformer, "src/app.js"),
+            "packages/web/src/app.js"
+        );
+        assert_eq!(
+            fix_path(&transformer, "/build/api/src/index.js"),
+            "packages/api/src/index.js"
+        );
+
+        // Ambiguous and unmatched paths are left alone
+        assert_eq!(fix_path(&transformer, "src/index.js"), "src/index.js");
+        assert_eq!(fix_path(&transformer, "lib/missing.rb"), "lib/missing.rb");
+
+        // Matching only the file name is not enough
+        assert_eq!(fix_path(&transformer, "vendor/lib.rb"), "vendor/lib.rb");
+        assert_eq!(fix_path(&transformer, "lib.rb"), "lib.rb");
+    }
+
+    #[test]
+    fn test_fix_paths_candidates() {
+        let dir = fix_paths_workspace();
+        let transformer = FixPaths::new(dir.path().to_path_buf());
+
+        assert_eq!(
+            transformer.candidates("/app/src/index.js"),
+            vec!["packages/api/src/index.js", "packages/web/src/index.js"]
+        );
+        assert_eq!(
+            transformer.candidates("web/src/index.js"),
+            vec!["packages/web/src/index.js"]
+        );
+        assert!(transformer.candidates("missing.rb").is_empty());
+        assert!(transformer.candidates("/app/vendor/lib.rb").is_empty());
+    }
 }
@@ -5,7 +5,7 @@ use std::str::FromStr;
 
 // Format specification priority:
 // 1. The format specified in the path, e.g.: simplecov:./coverage/coverage.json
-// 2. The format specified in the command line arguments, through --report-format simplecov
+// 2. The format specified in the command line arguments, through --format simplecov
 // 3. The format is inferred from the file extension or contents
 pub fn extract_path_and_format(
     path: &str,
@@ -80,4 +80,55 @@ mod tests {
         assert_eq!(result.0, PathBuf::from(path));
         assert_eq!(result.1, Formats::Clover);
     }
+
+    #[test]
+    fn test_infer_format_from_contents() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+        for (fixture, format) in [
+            ("simplecov/sample.json", Formats::Simplecov),
+            ("istanbul/coverage-final.json", Formats::Istanbul),
+            ("coveragepy/coverage.json", Formats::Coveragepy),
+            ("llvm/sample.json", Formats::Llvm),
+            ("opencover/sample.xml", Formats::Opencover),
+            ("cobertura/sample.xml", Formats::Cobertura),
+            ("clover/sample.xml", Formats::Clover),
+            ("jacoco/sample.xml", Formats::Jacoco),
+            ("lcov/sample.lcov", Formats::Lcov),
+            ("coverprofile/sample.out", Formats::Coverprofile),
+        ] {
+            let path = fixtures.join(fixture);
+            let result = extract_path_and_format(path.to_str().unwrap(), None).unwrap();
+            assert_eq!(result.1, format, "{}", fixture);
+        }
+    }
+
+    #[test]
+    fn test_infer_format_ignores_misleading_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("coverage.xml");
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        std::fs::copy(fixtures.join("jacoco/sample.xml"), &path).unwrap();
+
+        let result = extract_path_and_format(path.to_str().unwrap(), None).unwrap();
+        assert_eq!(result.1, Formats::Jacoco);
+    }
+
+    #[test]
+    fn test_infer_format_ambiguous() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("coverage.xml");
+        std::fs::write(&path, "<coverage></coverage>").unwrap();
+
+        let error = extract_path_and_format(path.to_str().unwrap(), None).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("it could be any of: clover, cobertura"));
+
+        let named = dir.path().join("clover.xml");
+        std::fs::write(&named, "<coverage></coverage>").unwrap();
+
+        let result = extract_path_and_format(named.to_str().unwrap(), None).unwrap();
+        assert_eq!(result.1, Formats::Clover);
+    }
 }
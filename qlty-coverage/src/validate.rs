@@ -90,7 +90,7 @@ mod tests {
     use super::*;
     use qlty_analysis::utils::fs::path_to_string;
     use qlty_types::tests::v1::{CoverageMetadata, FileCoverage, ReportFile};
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::fs::{self, File};
     use tempfile::tempdir;
 
@@ -103,6 +103,7 @@ mod tests {
             file_coverages,
             found_files: HashSet::new(),
             missing_files: HashSet::new(),
+            ambiguous_files: HashMap::new(),
             totals: Default::default(),
         }
     }
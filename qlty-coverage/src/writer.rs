@@ -0,0 +1,45 @@
+use qlty_formats::{Formatter, JsonEachRowFormatter};
+use qlty_types::tests::v1::FileCoverage;
+use serde::{Deserialize, Serialize};
+
+mod cobertura;
+mod lcov;
+
+pub use cobertura::CoberturaWriter;
+pub use lcov::LcovWriter;
+
+#[derive(clap::ValueEnum, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormats {
+    #[default]
+    Qlty,
+    Lcov,
+    Cobertura,
+}
+
+impl OutputFormats {
+    pub fn default_filename(&self) -> &'static str {
+        match self {
+            OutputFormats::Qlty => "coverage.jsonl",
+            OutputFormats::Lcov => "lcov.info",
+            OutputFormats::Cobertura => "cobertura.xml",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            OutputFormats::Qlty => write!(f, "qlty"),
+            OutputFormats::Lcov => write!(f, "lcov"),
+            OutputFormats::Cobertura => write!(f, "cobertura"),
+        }
+    }
+}
+
+pub fn writer_for(format: OutputFormats, file_coverages: Vec<FileCoverage>) -> Box<dyn Formatter> {
+    match format {
+        OutputFormats::Qlty => JsonEachRowFormatter::boxed(file_coverages),
+        OutputFormats::Lcov => Box::new(LcovWriter::new(file_coverages)),
+        OutputFormats::Cobertura => Box::new(CoberturaWriter::new(file_coverages)),
+    }
+}
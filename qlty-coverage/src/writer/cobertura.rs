@@ -0,0 +1,279 @@
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+const DTD: &str = "http://cobertura.sourceforge.net/xml/coverage-04.dtd";
+
+/// Writes file coverages as a Cobertura XML report, with one class per file
+#[derive(Debug, Clone)]
+pub struct CoberturaWriter {
+    file_coverages: Vec<FileCoverage>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counts {
+    lines_covered: usize,
+    lines_valid: usize,
+    branches_covered: usize,
+    branches_valid: usize,
+}
+
+impl Counts {
+    fn for_file(file_coverage: &FileCoverage) -> Self {
+        let lines = file_coverage.hits.iter().filter(|hits| **hits >= 0);
+
+        Self {
+            lines_covered: lines.clone().filter(|hits| **hits > 0).count(),
+            lines_valid: lines.count(),
+            branches_covered: file_coverage
+                .branches
+                .iter()
+                .filter(|branch| branch.hits > 0)
+                .count(),
+            branches_valid: file_coverage.branches.len(),
+        }
+    }
+
+    fn add(&mut self, other: Counts) {
+        self.lines_covered += other.lines_covered;
+        self.lines_valid += other.lines_valid;
+        self.branches_covered += other.branches_covered;
+        self.branches_valid += other.branches_valid;
+    }
+
+    fn line_rate(&self) -> String {
+        rate(self.lines_covered, self.lines_valid)
+    }
+
+    fn branch_rate(&self) -> String {
+        rate(self.branches_covered, self.branches_valid)
+    }
+}
+
+impl CoberturaWriter {
+    pub fn new(file_coverages: Vec<FileCoverage>) -> Self {
+        Self { file_coverages }
+    }
+
+    fn write_class(writer: &mut dyn Write, file_coverage: &FileCoverage) -> std::io::Result<()> {
+        let counts = Counts::for_file(file_coverage);
+        let filename = escape(&file_coverage.path);
+
+        writeln!(
+            writer,
+            r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            filename,
+            filename,
+            counts.line_rate(),
+            counts.branch_rate()
+        )?;
+
+        writeln!(writer, "          <methods>")?;
+
+        for function in &file_coverage.functions {
+            writeln!(
+                writer,
+                r#"            <method name="{}" signature="" line-rate="{}" branch-rate="0" complexity="0">"#,
+                escape(&function.name),
+                if function.hits > 0 { "1" } else { "0" }
+            )?;
+            writeln!(writer, "              <lines>")?;
+            writeln!(
+                writer,
+                r#"                <line number="{}" hits="{}" branch="false"/>"#,
+                function.line, function.hits
+            )?;
+            writeln!(writer, "              </lines>")?;
+            writeln!(writer, "            </method>")?;
+        }
+
+        writeln!(writer, "          </methods>")?;
+        writeln!(writer, "          <lines>")?;
+
+        let mut branches_by_line: BTreeMap<u32, Vec<&BranchCoverage>> = BTreeMap::new();
+
+        for branch in &file_coverage.branches {
+            branches_by_line
+                .entry(branch.line)
+                .or_default()
+                .push(branch);
+        }
+
+        for (index, hits) in file_coverage.hits.iter().enumerate() {
+            if *hits < 0 {
+                continue;
+            }
+
+            let line_number = index as u32 + 1;
+
+            match branches_by_line.get(&line_number) {
+                Some(branches) => {
+                    let covered = branches.iter().filter(|branch| branch.hits > 0).count();
+                    let total = branches.len();
+
+                    writeln!(
+                        writer,
+                        r#"            <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                        line_number,
+                        hits,
+                        covered * 100 / total,
+                        covered,
+                        total
+                    )?;
+                }
+                None => {
+                    writeln!(
+                        writer,
+                        r#"            <line number="{}" hits="{}" branch="false"/>"#,
+                        line_number, hits
+                    )?;
+                }
+            }
+        }
+
+        writeln!(writer, "          </lines>")?;
+        writeln!(writer, "        </class>")
+    }
+}
+
+impl Formatter for CoberturaWriter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        let mut totals = Counts::default();
+
+        for file_coverage in &self.file_coverages {
+            totals.add(Counts::for_file(file_coverage));
+        }
+
+        writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(writer, r#"<!DOCTYPE coverage SYSTEM "{}">"#, DTD)?;
+        writeln!(
+            writer,
+            r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="qlty" timestamp="0">"#,
+            totals.line_rate(),
+            totals.branch_rate(),
+            totals.lines_covered,
+            totals.lines_valid,
+            totals.branches_covered,
+            totals.branches_valid
+        )?;
+        writeln!(writer, "  <sources>")?;
+        writeln!(writer, "    <source>.</source>")?;
+        writeln!(writer, "  </sources>")?;
+        writeln!(writer, "  <packages>")?;
+        writeln!(
+            writer,
+            r#"    <package name="." line-rate="{}" branch-rate="{}" complexity="0">"#,
+            totals.line_rate(),
+            totals.branch_rate()
+        )?;
+        writeln!(writer, "      <classes>")?;
+
+        for file_coverage in &self.file_coverages {
+            Self::write_class(writer, file_coverage)?;
+        }
+
+        writeln!(writer, "      </classes>")?;
+        writeln!(writer, "    </package>")?;
+        writeln!(writer, "  </packages>")?;
+        writeln!(writer, "</coverage>")?;
+
+        Ok(())
+    }
+}
+
+fn rate(covered: usize, total: usize) -> String {
+    if total == 0 {
+        "0".to_string()
+    } else {
+        format!("{:.4}", covered as f64 / total as f64)
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Cobertura;
+    use crate::Parser;
+    use qlty_types::tests::v1::FunctionCoverage;
+
+    #[test]
+    fn test_round_trip() {
+        let file_coverages = vec![FileCoverage {
+            path: "src/a&b.rs".to_string(),
+            hits: vec![-1, 2, 0, 1],
+            branches: vec![
+                BranchCoverage {
+                    line: 2,
+                    block: 0,
+                    branch: 0,
+                    hits: 1,
+                },
+                BranchCoverage {
+                    line: 2,
+                    block: 0,
+                    branch: 1,
+                    hits: 0,
+                },
+            ],
+            functions: vec![FunctionCoverage {
+                name: "run".to_string(),
+                line: 2,
+                hits: 2,
+            }],
+            ..Default::default()
+        }];
+
+        let output = CoberturaWriter::new(file_coverages.clone()).read().unwrap();
+        let reparsed = Cobertura::new()
+            .parse_text(&String::from_utf8(output).unwrap())
+            .unwrap();
+
+        assert_eq!(reparsed, file_coverages);
+    }
+
+    #[test]
+    fn test_write() {
+        let file_coverages = vec![FileCoverage {
+            path: "src/lib.rs".to_string(),
+            hits: vec![1, 0, -1, 4],
+            ..Default::default()
+        }];
+
+        let output = CoberturaWriter::new(file_coverages).read().unwrap();
+
+        insta::assert_snapshot!(String::from_utf8(output).unwrap(), @r#"
+        <?xml version="1.0" ?>
+        <!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+        <coverage line-rate="0.6667" branch-rate="0" lines-covered="2" lines-valid="3" branches-covered="0" branches-valid="0" complexity="0" version="qlty" timestamp="0">
+          <sources>
+            <source>.</source>
+          </sources>
+          <packages>
+            <package name="." line-rate="0.6667" branch-rate="0" complexity="0">
+              <classes>
+                <class name="src/lib.rs" filename="src/lib.rs" line-rate="0.6667" branch-rate="0" complexity="0">
+                  <methods>
+                  </methods>
+                  <lines>
+                    <line number="1" hits="1" branch="false"/>
+                    <line number="2" hits="0" branch="false"/>
+                    <line number="4" hits="4" branch="false"/>
+                  </lines>
+                </class>
+              </classes>
+            </package>
+          </packages>
+        </coverage>
+        "#);
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::tests::v1::FileCoverage;
+use std::io::Write;
+
+/// Writes file coverages as an lcov tracefile
+#[derive(Debug, Clone)]
+pub struct LcovWriter {
+    file_coverages: Vec<FileCoverage>,
+}
+
+impl LcovWriter {
+    pub fn new(file_coverages: Vec<FileCoverage>) -> Self {
+        Self { file_coverages }
+    }
+
+    fn write_file_coverage(
+        writer: &mut dyn Write,
+        file_coverage: &FileCoverage,
+    ) -> std::io::Result<()> {
+        writeln!(writer, "TN:")?;
+        writeln!(writer, "SF:{}", file_coverage.path)?;
+
+        for function in &file_coverage.functions {
+            writeln!(writer, "FN:{},{}", function.line, function.name)?;
+        }
+
+        for function in &file_coverage.functions {
+            writeln!(writer, "FNDA:{},{}", function.hits, function.name)?;
+        }
+
+        if !file_coverage.functions.is_empty() {
+            let hit = file_coverage
+                .functions
+                .iter()
+                .filter(|function| function.hits > 0)
+                .count();
+
+            writeln!(writer, "FNF:{}", file_coverage.functions.len())?;
+            writeln!(writer, "FNH:{}", hit)?;
+        }
+
+        for branch in &file_coverage.branches {
+            writeln!(
+                writer,
+                "BRDA:{},{},{},{}",
+                branch.line, branch.block, branch.branch, branch.hits
+            )?;
+        }
+
+        if !file_coverage.branches.is_empty() {
+            let hit = file_coverage
+                .branches
+                .iter()
+                .filter(|branch| branch.hits > 0)
+                .count();
+
+            writeln!(writer, "BRF:{}", file_coverage.branches.len())?;
+            writeln!(writer, "BRH:{}", hit)?;
+        }
+
+        let mut found = 0;
+        let mut hit = 0;
+
+        for (index, hits) in file_coverage.hits.iter().enumerate() {
+            if *hits < 0 {
+                continue;
+            }
+
+            found += 1;
+
+            if *hits > 0 {
+                hit += 1;
+            }
+
+            writeln!(writer, "DA:{},{}", index + 1, hits)?;
+        }
+
+        writeln!(writer, "LF:{}", found)?;
+        writeln!(writer, "LH:{}", hit)?;
+        writeln!(writer, "end_of_record")
+    }
+}
+
+impl Formatter for LcovWriter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        for file_coverage in &self.file_coverages {
+            Self::write_file_coverage(writer, file_coverage)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Lcov;
+    use crate::Parser;
+
+    #[test]
+    fn test_round_trip() {
+        let input = include_str!("../../tests/fixtures/lcov/sample.lcov");
+        let file_coverages = Lcov::new().parse_text(input).unwrap();
+
+        let output = LcovWriter::new(file_coverages.clone()).read().unwrap();
+        let reparsed = Lcov::new()
+            .parse_text(&String::from_utf8(output).unwrap())
+            .unwrap();
+
+        assert_eq!(reparsed, file_coverages);
+    }
+
+    #[test]
+    fn test_write() {
+        let file_coverages = vec![FileCoverage {
+            path: "src/lib.rs".to_string(),
+            hits: vec![1, 0, -1, 4],
+            ..Default::default()
+        }];
+
+        let output = LcovWriter::new(file_coverages).read().unwrap();
+
+        insta::assert_snapshot!(String::from_utf8(output).unwrap(), @"
+        TN:
+        SF:src/lib.rs
+        DA:1,1
+        DA:2,0
+        DA:4,4
+        LF:3
+        LH:2
+        end_of_record
+        ");
+    }
+}
@@ -13,6 +13,7 @@ interface Issue {
   ruleKey: string;
   path: string;
   message: string;
+  fingerprint?: string;
 }
 
 const execFilePromise = util.promisify(execFile);
@@ -200,7 +201,7 @@ export class QltyDriver {
   }
 
   async runCheck() {
-    const fullArgs = `check --all --json --no-fail --no-cache --no-progress --filter=${this.linterName}`;
+    const fullArgs = `check --all --format=json --no-fail --no-cache --no-progress --filter=${this.linterName}`;
 
     let output = { stdout: "", stderr: "" };
     let exitCode = 0;
@@ -229,6 +230,13 @@ export class QltyDriver {
       /* empty */
     }
 
+    // fingerprints hash the snippet and message, which may contain the sandbox path
+    if (Array.isArray(outputJson)) {
+      outputJson.forEach((issue: Issue) => {
+        delete issue.fingerprint;
+      });
+    }
+
     return this.parseRunResult({
       exitCode,
       outputJson,
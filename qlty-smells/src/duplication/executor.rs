@@ -5,6 +5,7 @@ use super::Plan;
 use qlty_analysis::utils::fs::path_to_string;
 use qlty_analysis::{
     code::{File, NodeFilterBuilder, Visitor},
+    fingerprint::issue_fingerprint,
     Report,
 };
 use qlty_types::analysis::v1::{Category, Issue, Level, Location, Range};
@@ -240,6 +241,7 @@ impl Executor {
                 issue.set_property_string("structural_hash", format!("{:x}", structural_hash));
                 issue.set_property_number("mass", first_node.node.mass as f64);
                 issue.set_property_bool("identical", identical);
+                issue.fingerprint = issue_fingerprint(&issue);
 
                 issues.push(issue);
             }
@@ -1,5 +1,6 @@
 use super::{checks, Plan};
 use qlty_analysis::code::File;
+use qlty_analysis::fingerprint::issue_fingerprint;
 use qlty_analysis::Report;
 use qlty_types::analysis::v1::Issue;
 use rayon::prelude::*;
@@ -92,6 +93,7 @@ impl Executor {
 
         for issue in &mut issues {
             issue.mode = language.issue_mode as i32;
+            issue.fingerprint = issue_fingerprint(issue);
         }
 
         issues
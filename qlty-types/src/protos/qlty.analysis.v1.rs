@@ -554,6 +554,7 @@ pub enum Category {
     Lint = 13,
     Secret = 14,
     DependencyAlert = 15,
+    Coverage = 16,
 }
 impl Category {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -578,6 +579,7 @@ impl Category {
             Self::Lint => "CATEGORY_LINT",
             Self::Secret => "CATEGORY_SECRET",
             Self::DependencyAlert => "CATEGORY_DEPENDENCY_ALERT",
+            Self::Coverage => "CATEGORY_COVERAGE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -599,6 +601,7 @@ impl Category {
             "CATEGORY_LINT" => Some(Self::Lint),
             "CATEGORY_SECRET" => Some(Self::Secret),
             "CATEGORY_DEPENDENCY_ALERT" => Some(Self::DependencyAlert),
+            "CATEGORY_COVERAGE" => Some(Self::Coverage),
             _ => None,
         }
     }
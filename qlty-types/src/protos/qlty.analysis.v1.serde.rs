@@ -96,6 +96,7 @@ impl serde::Serialize for Category {
             Self::Lint => "CATEGORY_LINT",
             Self::Secret => "CATEGORY_SECRET",
             Self::DependencyAlert => "CATEGORY_DEPENDENCY_ALERT",
+            Self::Coverage => "CATEGORY_COVERAGE",
         };
         serializer.serialize_str(variant)
     }
@@ -123,6 +124,7 @@ impl<'de> serde::Deserialize<'de> for Category {
             "CATEGORY_LINT",
             "CATEGORY_SECRET",
             "CATEGORY_DEPENDENCY_ALERT",
+            "CATEGORY_COVERAGE",
         ];
 
         struct GeneratedVisitor;
@@ -179,6 +181,7 @@ impl<'de> serde::Deserialize<'de> for Category {
                     "CATEGORY_LINT" => Ok(Category::Lint),
                     "CATEGORY_SECRET" => Ok(Category::Secret),
                     "CATEGORY_DEPENDENCY_ALERT" => Ok(Category::DependencyAlert),
+                    "CATEGORY_COVERAGE" => Ok(Category::Coverage),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
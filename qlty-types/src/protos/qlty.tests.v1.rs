@@ -134,6 +134,14 @@ pub struct FileCoverage {
     pub branch: ::prost::alloc::string::String,
     #[prost(string, optional, tag = "14")]
     pub pull_request_number: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "16")]
+    pub branches: ::prost::alloc::vec::Vec<BranchCoverage>,
+    #[prost(message, repeated, tag = "17")]
+    pub functions: ::prost::alloc::vec::Vec<FunctionCoverage>,
+    #[prost(message, optional, tag = "18")]
+    pub branch_summary: ::core::option::Option<CoverageSummary>,
+    #[prost(message, optional, tag = "19")]
+    pub function_summary: ::core::option::Option<CoverageSummary>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct CoverageSummary {
@@ -146,5 +154,25 @@ pub struct CoverageSummary {
     #[prost(int64, tag = "4")]
     pub total: i64,
 }
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BranchCoverage {
+    #[prost(uint32, tag = "1")]
+    pub line: u32,
+    #[prost(uint32, tag = "2")]
+    pub block: u32,
+    #[prost(uint32, tag = "3")]
+    pub branch: u32,
+    #[prost(int64, tag = "4")]
+    pub hits: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FunctionCoverage {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub line: u32,
+    #[prost(int64, tag = "3")]
+    pub hits: i64,
+}
 include!("qlty.tests.v1.serde.rs");
 // @@protoc_insertion_point(module)
@@ -1,4 +1,156 @@
 // @generated
+impl serde::Serialize for BranchCoverage {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.line != 0 {
+            len += 1;
+        }
+        if self.block != 0 {
+            len += 1;
+        }
+        if self.branch != 0 {
+            len += 1;
+        }
+        if self.hits != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("qlty.tests.v1.BranchCoverage", len)?;
+        if self.line != 0 {
+            struct_ser.serialize_field("line", &self.line)?;
+        }
+        if self.block != 0 {
+            struct_ser.serialize_field("block", &self.block)?;
+        }
+        if self.branch != 0 {
+            struct_ser.serialize_field("branch", &self.branch)?;
+        }
+        if self.hits != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("hits", ToString::to_string(&self.hits).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for BranchCoverage {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "line",
+            "block",
+            "branch",
+            "hits",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Line,
+            Block,
+            Branch,
+            Hits,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "line" => Ok(GeneratedField::Line),
+                            "block" => Ok(GeneratedField::Block),
+                            "branch" => Ok(GeneratedField::Branch),
+                            "hits" => Ok(GeneratedField::Hits),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = BranchCoverage;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct qlty.tests.v1.BranchCoverage")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<BranchCoverage, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut line__ = None;
+                let mut block__ = None;
+                let mut branch__ = None;
+                let mut hits__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Line => {
+                            if line__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("line"));
+                            }
+                            line__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Block => {
+                            if block__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("block"));
+                            }
+                            block__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Branch => {
+                            if branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("branch"));
+                            }
+                            branch__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Hits => {
+                            if hits__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("hits"));
+                            }
+                            hits__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(BranchCoverage {
+                    line: line__.unwrap_or_default(),
+                    block: block__.unwrap_or_default(),
+                    branch: branch__.unwrap_or_default(),
+                    hits: hits__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("qlty.tests.v1.BranchCoverage", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for CoverageMetadata {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -940,6 +1092,18 @@ impl serde::Serialize for FileCoverage {
         if self.pull_request_number.is_some() {
             len += 1;
         }
+        if !self.branches.is_empty() {
+            len += 1;
+        }
+        if !self.functions.is_empty() {
+            len += 1;
+        }
+        if self.branch_summary.is_some() {
+            len += 1;
+        }
+        if self.function_summary.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("qlty.tests.v1.FileCoverage", len)?;
         if !self.upload_id.is_empty() {
             struct_ser.serialize_field("uploadId", &self.upload_id)?;
@@ -983,6 +1147,18 @@ impl serde::Serialize for FileCoverage {
         if let Some(v) = self.pull_request_number.as_ref() {
             struct_ser.serialize_field("pullRequestNumber", v)?;
         }
+        if !self.branches.is_empty() {
+            struct_ser.serialize_field("branches", &self.branches)?;
+        }
+        if !self.functions.is_empty() {
+            struct_ser.serialize_field("functions", &self.functions)?;
+        }
+        if let Some(v) = self.branch_summary.as_ref() {
+            struct_ser.serialize_field("branchSummary", v)?;
+        }
+        if let Some(v) = self.function_summary.as_ref() {
+            struct_ser.serialize_field("functionSummary", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -1016,6 +1192,12 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
             "branch",
             "pull_request_number",
             "pullRequestNumber",
+            "branches",
+            "functions",
+            "branch_summary",
+            "branchSummary",
+            "function_summary",
+            "functionSummary",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1034,6 +1216,10 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
             UploadedAt,
             Branch,
             PullRequestNumber,
+            Branches,
+            Functions,
+            BranchSummary,
+            FunctionSummary,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1069,6 +1255,10 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                             "uploadedAt" | "uploaded_at" => Ok(GeneratedField::UploadedAt),
                             "branch" => Ok(GeneratedField::Branch),
                             "pullRequestNumber" | "pull_request_number" => Ok(GeneratedField::PullRequestNumber),
+                            "branches" => Ok(GeneratedField::Branches),
+                            "functions" => Ok(GeneratedField::Functions),
+                            "branchSummary" | "branch_summary" => Ok(GeneratedField::BranchSummary),
+                            "functionSummary" | "function_summary" => Ok(GeneratedField::FunctionSummary),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1102,6 +1292,10 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                 let mut uploaded_at__ = None;
                 let mut branch__ = None;
                 let mut pull_request_number__ = None;
+                let mut branches__ = None;
+                let mut functions__ = None;
+                let mut branch_summary__ = None;
+                let mut function_summary__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::UploadId => {
@@ -1191,6 +1385,30 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                             }
                             pull_request_number__ = map_.next_value()?;
                         }
+                        GeneratedField::Branches => {
+                            if branches__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("branches"));
+                            }
+                            branches__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Functions => {
+                            if functions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("functions"));
+                            }
+                            functions__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::BranchSummary => {
+                            if branch_summary__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("branchSummary"));
+                            }
+                            branch_summary__ = map_.next_value()?;
+                        }
+                        GeneratedField::FunctionSummary => {
+                            if function_summary__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("functionSummary"));
+                            }
+                            function_summary__ = map_.next_value()?;
+                        }
                     }
                 }
                 Ok(FileCoverage {
@@ -1208,12 +1426,147 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                     uploaded_at: uploaded_at__,
                     branch: branch__.unwrap_or_default(),
                     pull_request_number: pull_request_number__,
+                    branches: branches__.unwrap_or_default(),
+                    functions: functions__.unwrap_or_default(),
+                    branch_summary: branch_summary__,
+                    function_summary: function_summary__,
                 })
             }
         }
         deserializer.deserialize_struct("qlty.tests.v1.FileCoverage", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for FunctionCoverage {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if self.line != 0 {
+            len += 1;
+        }
+        if self.hits != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("qlty.tests.v1.FunctionCoverage", len)?;
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if self.line != 0 {
+            struct_ser.serialize_field("line", &self.line)?;
+        }
+        if self.hits != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("hits", ToString::to_string(&self.hits).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for FunctionCoverage {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "name",
+            "line",
+            "hits",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Name,
+            Line,
+            Hits,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "name" => Ok(GeneratedField::Name),
+                            "line" => Ok(GeneratedField::Line),
+                            "hits" => Ok(GeneratedField::Hits),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = FunctionCoverage;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct qlty.tests.v1.FunctionCoverage")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<FunctionCoverage, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut name__ = None;
+                let mut line__ = None;
+                let mut hits__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Name => {
+                            if name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Line => {
+                            if line__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("line"));
+                            }
+                            line__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Hits => {
+                            if hits__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("hits"));
+                            }
+                            hits__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(FunctionCoverage {
+                    name: name__.unwrap_or_default(),
+                    line: line__.unwrap_or_default(),
+                    hits: hits__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("qlty.tests.v1.FunctionCoverage", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for ReportFile {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>